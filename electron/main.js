@@ -1,4 +1,4 @@
-const { app, Tray, Menu, shell, nativeImage } = require("electron");
+const { app, Tray, Menu, Notification, shell, nativeImage } = require("electron");
 const { spawn } = require("child_process");
 const path = require("path");
 const fs = require("fs");
@@ -51,6 +51,7 @@ const BROWSER_URL_BASE = "http://reelname.localhost";
 const HEALTH_CHECK_INTERVAL = 500;
 const HEALTH_CHECK_MAX_ATTEMPTS = 30;
 const SHUTDOWN_TIMEOUT = 5000;
+const TRAY_POLL_INTERVAL = 10000;
 
 // ── Single instance lock ───────────────────────────────
 
@@ -65,6 +66,10 @@ let tray = null;
 let serverProcess = null;
 let serverPort = DEFAULT_PORT;
 let serverRunning = false;
+let trayPollTimer = null;
+let trayBusyLabel = null;
+let pendingReviewCount = null;
+let trayDestinations = [];
 
 // Project root: electron/main.js lives one level inside the project
 const PROJECT_ROOT = path.join(__dirname, "..");
@@ -197,6 +202,7 @@ async function startServer() {
     console.log(`Server exited: code=${code}, signal=${signal}`);
     serverProcess = null;
     serverRunning = false;
+    stopTrayPolling();
     updateTrayMenu();
     if (code !== 0 && code !== null) {
       console.error("Server stopped unexpectedly");
@@ -210,6 +216,7 @@ async function startServer() {
     serverRunning = true;
     console.log(`Server ready on port ${serverPort}`);
     updateTrayMenu();
+    startTrayPolling();
   } else {
     console.error("Server failed to start within timeout");
     stopServer();
@@ -225,6 +232,7 @@ function stopServer() {
   const proc = serverProcess;
   serverProcess = null;
   serverRunning = false;
+  stopTrayPolling();
 
   // Graceful shutdown
   if (process.platform === "win32") {
@@ -253,6 +261,153 @@ async function restartServer() {
   if (ready) openBrowser();
 }
 
+// ── Server API ─────────────────────────────────────────
+
+function apiRequest(method, apiPath, body) {
+  return new Promise((resolve, reject) => {
+    const payload = body ? JSON.stringify(body) : null;
+    const req = http.request(
+      {
+        hostname: HOSTNAME,
+        port: serverPort,
+        path: apiPath,
+        method,
+        headers: payload
+          ? {
+              "Content-Type": "application/json",
+              "Content-Length": Buffer.byteLength(payload),
+            }
+          : {},
+      },
+      (res) => {
+        let data = "";
+        res.setEncoding("utf-8");
+        res.on("data", (chunk) => (data += chunk));
+        res.on("end", () => {
+          try {
+            resolve(JSON.parse(data));
+          } catch (err) {
+            reject(err);
+          }
+        });
+      }
+    );
+    req.on("error", reject);
+    if (payload) req.write(payload);
+    req.end();
+  });
+}
+
+// ── Tray actions ───────────────────────────────────────
+
+async function refreshTrayState() {
+  if (!serverRunning) return;
+  try {
+    const [matched, ambiguous, dests] = await Promise.all([
+      apiRequest("GET", "/api/groups?status=matched&limit=1"),
+      apiRequest("GET", "/api/groups?status=ambiguous&limit=1"),
+      apiRequest("GET", "/api/destinations"),
+    ]);
+    const count = (matched.total || 0) + (ambiguous.total || 0);
+    const destsChanged =
+      JSON.stringify(dests.map((d) => [d.id, d.name])) !==
+      JSON.stringify(trayDestinations.map((d) => [d.id, d.name]));
+    if (count !== pendingReviewCount || destsChanged) {
+      pendingReviewCount = count;
+      trayDestinations = dests;
+      updateTrayMenu();
+    }
+  } catch (err) {
+    console.warn("Tray poll failed:", err);
+  }
+}
+
+function startTrayPolling() {
+  stopTrayPolling();
+  refreshTrayState();
+  trayPollTimer = setInterval(refreshTrayState, TRAY_POLL_INTERVAL);
+}
+
+function stopTrayPolling() {
+  if (trayPollTimer) {
+    clearInterval(trayPollTimer);
+    trayPollTimer = null;
+  }
+  pendingReviewCount = null;
+}
+
+async function runTrayAction(label, action) {
+  if (trayBusyLabel) return;
+  trayBusyLabel = label;
+  updateTrayMenu();
+  try {
+    await action();
+  } catch (err) {
+    console.error(`${label} failed:`, err);
+  }
+  trayBusyLabel = null;
+  updateTrayMenu();
+  refreshTrayState();
+}
+
+async function showNotification(title, body) {
+  if (!Notification.isSupported()) return;
+  try {
+    const settings = await apiRequest("GET", "/api/settings");
+    if (settings.desktop_notifications !== "true") return;
+  } catch {
+    return;
+  }
+  new Notification({ title, body }).show();
+}
+
+function scanNow() {
+  return runTrayAction("Scanning...", async () => {
+    const result = await apiRequest("POST", "/api/scan", {});
+    if (result.error) {
+      console.error(`Tray scan failed: ${result.error}`);
+      showNotification("Scan failed", result.error);
+      return;
+    }
+    console.log(
+      `Tray scan: added ${result.addedGroups} groups, matched ${result.matched}, ambiguous ${result.ambiguous}`
+    );
+    const attempted = (result.matched || 0) + (result.ambiguous || 0);
+    showNotification(
+      "Scan complete",
+      attempted > 0
+        ? `Matched ${result.matched} of ${attempted} groups`
+        : `Added ${result.addedGroups || 0} groups`
+    );
+  });
+}
+
+function startPendingTransfers(destinationId) {
+  return runTrayAction("Starting transfers...", async () => {
+    // The groups API caps a page at 1000, so walk every page
+    const groupIds = [];
+    for (let page = 1; ; page++) {
+      const data = await apiRequest("GET", `/api/groups?status=confirmed&limit=1000&page=${page}`);
+      const ids = (data.groups || []).map((g) => g.id);
+      groupIds.push(...ids);
+      if (ids.length === 0 || groupIds.length >= (data.total || 0)) break;
+    }
+    if (groupIds.length === 0) {
+      console.log("Tray transfer: no confirmed groups pending");
+      return;
+    }
+    const result = await apiRequest("POST", "/api/transfer", {
+      groupIds,
+      destinationId,
+    });
+    if (result.error) {
+      console.error(`Tray transfer failed: ${result.error}`);
+    } else {
+      console.log(`Tray transfer: queued ${result.queued} jobs`);
+    }
+  });
+}
+
 // ── Browser ────────────────────────────────────────────
 
 function openBrowser() {
@@ -306,20 +461,43 @@ function updateTrayMenu(statusOverride) {
   let statusLabel;
   if (statusOverride) {
     statusLabel = statusOverride;
+  } else if (trayBusyLabel) {
+    statusLabel = trayBusyLabel;
   } else if (serverRunning) {
     statusLabel = `Running on port ${serverPort}`;
   } else {
     statusLabel = "Stopped";
   }
 
+  const actionsEnabled = serverRunning && !trayBusyLabel && !statusOverride;
+  const reviewLabel =
+    pendingReviewCount == null
+      ? null
+      : `${pendingReviewCount} group${pendingReviewCount !== 1 ? "s" : ""} awaiting review`;
+
   const menu = Menu.buildFromTemplate([
     { label: `${APP_NAME} — ${statusLabel}`, enabled: false },
+    ...(reviewLabel ? [{ label: reviewLabel, enabled: false }] : []),
     { type: "separator" },
     {
       label: "Open in Browser",
       enabled: serverRunning,
       click: openBrowser,
     },
+    {
+      label: "Scan Now",
+      enabled: actionsEnabled,
+      click: scanNow,
+    },
+    {
+      label: "Start Pending Transfers",
+      enabled: actionsEnabled && trayDestinations.length > 0,
+      submenu: trayDestinations.map((d) => ({
+        label: d.name,
+        click: () => startPendingTransfers(d.id),
+      })),
+    },
+    { type: "separator" },
     {
       label: "Restart Server",
       enabled: !statusOverride, // Disabled during transitions
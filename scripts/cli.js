@@ -0,0 +1,249 @@
+#!/usr/bin/env node
+
+/**
+ * Headless command line for servers without a browser.
+ * Drives a running ReelName server (`pnpm start`) over its HTTP API, so scans,
+ * matches and transfers go through the same code and database as the UI.
+ *
+ *   node scripts/cli.js scan [--path <dir>]...
+ *   node scripts/cli.js match [--threshold <0-1>]
+ *   node scripts/cli.js transfer --destination <id> [--group <id>]... [--include-matched]
+ *                                [--include-completed] [--dry-run]
+ *
+ * The server must already be running; the CLI never opens the database itself.
+ * Set REELNAME_URL to reach a server other than http://localhost:3000.
+ */
+
+const BASE_URL = (process.env.REELNAME_URL || "http://localhost:3000").replace(/\/+$/, "");
+const POLL_MS = 1000;
+
+const USAGE = `Usage: node scripts/cli.js <command> [options]
+
+Requires a running ReelName server (pnpm start). The CLI sends every command
+to it over HTTP and never opens the database itself.
+
+Commands:
+  scan                 Scan the configured source folders (auto-matches if a TMDB key is set)
+    --path <dir>       Scan this folder instead of the configured ones (repeatable)
+
+  match                Match every scanned group against TMDB
+    --threshold <n>    Auto-match confidence (0-1) for this run instead of the setting
+
+  transfer             Transfer confirmed groups to a destination
+    --destination <id> Destination id (required)
+    --group <id>       Only transfer this group (repeatable; default: every confirmed group)
+    --include-matched  Also send matched files that haven't been confirmed
+    --include-completed
+                       Also send files already transferred (e.g. to a second destination)
+    --dry-run          Report what would be transferred and whether it fits, then exit
+
+Environment:
+  REELNAME_URL         Server address (default: http://localhost:3000)`;
+
+function fail(message) {
+  console.error(`Error: ${message}`);
+  process.exit(1);
+}
+
+/** Split argv into repeatable `--flag value` options plus boolean switches. */
+function parseArgs(argv, { values = [], switches = [] }) {
+  const opts = {};
+  for (let i = 0; i < argv.length; i++) {
+    const name = argv[i].replace(/^--/, "");
+    if (switches.includes(name)) {
+      opts[name] = true;
+    } else if (values.includes(name)) {
+      const value = argv[++i];
+      if (value === undefined) fail(`--${name} needs a value`);
+      opts[name] = [...(opts[name] || []), value];
+    } else {
+      fail(`Unknown option ${argv[i]}\n\n${USAGE}`);
+    }
+  }
+  return opts;
+}
+
+function parseId(value, flag) {
+  const id = parseInt(value, 10);
+  if (!Number.isInteger(id) || id < 1 || String(id) !== value) {
+    fail(`--${flag} must be a positive integer`);
+  }
+  return id;
+}
+
+async function api(path, init) {
+  let res;
+  try {
+    res = await fetch(`${BASE_URL}${path}`, {
+      ...init,
+      headers: { "Content-Type": "application/json" },
+    });
+  } catch {
+    fail(`Could not reach ReelName at ${BASE_URL}. Is the server running?`);
+  }
+  const body = await res.json().catch(() => ({}));
+  if (!res.ok || body.error) fail(body.error || `${path} returned ${res.status}`);
+  return body;
+}
+
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+function formatSize(bytes) {
+  const units = ["B", "KB", "MB", "GB", "TB"];
+  let size = bytes;
+  let unit = 0;
+  while (size >= 1024 && unit < units.length - 1) {
+    size /= 1024;
+    unit++;
+  }
+  return `${size.toFixed(unit === 0 ? 0 : 1)} ${units[unit]}`;
+}
+
+/** Print match progress until the request resolves. */
+async function withMatchProgress(promise) {
+  let done = false;
+  promise.finally(() => (done = true)).catch(() => {});
+  let last = "";
+  while (!done) {
+    await sleep(POLL_MS);
+    if (done) break;
+    const progress = await fetch(`${BASE_URL}/api/match/progress`)
+      .then((r) => r.json())
+      .catch(() => null);
+    if (!progress?.running) continue;
+    const line = `Matching ${progress.completed}/${progress.total}` +
+      (progress.currentTitle ? ` — ${progress.currentTitle}` : "");
+    if (line !== last) console.log(line);
+    last = line;
+  }
+  return promise;
+}
+
+function printMatchResult(result) {
+  let line = `Matched ${result.matched}, ambiguous ${result.ambiguous}, no results ${result.unmatched}.`;
+  if (result.matchCancelled || result.cancelled) line += " (cancelled)";
+  console.log(line);
+}
+
+async function scan(argv) {
+  const opts = parseArgs(argv, { values: ["path"] });
+  console.log("Scanning...");
+  const result = await withMatchProgress(
+    api("/api/scan", {
+      method: "POST",
+      body: JSON.stringify(opts.path ? { path: opts.path.join("\n") } : {}),
+    })
+  );
+
+  console.log(
+    `Found ${result.scannedGroups} groups: added ${result.addedGroups} (${result.addedFiles} files), ` +
+      `${result.skippedGroups} already queued, ${result.missingGroups} missing on disk.`
+  );
+  for (const skipped of result.skippedPaths || []) {
+    console.log(`Skipped ${skipped.path} (${skipped.reason})`);
+  }
+  if (result.matchError) {
+    console.log(result.matchError);
+  } else {
+    printMatchResult(result);
+  }
+}
+
+async function match(argv) {
+  const opts = parseArgs(argv, { values: ["threshold"] });
+  const body = {};
+  if (opts.threshold) {
+    const threshold = parseFloat(opts.threshold[0]);
+    if (!Number.isFinite(threshold) || threshold < 0 || threshold > 1) {
+      fail("--threshold must be a number between 0 and 1");
+    }
+    body.threshold = threshold;
+  }
+
+  console.log("Matching...");
+  const result = await withMatchProgress(
+    api("/api/match", { method: "POST", body: JSON.stringify(body) })
+  );
+  printMatchResult(result);
+}
+
+async function groupIdsWithStatus(status) {
+  const ids = [];
+  for (let page = 1; ; page++) {
+    const result = await api(`/api/groups?status=${status}&limit=1000&page=${page}`);
+    ids.push(...result.groups.map((g) => g.id));
+    if (ids.length >= result.total || result.groups.length === 0) return ids;
+  }
+}
+
+async function transfer(argv) {
+  const opts = parseArgs(argv, {
+    values: ["destination", "group"],
+    switches: ["dry-run", "include-matched", "include-completed"],
+  });
+  if (!opts.destination) fail(`--destination is required\n\n${USAGE}`);
+  const destinationId = parseId(opts.destination[0], "destination");
+  const statuses = ["confirmed"];
+  if (opts["include-matched"]) statuses.push("matched");
+  if (opts["include-completed"]) statuses.push("completed");
+  const groupIds = opts.group
+    ? opts.group.map((id) => parseId(id, "group"))
+    : (await Promise.all(statuses.map(groupIdsWithStatus))).flat();
+  if (groupIds.length === 0) fail(`No ${statuses.join(" or ")} groups to transfer`);
+
+  const preview = await api("/api/transfer/preview", {
+    method: "POST",
+    body: JSON.stringify({ groupIds, destinationId, statuses }),
+  });
+  console.log(`${preview.jobCount} files from ${groupIds.length} groups. ${preview.summary}`);
+  if (opts["dry-run"]) return;
+  if (!preview.fits) process.exit(1);
+
+  const queued = await api("/api/transfer", {
+    method: "POST",
+    body: JSON.stringify({ groupIds, destinationId, statuses }),
+  });
+  console.log(`Queued ${queued.queued} files.`);
+
+  const reported = new Set();
+  let completed = 0;
+  let failed = 0;
+  for (;;) {
+    await sleep(POLL_MS);
+    const status = await api("/api/transfer/status");
+    for (const job of status.jobs) {
+      if ((job.status === "completed" || job.status === "failed") && !reported.has(job.id)) {
+        reported.add(job.id);
+        if (job.status === "completed") completed++;
+        else failed++;
+        console.log(
+          job.status === "completed"
+            ? `Done    ${job.fileName} → ${job.destinationPath}`
+            : `Failed  ${job.fileName}: ${job.transferError}`
+        );
+      }
+    }
+    const current = status.jobs.find((j) => j.status === "transferring");
+    if (current) {
+      console.log(
+        `        ${current.fileName} ${Math.round((current.transferProgress ?? 0) * 100)}%` +
+          (current.bytesPerSecond ? ` at ${formatSize(current.bytesPerSecond)}/s` : "")
+      );
+    }
+    if (!status.active) break;
+  }
+
+  console.log(`Transfer finished: ${completed} completed, ${failed} failed.`);
+  if (failed > 0) process.exit(1);
+}
+
+const COMMANDS = { scan, match, transfer };
+
+const [command, ...rest] = process.argv.slice(2);
+if (!command || command === "--help" || command === "-h") {
+  console.log(USAGE);
+} else if (!COMMANDS[command]) {
+  fail(`Unknown command "${command}"\n\n${USAGE}`);
+} else {
+  COMMANDS[command](rest).catch((err) => fail(err.message || String(err)));
+}
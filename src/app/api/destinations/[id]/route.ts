@@ -2,6 +2,9 @@ import { NextResponse } from "next/server";
 import { db } from "@/lib/db";
 import { destinations } from "@/lib/db/schema";
 import { eq } from "drizzle-orm";
+import { checkLocalDestination } from "@/lib/transfer";
+import { parsePort, validateDestinationFields } from "@/lib/destinations";
+import { encryptSecret, withoutSecrets } from "@/lib/secrets";
 
 export async function PATCH(
   request: Request,
@@ -11,6 +14,29 @@ export async function PATCH(
   const destId = parseInt(id, 10);
   const body = await request.json();
 
+  const existing = db.select().from(destinations).where(eq(destinations.id, destId)).get();
+  if (!existing) {
+    return NextResponse.json({ error: "Destination not found" }, { status: 404 });
+  }
+
+  // Validate the destination as it will be after the update
+  const merged = { ...existing, ...body };
+  const errors = Object.values(validateDestinationFields(merged));
+  if (errors.length > 0) {
+    return NextResponse.json({ error: errors[0] }, { status: 400 });
+  }
+  if ("sshPort" in body) body.sshPort = parsePort(body.sshPort);
+  if (merged.type === "local" && ("basePath" in body || "type" in body)) {
+    const check = checkLocalDestination(merged.basePath);
+    if (!check.ok) {
+      return NextResponse.json({ error: check.error }, { status: 400 });
+    }
+  }
+
+  if ("sshKeyPassphrase" in body) {
+    body.sshKeyPassphrase = body.sshKeyPassphrase ? encryptSecret(body.sshKeyPassphrase) : null;
+  }
+
   const updated = db
     .update(destinations)
     .set(body)
@@ -22,7 +48,7 @@ export async function PATCH(
     return NextResponse.json({ error: "Destination not found" }, { status: 404 });
   }
 
-  return NextResponse.json(updated);
+  return NextResponse.json(withoutSecrets(updated));
 }
 
 export async function DELETE(
@@ -1,14 +1,33 @@
 import { NextResponse } from "next/server";
 import { db } from "@/lib/db";
 import { destinations } from "@/lib/db/schema";
+import { checkLocalDestination } from "@/lib/transfer";
+import { parsePort, validateDestinationFields } from "@/lib/destinations";
+import { encryptSecret, withoutSecrets } from "@/lib/secrets";
 
 export async function GET() {
   const all = db.select().from(destinations).all();
-  return NextResponse.json(all);
+  return NextResponse.json(all.map(withoutSecrets));
 }
 
 export async function POST(request: Request) {
   const body = await request.json();
+
+  const errors = Object.values(validateDestinationFields(body));
+  if (errors.length > 0) {
+    return NextResponse.json({ error: errors[0] }, { status: 400 });
+  }
+  if (body.type === "ssh") body.sshPort = parsePort(body.sshPort);
+
+  if (body.type === "local") {
+    const check = checkLocalDestination(body.basePath || "");
+    if (!check.ok) {
+      return NextResponse.json({ error: check.error }, { status: 400 });
+    }
+  }
+
+  body.sshKeyPassphrase = body.sshKeyPassphrase ? encryptSecret(body.sshKeyPassphrase) : null;
+
   const inserted = db.insert(destinations).values(body).returning().get();
-  return NextResponse.json(inserted);
+  return NextResponse.json(withoutSecrets(inserted));
 }
@@ -1,10 +1,18 @@
 import { NextResponse } from "next/server";
 import fs from "fs";
 import { Client as SSHClient } from "ssh2";
+import { SshStageError, classifySshError } from "@/lib/ssh";
+import { parsePort } from "@/lib/destinations";
+import { db } from "@/lib/db";
+import { destinations } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+import { decryptSecret, registerSecret } from "@/lib/secrets";
 
 export async function POST(request: Request) {
   const body = await request.json();
-  const { sshHost, sshPort, sshUser, sshKeyPath, sshKeyPassphrase, basePath } = body;
+  const { sshHost, sshPort, sshUser, sshKeyPath, basePath, destinationId } = body;
+  let { sshKeyPassphrase } = body;
+  registerSecret(sshKeyPassphrase);
 
   if (!sshHost || !sshUser) {
     return NextResponse.json(
@@ -12,14 +20,39 @@ export async function POST(request: Request) {
       { status: 400 }
     );
   }
+  const port = sshPort == null || sshPort === "" ? 22 : parsePort(sshPort);
+  if (port === null) {
+    return NextResponse.json(
+      { ok: false, error: "Port must be a number from 1 to 65535" },
+      { status: 400 }
+    );
+  }
 
   const connectConfig: Record<string, unknown> = {
     host: sshHost,
-    port: sshPort || 22,
+    port,
     username: sshUser,
     readyTimeout: 10000,
   };
 
+  // Editing a saved destination: the form leaves the passphrase blank to keep
+  // the stored one, so test with that
+  if (!sshKeyPassphrase && destinationId) {
+    const stored = db
+      .select({ passphrase: destinations.sshKeyPassphrase })
+      .from(destinations)
+      .where(eq(destinations.id, Number(destinationId)))
+      .get()?.passphrase;
+    try {
+      if (stored) sshKeyPassphrase = decryptSecret(stored);
+    } catch (err) {
+      return NextResponse.json(
+        { ok: false, stage: "auth", error: (err as Error).message },
+        { status: 400 }
+      );
+    }
+  }
+
   if (sshKeyPath) {
     try {
       connectConfig.privateKey = fs.readFileSync(sshKeyPath);
@@ -28,7 +61,7 @@ export async function POST(request: Request) {
       }
     } catch {
       return NextResponse.json(
-        { ok: false, error: `Cannot read SSH key: ${sshKeyPath}` },
+        { ok: false, stage: "auth", error: `Cannot read SSH key: ${sshKeyPath}` },
         { status: 400 }
       );
     }
@@ -39,7 +72,7 @@ export async function POST(request: Request) {
       const conn = new SSHClient();
       const timeout = setTimeout(() => {
         conn.end();
-        reject(new Error("Connection timed out after 10s"));
+        reject(new SshStageError("connect", "timed out after 10s"));
       }, 12000);
 
       conn.on("ready", () => {
@@ -49,7 +82,7 @@ export async function POST(request: Request) {
             if (err) {
               clearTimeout(timeout);
               conn.end();
-              reject(new Error(`SFTP error: ${err.message}`));
+              reject(new SshStageError("path", `SFTP unavailable: ${err.message}`));
               return;
             }
             sftp.stat(basePath, (statErr) => {
@@ -57,7 +90,7 @@ export async function POST(request: Request) {
               conn.end();
               if (statErr) {
                 reject(
-                  new Error(`Base path not found on remote: ${basePath}`)
+                  new SshStageError("path", `base path not found on remote: ${basePath}`)
                 );
               } else {
                 resolve();
@@ -73,15 +106,21 @@ export async function POST(request: Request) {
 
       conn.on("error", (err) => {
         clearTimeout(timeout);
-        reject(err);
+        reject(classifySshError(err));
       });
 
-      conn.connect(connectConfig);
+      try {
+        conn.connect(connectConfig);
+      } catch (err) {
+        // ssh2 throws synchronously for unparseable keys / bad passphrases
+        clearTimeout(timeout);
+        reject(classifySshError(err));
+      }
     });
 
     return NextResponse.json({ ok: true });
   } catch (err) {
-    const message = err instanceof Error ? err.message : "Connection failed";
-    return NextResponse.json({ ok: false, error: message });
+    const failure = classifySshError(err);
+    return NextResponse.json({ ok: false, stage: failure.stage, error: failure.message });
   }
 }
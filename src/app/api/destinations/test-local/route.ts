@@ -0,0 +1,15 @@
+import { NextResponse } from "next/server";
+import { checkLocalDestination } from "@/lib/transfer";
+
+export async function POST(request: Request) {
+  const { basePath } = await request.json();
+
+  if (!basePath) {
+    return NextResponse.json(
+      { ok: false, error: "Base path is required" },
+      { status: 400 }
+    );
+  }
+
+  return NextResponse.json(checkLocalDestination(basePath));
+}
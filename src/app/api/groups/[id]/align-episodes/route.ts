@@ -0,0 +1,17 @@
+import { NextResponse } from "next/server";
+import { alignEpisodesByTitle } from "@/lib/matcher";
+
+export async function POST(
+  _request: Request,
+  { params }: { params: Promise<{ id: string }> }
+) {
+  const { id } = await params;
+
+  try {
+    const result = await alignEpisodesByTitle(parseInt(id, 10));
+    return NextResponse.json(result);
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Episode alignment failed";
+    return NextResponse.json({ error: message }, { status: 400 });
+  }
+}
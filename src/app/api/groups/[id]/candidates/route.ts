@@ -0,0 +1,40 @@
+import { NextResponse } from "next/server";
+import { db } from "@/lib/db";
+import { groups, matchCandidates } from "@/lib/db/schema";
+import { asc, eq } from "drizzle-orm";
+import { searchGroupCandidates } from "@/lib/matcher";
+
+const SEARCH_TYPES = ["movie", "tv", "unknown"] as const;
+
+// Re-run the group's TMDB search as a movie, a show, or either ("unknown"),
+// replacing its candidates. The group's own media type is only changed on confirm.
+export async function POST(
+  request: Request,
+  { params }: { params: Promise<{ id: string }> }
+) {
+  const { id } = await params;
+  const groupId = parseInt(id, 10);
+  const body = await request.json().catch(() => ({}));
+
+  const group = db.select().from(groups).where(eq(groups.id, groupId)).get();
+  if (!group) {
+    return NextResponse.json({ error: "Group not found" }, { status: 404 });
+  }
+
+  const mediaType = SEARCH_TYPES.includes(body.mediaType) ? body.mediaType : "unknown";
+
+  try {
+    await searchGroupCandidates(group, mediaType);
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Search failed";
+    return NextResponse.json({ error: message }, { status: 502 });
+  }
+
+  const candidates = db
+    .select()
+    .from(matchCandidates)
+    .where(eq(matchCandidates.groupId, groupId))
+    .orderBy(asc(matchCandidates.id))
+    .all();
+  return NextResponse.json({ candidates });
+}
@@ -0,0 +1,26 @@
+import { NextResponse } from "next/server";
+import { db } from "@/lib/db";
+import { groups } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+import { getLabelsForGroups, setGroupLabels } from "@/lib/labels";
+
+// PUT { labelIds } replaces the group's labels
+export async function PUT(
+  request: Request,
+  { params }: { params: Promise<{ id: string }> }
+) {
+  const { id } = await params;
+  const groupId = parseInt(id, 10);
+  const body = await request.json();
+
+  const group = db.select().from(groups).where(eq(groups.id, groupId)).get();
+  if (!group) {
+    return NextResponse.json({ error: "Group not found" }, { status: 404 });
+  }
+  if (!Array.isArray(body.labelIds) || !body.labelIds.every(Number.isInteger)) {
+    return NextResponse.json({ error: "labelIds must be an array of ids" }, { status: 400 });
+  }
+
+  setGroupLabels(groupId, body.labelIds);
+  return NextResponse.json({ labels: getLabelsForGroups([groupId]).get(groupId) || [] });
+}
@@ -0,0 +1,45 @@
+import { NextResponse } from "next/server";
+import { mergeGroups } from "@/lib/regroup";
+import { fetchEpisodeTitles } from "@/lib/matcher";
+import { createLogger } from "@/lib/log";
+
+const log = createLogger("merge");
+
+export async function POST(
+  request: Request,
+  { params }: { params: Promise<{ id: string }> }
+) {
+  const { id } = await params;
+  const body = await request.json().catch(() => ({}));
+  const sourceIds: unknown = body.sourceIds;
+  if (!Array.isArray(sourceIds) || sourceIds.length === 0 || !sourceIds.every(Number.isInteger)) {
+    return NextResponse.json({ error: "sourceIds must be a non-empty list of group ids" }, { status: 400 });
+  }
+
+  let result;
+  try {
+    result = mergeGroups(parseInt(id, 10), sourceIds as number[], body.force === true);
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Merge failed";
+    return NextResponse.json({ error: message }, { status: 400 });
+  }
+
+  if (result.conflicts) {
+    return NextResponse.json(
+      {
+        error: "Some groups are matched to a different title",
+        conflicts: result.conflicts.map((g) => ({ id: g.id, title: g.tmdbTitle || g.folderName })),
+      },
+      { status: 409 }
+    );
+  }
+
+  // Moved episodes need titles from the target's show
+  const { target } = result;
+  if (target.tmdbId && target.mediaType === "tv") {
+    await fetchEpisodeTitles(target.id, target.tmdbId).catch((err) => {
+      log.warn("Couldn't fetch episode titles after merge", { groupId: target.id, err });
+    });
+  }
+  return NextResponse.json(result);
+}
@@ -1,9 +1,11 @@
 import { NextResponse } from "next/server";
 import { db } from "@/lib/db";
 import { groups, jobs, matchCandidates, settings } from "@/lib/db/schema";
-import { eq } from "drizzle-orm";
-import { getEpisode } from "@/lib/tmdb";
+import { eq, and, asc, isNull } from "drizzle-orm";
+import { getEpisode, getMovieCollection } from "@/lib/tmdb";
 import { formatGroupedPath } from "@/lib/naming";
+import { trashGroups } from "@/lib/trash";
+import { getLabelsForGroups } from "@/lib/labels";
 
 export async function GET(
   _request: Request,
@@ -17,16 +19,22 @@ export async function GET(
     return NextResponse.json({ error: "Group not found" }, { status: 404 });
   }
 
+  // A trashed group shows everything it held; a live one hides trashed jobs
   const groupJobs = db
     .select()
     .from(jobs)
-    .where(eq(jobs.groupId, groupId))
+    .where(
+      group.deletedAt
+        ? eq(jobs.groupId, groupId)
+        : and(eq(jobs.groupId, groupId), isNull(jobs.deletedAt))
+    )
     .all();
 
   const candidates = db
     .select()
     .from(matchCandidates)
     .where(eq(matchCandidates.groupId, groupId))
+    .orderBy(asc(matchCandidates.id))
     .all();
 
   // Compute preview names if group has a TMDB match
@@ -39,6 +47,9 @@ export async function GET(
       naming_preset: settingsMap["naming_preset"] || "jellyfin",
       specials_folder_name: settingsMap["specials_folder_name"] || "Specials",
       extras_folder_name: settingsMap["extras_folder_name"] || "Extras",
+      use_collections: settingsMap["use_collections"],
+      season_pad_width: settingsMap["season_pad_width"],
+      episode_pad_width: settingsMap["episode_pad_width"],
     };
     jobsWithPreview = groupJobs.map((job) => ({
       ...job,
@@ -46,7 +57,8 @@ export async function GET(
     }));
   }
 
-  return NextResponse.json({ ...group, jobs: jobsWithPreview, candidates });
+  const labels = getLabelsForGroups([groupId]).get(groupId) || [];
+  return NextResponse.json({ ...group, jobs: jobsWithPreview, candidates, labels });
 }
 
 export async function PATCH(
@@ -67,7 +79,7 @@ export async function PATCH(
   const allowedFields = [
     "status", "mediaType", "parsedTitle", "parsedYear",
     "tmdbId", "tmdbTitle", "tmdbYear", "tmdbPosterPath",
-    "matchConfidence", "destinationId",
+    "matchConfidence", "destinationId", "episodeOrder", "locked",
   ];
 
   // Use camelCase keys directly — Drizzle .set() expects JS property names, not SQL column names
@@ -78,6 +90,24 @@ export async function PATCH(
     }
   }
 
+  // A new movie match brings its collection along; anything else clears it
+  if ("tmdbId" in body) {
+    const resolvedMediaType = body.mediaType || group.mediaType;
+    updates.tmdbCollection =
+      body.tmdbId && resolvedMediaType === "movie"
+        ? await getMovieCollection(body.tmdbId).catch(() => null)
+        : null;
+  }
+
+  if ("parsedTitle" in body || "parsedYear" in body) {
+    updates.titleEdited = true;
+  }
+
+  // Matching or resolving the group by hand settles any earlier failed attempt
+  if ("tmdbId" in body || "status" in body) {
+    updates.matchError = null;
+  }
+
   const updated = db
     .update(groups)
     .set(updates)
@@ -117,7 +147,7 @@ export async function PATCH(
   const groupJobs = db
     .select()
     .from(jobs)
-    .where(eq(jobs.groupId, groupId))
+    .where(and(eq(jobs.groupId, groupId), isNull(jobs.deletedAt)))
     .all();
 
   // Compute preview names if group has a TMDB match
@@ -130,6 +160,9 @@ export async function PATCH(
       naming_preset: settingsMap["naming_preset"] || "jellyfin",
       specials_folder_name: settingsMap["specials_folder_name"] || "Specials",
       extras_folder_name: settingsMap["extras_folder_name"] || "Extras",
+      use_collections: settingsMap["use_collections"],
+      season_pad_width: settingsMap["season_pad_width"],
+      episode_pad_width: settingsMap["episode_pad_width"],
     };
     jobsWithPreview = groupJobs.map((job) => ({
       ...job,
@@ -137,7 +170,8 @@ export async function PATCH(
     }));
   }
 
-  return NextResponse.json({ ...updated, jobs: jobsWithPreview });
+  const labels = getLabelsForGroups([groupId]).get(groupId) || [];
+  return NextResponse.json({ ...updated, jobs: jobsWithPreview, labels });
 }
 
 async function fetchEpisodeTitlesForGroup(
@@ -153,6 +187,7 @@ async function fetchEpisodeTitlesForGroup(
   for (const job of groupJobs) {
     if (
       job.fileCategory === "extra" ||
+      job.fileCategory === "metadata" ||
       job.parsedSeason == null ||
       job.parsedEpisode == null
     ) {
@@ -183,8 +218,8 @@ export async function DELETE(
   const { id } = await params;
   const groupId = parseInt(id, 10);
 
-  // Cascade delete handles jobs and matchCandidates
-  db.delete(groups).where(eq(groups.id, groupId)).run();
+  // Soft delete — the group and its jobs move to the trash
+  trashGroups([groupId]);
 
   return NextResponse.json({ success: true });
 }
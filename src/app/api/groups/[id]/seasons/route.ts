@@ -2,7 +2,7 @@ import { NextResponse } from "next/server";
 import { db } from "@/lib/db";
 import { groups } from "@/lib/db/schema";
 import { eq } from "drizzle-orm";
-import { getShowSeasons, getSeason } from "@/lib/tmdb";
+import { getMetadataProvider, EPISODE_ORDERS, type EpisodeOrder } from "@/lib/metadata";
 
 export async function GET(
   request: Request,
@@ -25,19 +25,28 @@ export async function GET(
 
   const { searchParams } = new URL(request.url);
   const seasonParam = searchParams.get("season");
+  const provider = getMetadataProvider(searchParams.get("provider"));
+  const orderParam = searchParams.get("order") as EpisodeOrder | null;
+  const order =
+    orderParam && EPISODE_ORDERS.includes(orderParam) ? orderParam : group.episodeOrder;
 
-  if (seasonParam != null) {
-    const seasonNumber = parseInt(seasonParam, 10);
-    const season = await getSeason(group.tmdbId, seasonNumber);
-    if (!season) {
-      return NextResponse.json(
-        { error: "Season not found" },
-        { status: 404 }
-      );
+  try {
+    if (seasonParam != null) {
+      const seasonNumber = parseInt(seasonParam, 10);
+      const season = await provider.getSeasonDetail(group.tmdbId, seasonNumber, order);
+      if (!season) {
+        return NextResponse.json(
+          { error: "Season not found" },
+          { status: 404 }
+        );
+      }
+      return NextResponse.json({ ...season, provider: provider.id, order });
     }
-    return NextResponse.json(season);
-  }
 
-  const seasons = await getShowSeasons(group.tmdbId);
-  return NextResponse.json({ seasons });
+    const seasons = await provider.getSeasons(group.tmdbId, order);
+    return NextResponse.json({ seasons, provider: provider.id, order });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Lookup failed";
+    return NextResponse.json({ error: message }, { status: 502 });
+  }
 }
@@ -0,0 +1,22 @@
+import { NextResponse } from "next/server";
+import { splitGroup } from "@/lib/regroup";
+
+export async function POST(
+  request: Request,
+  { params }: { params: Promise<{ id: string }> }
+) {
+  const { id } = await params;
+  const body = await request.json().catch(() => ({}));
+  const jobIds: unknown = body.jobIds;
+  if (!Array.isArray(jobIds) || jobIds.length === 0 || !jobIds.every(Number.isInteger)) {
+    return NextResponse.json({ error: "jobIds must be a non-empty list of file ids" }, { status: 400 });
+  }
+
+  try {
+    const group = splitGroup(parseInt(id, 10), jobIds as number[]);
+    return NextResponse.json({ group });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Split failed";
+    return NextResponse.json({ error: message }, { status: 400 });
+  }
+}
@@ -0,0 +1,23 @@
+import { NextResponse } from "next/server";
+import { findDuplicateGroups, mergeDuplicateGroups } from "@/lib/duplicates";
+
+// Missing groups that a moved folder's new group duplicates
+export async function GET() {
+  const pairs = findDuplicateGroups().map(({ stale, live, reason }) => ({
+    staleId: stale.id,
+    liveId: live.id,
+    reason,
+    title: stale.tmdbTitle || stale.parsedTitle || stale.folderName,
+    staleFolder: stale.folderPath,
+    liveFolder: live.folderPath,
+  }));
+  return NextResponse.json({ pairs });
+}
+
+export async function POST(request: Request) {
+  const body = await request.json().catch(() => ({}));
+  const staleIds = Array.isArray(body.staleIds) ? (body.staleIds as number[]) : undefined;
+
+  const merged = mergeDuplicateGroups(staleIds);
+  return NextResponse.json({ merged });
+}
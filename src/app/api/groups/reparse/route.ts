@@ -0,0 +1,41 @@
+import { NextResponse } from "next/server";
+import { maintenanceBlocker } from "@/lib/maintenance";
+import { reparseAll } from "@/lib/reparse";
+import { fetchEpisodeTitles } from "@/lib/matcher";
+import { db } from "@/lib/db";
+import { groups } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+import { createLogger } from "@/lib/log";
+
+const log = createLogger("reparse");
+
+export async function POST() {
+  const blocker = maintenanceBlocker();
+  if (blocker) {
+    return NextResponse.json({ error: blocker }, { status: 409 });
+  }
+
+  try {
+    const { renumberedGroupIds, ...result } = reparseAll();
+    log.info("Re-parsed library", result);
+
+    // Renumbered episodes lost their titles; fetch them again where TMDB allows
+    let titlesRefreshed = 0;
+    for (const groupId of renumberedGroupIds) {
+      const group = db.select().from(groups).where(eq(groups.id, groupId)).get();
+      if (!group?.tmdbId) continue;
+      try {
+        await fetchEpisodeTitles(groupId, group.tmdbId);
+        titlesRefreshed++;
+      } catch (err) {
+        log.warn("Couldn't refresh episode titles after re-parse", { groupId, err });
+        break;
+      }
+    }
+
+    return NextResponse.json({ ...result, renumbered: renumberedGroupIds.length, titlesRefreshed });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Re-parse failed";
+    return NextResponse.json({ error: message }, { status: 500 });
+  }
+}
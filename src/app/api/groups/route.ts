@@ -1,8 +1,22 @@
 import { NextRequest, NextResponse } from "next/server";
 import { db } from "@/lib/db";
 import { groups, jobs, settings } from "@/lib/db/schema";
-import { eq, like, sql, desc, asc } from "drizzle-orm";
+import { eq, like, sql, desc, asc, and, isNull, isNotNull } from "drizzle-orm";
 import { formatGroupedPath } from "@/lib/naming";
+import { getLabelsForGroups } from "@/lib/labels";
+
+const DEFAULT_PAGE_SIZE = 50;
+const MAX_PAGE_SIZE = 1000;
+
+// Explicit ?limit wins; otherwise fall back to the page_size setting
+function resolvePageSize(limitParam: string | null): number {
+  const raw =
+    limitParam ??
+    db.select().from(settings).where(eq(settings.key, "page_size")).get()?.value;
+  const size = parseInt(raw || "", 10);
+  if (!Number.isFinite(size) || size < 1) return DEFAULT_PAGE_SIZE;
+  return Math.min(size, MAX_PAGE_SIZE);
+}
 
 export async function GET(request: NextRequest) {
   const searchParams = request.nextUrl.searchParams;
@@ -11,10 +25,20 @@ export async function GET(request: NextRequest) {
   const search = searchParams.get("search");
   const sortBy = searchParams.get("sortBy") || "createdAt";
   const sortDir = searchParams.get("sortDir") || "desc";
-  const page = parseInt(searchParams.get("page") || "1", 10);
-  const limit = parseInt(searchParams.get("limit") || "50", 10);
+  const page = Math.max(1, parseInt(searchParams.get("page") || "1", 10) || 1);
+  const limit = resolvePageSize(searchParams.get("limit"));
+  const changed = searchParams.get("changed");
+  const trash = searchParams.get("trash") === "1";
+  const labelId = parseInt(searchParams.get("label") || "", 10);
+  const issue = searchParams.get("issue");
 
-  const conditions = [];
+  // The trash view lists trashed groups plus live groups holding trashed jobs
+  const trashedJobGroups = sql`${groups.id} IN (SELECT group_id FROM jobs WHERE deleted_at IS NOT NULL)`;
+  const conditions = [
+    trash
+      ? sql`(${isNotNull(groups.deletedAt)} OR ${trashedJobGroups})`
+      : isNull(groups.deletedAt),
+  ];
 
   if (status) {
     conditions.push(
@@ -29,14 +53,54 @@ export async function GET(request: NextRequest) {
   if (search) {
     conditions.push(like(groups.folderName, `%${search}%`));
   }
+  if (changed) {
+    // "new" = created by the most recent scan, "missing" = folder gone on disk
+    const lastScanAt =
+      db.select().from(settings).where(eq(settings.key, "last_scan_at")).get()?.value || "";
+    const isNew = lastScanAt ? sql`${groups.createdAt} >= ${lastScanAt}` : sql`0`;
+    const isMissing = isNotNull(groups.missingAt);
+    if (changed === "new") conditions.push(isNew);
+    else if (changed === "missing") conditions.push(isMissing);
+    else conditions.push(sql`(${isNew} OR ${isMissing})`);
+  }
+
+  if (Number.isInteger(labelId)) {
+    conditions.push(
+      sql`${groups.id} IN (SELECT group_id FROM group_labels WHERE label_id = ${labelId})`
+    );
+  }
+
+  if (issue === "unresolved" || issue === "no_title") {
+    // TV groups holding a live episode/special that can't be named fully
+    const missing =
+      issue === "unresolved"
+        ? sql`(${jobs.parsedSeason} IS NULL OR ${jobs.parsedEpisode} IS NULL)`
+        : sql`${jobs.tmdbEpisodeTitle} IS NULL`;
+    conditions.push(eq(groups.mediaType, "tv"));
+    conditions.push(
+      sql`${groups.id} IN (SELECT ${jobs.groupId} FROM ${jobs} WHERE ${jobs.deletedAt} IS NULL AND ${jobs.fileCategory} IN ('episode', 'special') AND ${missing})`
+    );
+  }
+
+  if (issue === "match_error") {
+    conditions.push(isNotNull(groups.matchError));
+  }
+
+  if (issue === "transfer_failed") {
+    conditions.push(
+      sql`${groups.id} IN (SELECT ${jobs.groupId} FROM ${jobs} WHERE ${jobs.deletedAt} IS NULL AND ${jobs.status} = 'failed')`
+    );
+  }
 
-  const where =
-    conditions.length > 0
-      ? sql`${sql.join(conditions, sql` AND `)}`
-      : undefined;
+  const where = sql`${sql.join(conditions, sql` AND `)}`;
+
+  // The displayed title: the TMDB title once matched, else the parsed one
+  const titleColumn = sql`COALESCE(${groups.tmdbTitle}, ${groups.parsedTitle})`;
 
   const sortColumn =
-    sortBy === "folderName"
+    sortBy === "title"
+      ? titleColumn
+      : sortBy === "folderName"
       ? groups.folderName
       : sortBy === "totalFileSize"
       ? groups.totalFileSize
@@ -51,12 +115,18 @@ export async function GET(request: NextRequest) {
       : groups.createdAt;
 
   const orderFn = sortDir === "asc" ? asc : desc;
+  // Groups without a title or confidence go last in either direction
+  const nullsLast =
+    sortBy === "title" || sortBy === "matchConfidence"
+      ? [sql`${sortColumn} IS NULL`]
+      : [];
+  const tieBreak = sortBy === "title" ? [asc(groups.folderName)] : [];
 
   const results = db
     .select()
     .from(groups)
     .where(where)
-    .orderBy(orderFn(sortColumn))
+    .orderBy(...nullsLast, orderFn(sortColumn), ...tieBreak)
     .limit(limit)
     .offset((page - 1) * limit)
     .all();
@@ -75,14 +145,26 @@ export async function GET(request: NextRequest) {
     naming_preset: settingsMap["naming_preset"] || "jellyfin",
     specials_folder_name: settingsMap["specials_folder_name"] || "Specials",
     extras_folder_name: settingsMap["extras_folder_name"] || "Extras",
+    use_collections: settingsMap["use_collections"],
+    season_pad_width: settingsMap["season_pad_width"],
+    episode_pad_width: settingsMap["episode_pad_width"],
   };
 
+  const labelsByGroup = getLabelsForGroups(results.map((g) => g.id));
+
   // Fetch jobs for each group
   const groupsWithJobs = results.map((group) => {
     const groupJobs = db
       .select()
       .from(jobs)
-      .where(eq(jobs.groupId, group.id))
+      .where(
+        and(
+          eq(jobs.groupId, group.id),
+          trash
+            ? group.deletedAt ? undefined : isNotNull(jobs.deletedAt)
+            : isNull(jobs.deletedAt)
+        )
+      )
       .all();
 
     // Compute preview names for groups with a TMDB match
@@ -96,6 +178,7 @@ export async function GET(request: NextRequest) {
     return {
       ...group,
       jobs: jobsWithPreview,
+      labels: labelsByGroup.get(group.id) || [],
     };
   });
 
@@ -0,0 +1,20 @@
+import { NextResponse } from "next/server";
+import { db } from "@/lib/db";
+import { jobs } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+import { getJobEvents } from "@/lib/transfer";
+
+export async function GET(
+  _request: Request,
+  { params }: { params: Promise<{ id: string }> }
+) {
+  const { id } = await params;
+  const jobId = parseInt(id, 10);
+
+  const job = db.select({ id: jobs.id }).from(jobs).where(eq(jobs.id, jobId)).get();
+  if (!job) {
+    return NextResponse.json({ error: "Job not found" }, { status: 404 });
+  }
+
+  return NextResponse.json(getJobEvents(jobId));
+}
@@ -2,6 +2,8 @@ import { NextResponse } from "next/server";
 import { db } from "@/lib/db";
 import { jobs, matchCandidates } from "@/lib/db/schema";
 import { eq } from "drizzle-orm";
+import { trashJobs } from "@/lib/trash";
+import { validateDestinationOverride } from "@/lib/naming";
 
 export async function GET(
   _request: Request,
@@ -39,12 +41,20 @@ export async function PATCH(
 
   const allowedFields = [
     "status", "mediaType", "parsedTitle", "parsedYear", "parsedSeason",
-    "parsedEpisode", "parsedQuality", "parsedCodec", "tmdbId", "tmdbTitle",
+    "parsedEpisode", "parsedQuality", "parsedCodec", "parsedHdr", "parsedPart", "tmdbId", "tmdbTitle",
     "tmdbYear", "tmdbPosterPath", "tmdbEpisodeTitle", "matchConfidence",
     "destinationId", "destinationPath", "transferProgress", "transferError",
-    "fileCategory",
+    "fileCategory", "extraType", "destinationOverride",
   ];
 
+  if (typeof body.destinationOverride === "string") {
+    const error = validateDestinationOverride(body.destinationOverride);
+    if (error) {
+      return NextResponse.json({ error }, { status: 400 });
+    }
+    body.destinationOverride = body.destinationOverride.trim();
+  }
+
   // Use camelCase keys directly — Drizzle .set() expects JS property names, not SQL column names
   const updates: Record<string, unknown> = { updatedAt: new Date().toISOString() };
   for (const field of allowedFields) {
@@ -52,6 +62,9 @@ export async function PATCH(
       updates[field] = body[field];
     }
   }
+  if ("parsedSeason" in body || "parsedEpisode" in body) {
+    updates.numbersEdited = true;
+  }
 
   const updated = db
     .update(jobs)
@@ -70,8 +83,7 @@ export async function DELETE(
   const { id } = await params;
   const jobId = parseInt(id, 10);
 
-  db.delete(matchCandidates).where(eq(matchCandidates.jobId, jobId)).run();
-  db.delete(jobs).where(eq(jobs.id, jobId)).run();
+  trashJobs([jobId]);
 
   return NextResponse.json({ success: true });
 }
@@ -1,16 +1,80 @@
 import { NextResponse } from "next/server";
 import { db } from "@/lib/db";
 import { jobs, groups, matchCandidates } from "@/lib/db/schema";
-import { eq, inArray } from "drizzle-orm";
+import { and, eq, inArray, isNull } from "drizzle-orm";
+import { matchAllGroups } from "@/lib/matcher";
+import { trashGroups, trashJobs, restoreGroups, restoreJobs, purgeTrash } from "@/lib/trash";
+
+type GroupStatus = typeof groups.status.enumValues[number];
+type JobStatus = typeof jobs.status.enumValues[number];
+
+/** Group and job statuses from before a skip, so the skip can be undone exactly. */
+interface StatusSnapshot {
+  groups: { id: number; status: GroupStatus; matchError: string | null }[];
+  jobs: { id: number; status: JobStatus }[];
+}
+
+function snapshotStatuses(groupIds: number[]): StatusSnapshot {
+  return {
+    groups: db
+      .select({ id: groups.id, status: groups.status, matchError: groups.matchError })
+      .from(groups)
+      .where(inArray(groups.id, groupIds))
+      .all(),
+    jobs: db
+      .select({ id: jobs.id, status: jobs.status })
+      .from(jobs)
+      .where(inArray(jobs.groupId, groupIds))
+      .all(),
+  };
+}
+
+/**
+ * Put each group and job back to its snapshot status. Only rows still skipped
+ * are touched, so anything changed since the skip is left alone.
+ */
+function restoreStatuses(snapshot: StatusSnapshot, now: string) {
+  for (const g of snapshot.groups) {
+    db.update(groups)
+      .set({ status: g.status, matchError: g.matchError, updatedAt: now })
+      .where(and(eq(groups.id, g.id), eq(groups.status, "skipped")))
+      .run();
+  }
+  for (const j of snapshot.jobs) {
+    db.update(jobs)
+      .set({ status: j.status, updatedAt: now })
+      .where(and(eq(jobs.id, j.id), eq(jobs.status, "skipped")))
+      .run();
+  }
+}
 
 export async function POST(request: Request) {
   const body = await request.json();
-  const { action, jobIds, groupIds } = body as {
-    action: "confirm" | "skip" | "delete" | "rematch";
-    jobIds?: number[];
-    groupIds?: number[];
+  let { jobIds, groupIds } = body as { jobIds?: number[]; groupIds?: number[] };
+  const { action, title, year, snapshot } = body as {
+    action:
+      | "confirm"
+      | "skip"
+      | "unskip"
+      | "delete"
+      | "restore"
+      | "purge"
+      | "rematch"
+      | "retitle";
+    title?: string;
+    year?: number | null;
+    // unskip only: the snapshot a skip returned
+    snapshot?: StatusSnapshot;
   };
 
+  if (action === "unskip") {
+    if (!snapshot || !Array.isArray(snapshot.groups) || !Array.isArray(snapshot.jobs)) {
+      return NextResponse.json({ error: "unskip requires a snapshot" }, { status: 400 });
+    }
+    restoreStatuses(snapshot, new Date().toISOString());
+    return NextResponse.json({ success: true, affected: snapshot.groups.length });
+  }
+
   if (!action || (!jobIds?.length && !groupIds?.length)) {
     return NextResponse.json(
       { error: "action and jobIds or groupIds are required" },
@@ -18,15 +82,63 @@ export async function POST(request: Request) {
     );
   }
 
+  if (action === "retitle" && (!title?.trim() || !groupIds?.length)) {
+    return NextResponse.json(
+      { error: "retitle requires groupIds and a title" },
+      { status: 400 }
+    );
+  }
+
+  // Locked groups (and their files) keep their match through rematches
+  let lockedSkipped = 0;
+  if (action === "rematch" || action === "retitle") {
+    const lockedIds = new Set(
+      db.select({ id: groups.id }).from(groups).where(eq(groups.locked, true)).all().map((g) => g.id)
+    );
+    if (groupIds?.length) {
+      const unlocked = groupIds.filter((id) => !lockedIds.has(id));
+      lockedSkipped += groupIds.length - unlocked.length;
+      groupIds = unlocked;
+    }
+    if (jobIds?.length && lockedIds.size > 0) {
+      const lockedJobIds = new Set(
+        db.select({ id: jobs.id })
+          .from(jobs)
+          .where(and(inArray(jobs.id, jobIds), inArray(jobs.groupId, [...lockedIds])))
+          .all()
+          .map((j) => j.id)
+      );
+      lockedSkipped += lockedJobIds.size;
+      jobIds = jobIds.filter((id) => !lockedJobIds.has(id));
+    }
+  }
+
+  // Groups with no TMDB match (e.g. a search that found nothing) have nothing
+  // to confirm yet
+  let unmatchedSkipped = 0;
+  if (action === "confirm" && groupIds?.length) {
+    const unmatchedIds = new Set(
+      db.select({ id: groups.id })
+        .from(groups)
+        .where(and(inArray(groups.id, groupIds), isNull(groups.tmdbId)))
+        .all()
+        .map((g) => g.id)
+    );
+    unmatchedSkipped = unmatchedIds.size;
+    groupIds = groupIds.filter((id) => !unmatchedIds.has(id));
+  }
+
   const now = new Date().toISOString();
   let affected = 0;
+  // Returned from a group skip for its Undo
+  const previous = action === "skip" && groupIds?.length ? snapshotStatuses(groupIds) : undefined;
 
   // Handle group-level actions
   if (groupIds?.length) {
     switch (action) {
       case "confirm":
         db.update(groups)
-          .set({ status: "confirmed", updatedAt: now })
+          .set({ status: "confirmed", matchError: null, updatedAt: now })
           .where(inArray(groups.id, groupIds))
           .run();
         for (const gid of groupIds) {
@@ -39,7 +151,7 @@ export async function POST(request: Request) {
 
       case "skip":
         db.update(groups)
-          .set({ status: "skipped", updatedAt: now })
+          .set({ status: "skipped", matchError: null, updatedAt: now })
           .where(inArray(groups.id, groupIds))
           .run();
         for (const gid of groupIds) {
@@ -51,10 +163,24 @@ export async function POST(request: Request) {
         break;
 
       case "delete":
-        // Cascade delete handles child jobs and candidates
-        db.delete(groups).where(inArray(groups.id, groupIds)).run();
+        trashGroups(groupIds);
         break;
 
+      case "restore":
+        restoreGroups(groupIds);
+        break;
+
+      case "purge":
+        purgeTrash(groupIds);
+        break;
+
+      case "retitle":
+        db.update(groups)
+          .set({ parsedTitle: title!.trim(), parsedYear: year ?? null, titleEdited: true, updatedAt: now })
+          .where(inArray(groups.id, groupIds))
+          .run();
+      // falls through — a retitle is a rematch under the new title
+
       case "rematch":
         db.update(groups)
           .set({
@@ -63,6 +189,7 @@ export async function POST(request: Request) {
             tmdbTitle: null,
             tmdbYear: null,
             tmdbPosterPath: null,
+            tmdbCollection: null,
             matchConfidence: null,
             updatedAt: now,
           })
@@ -104,10 +231,11 @@ export async function POST(request: Request) {
         break;
 
       case "delete":
-        for (const id of jobIds) {
-          db.delete(matchCandidates).where(eq(matchCandidates.jobId, id)).run();
-        }
-        db.delete(jobs).where(inArray(jobs.id, jobIds)).run();
+        trashJobs(jobIds);
+        break;
+
+      case "restore":
+        restoreJobs(jobIds);
         break;
 
       case "rematch":
@@ -132,5 +260,16 @@ export async function POST(request: Request) {
     affected += jobIds.length;
   }
 
-  return NextResponse.json({ success: true, affected });
+  // Retitled groups are matched right away so the override takes effect
+  if (action === "retitle" && groupIds?.length) {
+    try {
+      const result = await matchAllGroups(groupIds);
+      return NextResponse.json({ success: true, affected, lockedSkipped, ...result });
+    } catch (err) {
+      const matchError = err instanceof Error ? err.message : "Matching failed";
+      return NextResponse.json({ success: true, affected, lockedSkipped, matchError });
+    }
+  }
+
+  return NextResponse.json({ success: true, affected, lockedSkipped, unmatchedSkipped, previous });
 }
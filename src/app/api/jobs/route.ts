@@ -1,7 +1,7 @@
 import { NextRequest, NextResponse } from "next/server";
 import { db } from "@/lib/db";
 import { jobs } from "@/lib/db/schema";
-import { eq, like, sql, desc, asc } from "drizzle-orm";
+import { eq, like, sql, desc, asc, isNull } from "drizzle-orm";
 
 export async function GET(request: NextRequest) {
   const searchParams = request.nextUrl.searchParams;
@@ -13,7 +13,7 @@ export async function GET(request: NextRequest) {
   const page = parseInt(searchParams.get("page") || "1", 10);
   const limit = parseInt(searchParams.get("limit") || "50", 10);
 
-  const conditions = [];
+  const conditions = [isNull(jobs.deletedAt)];
 
   if (status) {
     conditions.push(eq(jobs.status, status as typeof jobs.status.enumValues[number]));
@@ -25,9 +25,7 @@ export async function GET(request: NextRequest) {
     conditions.push(like(jobs.fileName, `%${search}%`));
   }
 
-  const where = conditions.length > 0
-    ? sql`${sql.join(conditions, sql` AND `)}`
-    : undefined;
+  const where = sql`${sql.join(conditions, sql` AND `)}`;
 
   const sortColumn = sortBy === "fileName" ? jobs.fileName
     : sortBy === "fileSize" ? jobs.fileSize
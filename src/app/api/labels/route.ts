@@ -0,0 +1,22 @@
+import { NextResponse } from "next/server";
+import { db } from "@/lib/db";
+import { labels } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+import { listLabels, createLabel } from "@/lib/labels";
+
+export async function GET() {
+  return NextResponse.json(listLabels());
+}
+
+export async function POST(request: Request) {
+  const body = await request.json();
+  const name = typeof body.name === "string" ? body.name.trim() : "";
+  if (!name) {
+    return NextResponse.json({ error: "Label name is required" }, { status: 400 });
+  }
+
+  const existing = db.select().from(labels).where(eq(labels.name, name)).get();
+  if (existing) return NextResponse.json(existing);
+
+  return NextResponse.json(createLabel(name, body.color), { status: 201 });
+}
@@ -0,0 +1,27 @@
+import { NextResponse } from "next/server";
+import fs from "fs";
+import os from "os";
+import path from "path";
+import { backupDatabase } from "@/lib/db";
+
+export async function GET() {
+  const stamp = new Date().toISOString().replace(/[:.]/g, "-");
+  const filename = `reelname-backup-${stamp}.db`;
+  const tempPath = path.join(os.tmpdir(), filename);
+
+  try {
+    await backupDatabase(tempPath);
+    const data = await fs.promises.readFile(tempPath);
+    return new NextResponse(new Uint8Array(data), {
+      headers: {
+        "Content-Type": "application/vnd.sqlite3",
+        "Content-Disposition": `attachment; filename="${filename}"`,
+      },
+    });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Backup failed";
+    return NextResponse.json({ error: message }, { status: 500 });
+  } finally {
+    await fs.promises.unlink(tempPath).catch(() => {});
+  }
+}
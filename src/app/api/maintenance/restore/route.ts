@@ -0,0 +1,31 @@
+import { NextResponse } from "next/server";
+import fs from "fs";
+import os from "os";
+import path from "path";
+import { restoreDatabase } from "@/lib/db";
+import { maintenanceBlocker } from "@/lib/maintenance";
+
+// The backup file is sent as the raw request body
+export async function POST(request: Request) {
+  const blocker = maintenanceBlocker();
+  if (blocker) {
+    return NextResponse.json({ error: blocker }, { status: 409 });
+  }
+
+  const data = Buffer.from(await request.arrayBuffer());
+  if (data.length === 0) {
+    return NextResponse.json({ error: "No backup file received" }, { status: 400 });
+  }
+
+  const tempPath = path.join(os.tmpdir(), `reelname-restore-${process.pid}-${Date.now()}.db`);
+  try {
+    await fs.promises.writeFile(tempPath, data);
+    await restoreDatabase(tempPath);
+    return NextResponse.json({ success: true });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Restore failed";
+    return NextResponse.json({ error: message }, { status: 400 });
+  } finally {
+    await fs.promises.unlink(tempPath).catch(() => {});
+  }
+}
@@ -0,0 +1,24 @@
+import { NextResponse } from "next/server";
+import { maintenanceBlocker, runMaintenance, type MaintenanceAction } from "@/lib/maintenance";
+
+const ACTIONS = new Set<MaintenanceAction>(["vacuum", "integrity"]);
+
+export async function POST(request: Request) {
+  const body = await request.json().catch(() => ({}));
+  const action = body.action as MaintenanceAction;
+  if (!ACTIONS.has(action)) {
+    return NextResponse.json({ error: "action must be vacuum or integrity" }, { status: 400 });
+  }
+
+  const blocker = maintenanceBlocker();
+  if (blocker) {
+    return NextResponse.json({ error: blocker }, { status: 409 });
+  }
+
+  try {
+    return NextResponse.json(runMaintenance(action));
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Maintenance failed";
+    return NextResponse.json({ error: message }, { status: 500 });
+  }
+}
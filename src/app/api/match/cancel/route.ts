@@ -0,0 +1,6 @@
+import { NextResponse } from "next/server";
+import { cancelMatch } from "@/lib/matcher";
+
+export async function POST() {
+  return NextResponse.json({ cancelled: cancelMatch() });
+}
@@ -0,0 +1,34 @@
+import { getMatchProgress } from "@/lib/matcher";
+
+// Streams until the client disconnects; a scan only starts matching after it
+// has walked the tree, so "not running" doesn't mean the stream is finished
+export async function GET() {
+  const encoder = new TextEncoder();
+  let timer: ReturnType<typeof setTimeout> | null = null;
+
+  const stream = new ReadableStream({
+    start(controller) {
+      const send = () => {
+        try {
+          controller.enqueue(encoder.encode(`data: ${JSON.stringify(getMatchProgress())}\n\n`));
+        } catch {
+          return;
+        }
+        timer = setTimeout(send, 500);
+      };
+
+      send();
+    },
+    cancel() {
+      if (timer) clearTimeout(timer);
+    },
+  });
+
+  return new Response(stream, {
+    headers: {
+      "Content-Type": "text/event-stream",
+      "Cache-Control": "no-cache",
+      Connection: "keep-alive",
+    },
+  });
+}
@@ -1,11 +1,17 @@
 import { NextResponse } from "next/server";
 import { db } from "@/lib/db";
-import { settings } from "@/lib/db/schema";
-import { eq } from "drizzle-orm";
+import { groups, settings } from "@/lib/db/schema";
+import { and, eq, isNotNull, isNull } from "drizzle-orm";
 import { matchAllGroups } from "@/lib/matcher";
 
-export async function POST() {
+export async function POST(request: Request) {
   try {
+    const body = await request.json().catch(() => ({}));
+    const threshold = typeof body.threshold === "number" ? body.threshold : undefined;
+    if (threshold !== undefined && (threshold < 0 || threshold > 1)) {
+      return NextResponse.json({ error: "threshold must be between 0 and 1" }, { status: 400 });
+    }
+
     const tmdbKey = db
       .select()
       .from(settings)
@@ -19,7 +25,17 @@ export async function POST() {
       );
     }
 
-    const result = await matchAllGroups();
+    // failedOnly retries just the groups whose last attempt couldn't reach TMDB
+    const groupIds = body.failedOnly
+      ? db
+          .select({ id: groups.id })
+          .from(groups)
+          .where(and(isNotNull(groups.matchError), isNull(groups.deletedAt)))
+          .all()
+          .map((g) => g.id)
+      : undefined;
+
+    const result = await matchAllGroups(groupIds, { threshold });
     return NextResponse.json(result);
   } catch (error) {
     const message = error instanceof Error ? error.message : "Matching failed";
@@ -0,0 +1,40 @@
+import { NextRequest, NextResponse } from "next/server";
+import {
+  getPoster,
+  getPosterCacheStats,
+  clearPosterCache,
+  isValidPosterPath,
+  POSTER_SIZES,
+} from "@/lib/posters";
+
+export async function GET(request: NextRequest) {
+  const searchParams = request.nextUrl.searchParams;
+  const posterPath = searchParams.get("path");
+  const size = searchParams.get("size") || "w185";
+
+  // No path → report cache usage for the settings modal
+  if (!posterPath) {
+    return NextResponse.json(getPosterCacheStats());
+  }
+
+  if (!isValidPosterPath(posterPath) || !POSTER_SIZES.has(size)) {
+    return NextResponse.json({ error: "Invalid poster path or size" }, { status: 400 });
+  }
+
+  const poster = await getPoster(posterPath, size);
+  if (!poster) {
+    return NextResponse.json({ error: "Poster not found" }, { status: 404 });
+  }
+
+  return new NextResponse(new Uint8Array(poster.data), {
+    headers: {
+      "Content-Type": poster.contentType,
+      "Cache-Control": "public, max-age=604800, immutable",
+    },
+  });
+}
+
+export async function DELETE() {
+  clearPosterCache();
+  return NextResponse.json({ success: true });
+}
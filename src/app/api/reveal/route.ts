@@ -0,0 +1,65 @@
+import { NextResponse } from "next/server";
+import { spawn } from "child_process";
+import fs from "fs";
+import path from "path";
+import { db } from "@/lib/db";
+import { groups, jobs } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+import { createLogger } from "@/lib/log";
+
+const log = createLogger("reveal");
+
+/**
+ * Open a folder, or reveal a file, in the OS file manager on the machine
+ * running the server. Linux file managers have no portable "select this file"
+ * flag, so files open their containing folder there.
+ */
+function openInFileManager(target: string, isFile: boolean) {
+  let command: string;
+  let args: string[];
+  if (process.platform === "darwin") {
+    command = "open";
+    args = isFile ? ["-R", target] : [target];
+  } else if (process.platform === "win32") {
+    command = "explorer.exe";
+    args = isFile ? [`/select,${target}`] : [target];
+  } else {
+    command = "xdg-open";
+    args = [isFile ? path.dirname(target) : target];
+  }
+
+  const child = spawn(command, args, { detached: true, stdio: "ignore" });
+  child.on("error", (err) => {
+    log.error(`Failed to run ${command}`, { err });
+  });
+  child.unref();
+}
+
+export async function POST(request: Request) {
+  const { groupId, jobId } = (await request.json()) as {
+    groupId?: number;
+    jobId?: number;
+  };
+
+  let target: string | undefined;
+  let isFile = false;
+  if (jobId) {
+    target = db.select().from(jobs).where(eq(jobs.id, jobId)).get()?.sourcePath;
+    isFile = true;
+  } else if (groupId) {
+    target = db.select().from(groups).where(eq(groups.id, groupId)).get()?.folderPath;
+  }
+
+  if (!target) {
+    return NextResponse.json({ error: "groupId or jobId is required" }, { status: 400 });
+  }
+  if (!fs.existsSync(target)) {
+    return NextResponse.json(
+      { error: `${isFile ? "File" : "Folder"} no longer exists: ${target}` },
+      { status: 404 }
+    );
+  }
+
+  openInFileManager(target, isFile);
+  return NextResponse.json({ success: true });
+}
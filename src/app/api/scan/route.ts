@@ -1,26 +1,41 @@
 import { NextResponse } from "next/server";
 import { db } from "@/lib/db";
 import { groups, jobs, settings } from "@/lib/db/schema";
-import { eq, isNull } from "drizzle-orm";
-import { scanDirectoryGrouped } from "@/lib/scanner";
-import { parseFolderName, parseFileName } from "@/lib/parser";
+import { eq, isNull, inArray } from "drizzle-orm";
+import { scanDirectoryGrouped, parseScanPaths, determineMediaType } from "@/lib/scanner";
+import { parseFolderName, parseFileName, parseBareEpisodeNumber } from "@/lib/parser";
 import { matchAllGroups } from "@/lib/matcher";
+import { findDuplicateGroups } from "@/lib/duplicates";
+import { probePendingJobs } from "@/lib/probe";
+import { createLogger, withSpan } from "@/lib/log";
+import path from "path";
+
+const log = createLogger("scan");
+
+function isWithin(folderPath: string, root: string): boolean {
+  const rel = path.relative(root, folderPath);
+  return rel !== "" && !rel.startsWith("..") && !path.isAbsolute(rel);
+}
 
 export async function POST(request: Request) {
+  return withSpan("scan", {}, () => scan(request));
+}
+
+async function scan(request: Request) {
   try {
     const body = await request.json().catch(() => ({}));
-    let scanPath = body.path as string | undefined;
+    let scanPaths = parseScanPaths(body.path as string | undefined);
 
-    if (!scanPath) {
+    if (scanPaths.length === 0) {
       const setting = db
         .select()
         .from(settings)
         .where(eq(settings.key, "scan_path"))
         .get();
-      scanPath = setting?.value;
+      scanPaths = parseScanPaths(setting?.value);
     }
 
-    if (!scanPath) {
+    if (scanPaths.length === 0) {
       return NextResponse.json(
         { error: "No scan path configured. Set it in settings." },
         { status: 400 }
@@ -30,38 +45,103 @@ export async function POST(request: Request) {
     // Clean up orphaned jobs (from before grouping refactor) that have no group
     db.delete(jobs).where(isNull(jobs.groupId)).run();
 
-    const scannedGroups = scanDirectoryGrouped(scanPath);
+    const setting = (key: string) =>
+      db.select().from(settings).where(eq(settings.key, key)).get()?.value;
+    const scanOptions = {
+      groupByParsedTitle: setting("group_by_parsed_title") === "true",
+      maxDepth: parseInt(setting("scan_max_depth") || "", 10) || undefined,
+      followSymlinks: setting("scan_follow_symlinks") === "true",
+      minEpisodeFiles: parseInt(setting("scan_min_episode_files") || "", 10) || undefined,
+      minUnnumberedEpisodes: parseInt(setting("scan_min_unnumbered_episodes") || "", 10) || undefined,
+    };
+    // Merge every root's groups; overlapping roots mustn't add a group twice
+    const results = scanPaths.map((root) => scanDirectoryGrouped(root, scanOptions));
+    const skippedPaths = results.flatMap((r) => r.skipped);
+    for (const skipped of skippedPaths) {
+      log.warn(`Skipped ${skipped.path}: ${skipped.reason}`);
+    }
+    // Loose files and groups split by parsed title share their parent's
+    // folder path, so a group is identified by its path and name together
+    const groupKey = (g: { folderPath: string; folderName: string }) => `${g.folderPath}\0${g.folderName}`;
+    const seenGroups = new Set<string>();
+    const scannedGroups = results
+      .flatMap((r) => r.groups)
+      .filter((g) => {
+        const key = groupKey(g);
+        if (seenGroups.has(key)) return false;
+        seenGroups.add(key);
+        return true;
+      });
 
-    // Get existing group folder paths to avoid duplicates
+    // Get existing groups to avoid adding them twice
     const existingGroups = db
-      .select({ folderPath: groups.folderPath })
+      .select({
+        id: groups.id,
+        folderPath: groups.folderPath,
+        folderName: groups.folderName,
+        missingAt: groups.missingAt,
+        locked: groups.locked,
+      })
       .from(groups)
       .all();
-    const existingPaths = new Set(existingGroups.map((g) => g.folderPath));
+    const existingKeys = new Set(existingGroups.map(groupKey));
+
+    // Files of a locked group stay with it even if this scan groups them elsewhere
+    const lockedGroupIds = new Set(existingGroups.filter((g) => g.locked).map((g) => g.id));
+    const inLockedGroup = (sourcePath: string) => {
+      if (lockedGroupIds.size === 0) return false;
+      const job = db
+        .select({ groupId: jobs.groupId })
+        .from(jobs)
+        .where(eq(jobs.sourcePath, sourcePath))
+        .get();
+      return job?.groupId != null && lockedGroupIds.has(job.groupId);
+    };
 
     let addedGroups = 0;
     let addedFiles = 0;
     let skippedGroups = 0;
     const now = new Date().toISOString();
 
+    // Flag groups under the scan roots whose folder has disappeared, and clear
+    // the flag on any that have come back. Folders that couldn't be read
+    // aren't known to be gone, so they're left alone.
+    const foundPaths = new Set(scannedGroups.map((g) => g.folderPath));
+    const unreadable = (folderPath: string) =>
+      skippedPaths.some((s) => s.path === folderPath || isWithin(folderPath, s.path));
+    const underRoot = existingGroups.filter(
+      (g) => scanPaths.some((root) => isWithin(g.folderPath, root)) && !unreadable(g.folderPath)
+    );
+    const goneIds = underRoot
+      .filter((g) => !foundPaths.has(g.folderPath) && !g.missingAt)
+      .map((g) => g.id);
+    const backIds = underRoot
+      .filter((g) => foundPaths.has(g.folderPath) && g.missingAt)
+      .map((g) => g.id);
+    if (goneIds.length > 0) {
+      db.update(groups).set({ missingAt: now }).where(inArray(groups.id, goneIds)).run();
+    }
+    if (backIds.length > 0) {
+      db.update(groups).set({ missingAt: null }).where(inArray(groups.id, backIds)).run();
+    }
+    const missingGroups = underRoot.filter((g) => !foundPaths.has(g.folderPath)).length;
+
     for (const scannedGroup of scannedGroups) {
-      if (existingPaths.has(scannedGroup.folderPath)) {
+      if (existingKeys.has(groupKey(scannedGroup))) {
+        skippedGroups++;
+        continue;
+      }
+      const files = scannedGroup.files.filter((f) => !inLockedGroup(f.sourcePath));
+      if (files.length === 0) {
         skippedGroups++;
         continue;
       }
 
       const parsedFolder = parseFolderName(scannedGroup.folderName);
 
-      // Determine media type: if any file is episode/special → TV, if all movie → movie
-      const hasEpisodes = scannedGroup.files.some(
-        (f) => f.fileCategory === "episode" || f.fileCategory === "special"
-      );
-      const allMovies = scannedGroup.files.every(
-        (f) => f.fileCategory === "movie"
-      );
-      const mediaType = allMovies ? "movie" : hasEpisodes ? "tv" : "unknown";
+      const mediaType = determineMediaType(files);
 
-      const totalSize = scannedGroup.files.reduce((sum, f) => sum + f.fileSize, 0);
+      const totalSize = files.reduce((sum, f) => sum + f.fileSize, 0);
 
       const insertedGroup = db
         .insert(groups)
@@ -70,7 +150,7 @@ export async function POST(request: Request) {
           mediaType,
           folderPath: scannedGroup.folderPath,
           folderName: scannedGroup.folderName,
-          totalFileCount: scannedGroup.files.length,
+          totalFileCount: files.length,
           totalFileSize: totalSize,
           parsedTitle: parsedFolder.title,
           parsedYear: parsedFolder.year,
@@ -83,10 +163,15 @@ export async function POST(request: Request) {
       addedGroups++;
 
       // Insert or re-link child jobs
-      for (const file of scannedGroup.files) {
+      for (const file of files) {
         const parsed = parseFileName(file.fileName);
         const season = file.detectedSeason ?? parsed.season;
-        const episode = parsed.episode;
+        // Season packs often number files "01.mkv", "02.mkv" with the season on the folder
+        const episode =
+          parsed.episode ??
+          (file.detectedSeason != null
+            ? parseBareEpisodeNumber(file.fileName)
+            : undefined);
 
         // Check if a job already exists for this source path (from a previous scan)
         const existingJob = db
@@ -102,7 +187,7 @@ export async function POST(request: Request) {
               groupId: insertedGroup.id,
               status: "scanned",
               mediaType,
-              fileCategory: file.fileCategory as "episode" | "movie" | "special" | "extra",
+              fileCategory: file.fileCategory,
               extraType: file.extraType as typeof jobs.extraType.enumValues[number] | null | undefined,
               parsedTitle: parsed.title,
               parsedYear: parsed.year,
@@ -110,6 +195,12 @@ export async function POST(request: Request) {
               parsedEpisode: episode,
               parsedQuality: parsed.quality,
               parsedCodec: parsed.codec,
+              parsedHdr: parsed.hdr ?? null,
+              parsedPart: parsed.part ?? null,
+              fileSize: file.fileSize,
+              deletedAt: null,
+              // The file may have been replaced, so probe it again
+              mediaProbedAt: null,
               // Clear old TMDB data so it gets re-fetched at group level
               tmdbId: null,
               tmdbTitle: null,
@@ -127,7 +218,7 @@ export async function POST(request: Request) {
               groupId: insertedGroup.id,
               status: "scanned",
               mediaType,
-              fileCategory: file.fileCategory as "episode" | "movie" | "special" | "extra",
+              fileCategory: file.fileCategory,
               extraType: file.extraType as typeof jobs.extraType.enumValues[number] | null | undefined,
               sourcePath: file.sourcePath,
               fileName: file.fileName,
@@ -139,6 +230,8 @@ export async function POST(request: Request) {
               parsedEpisode: episode,
               parsedQuality: parsed.quality,
               parsedCodec: parsed.codec,
+              parsedHdr: parsed.hdr ?? null,
+              parsedPart: parsed.part ?? null,
               createdAt: now,
               updatedAt: now,
             })
@@ -149,8 +242,27 @@ export async function POST(request: Request) {
       }
     }
 
+    log.info("Scan finished", {
+      roots: scanPaths.length,
+      scannedGroups: scannedGroups.length,
+      addedGroups,
+      addedFiles,
+      missingGroups,
+    });
+
+    // Groups created from here on count as "new since last scan"
+    db.insert(settings)
+      .values({ key: "last_scan_at", value: now })
+      .onConflictDoUpdate({ target: settings.key, set: { value: now } })
+      .run();
+
+    // Probe new files in the background so the listing isn't held up
+    probePendingJobs().catch((err) => {
+      log.error("Media probing failed", { err });
+    });
+
     // Auto-match if TMDB key is configured
-    let matchResult = { matched: 0, ambiguous: 0 };
+    let matchResult = { matched: 0, ambiguous: 0, unmatched: 0, failed: 0, cancelled: false, offline: false };
     let matchError: string | null = null;
     const tmdbKey = db
       .select()
@@ -162,7 +274,7 @@ export async function POST(request: Request) {
         matchResult = await matchAllGroups();
       } catch (err) {
         matchError = err instanceof Error ? err.message : "Matching failed";
-        console.error("Matching failed:", err);
+        log.error("Matching failed", { err });
       }
     } else {
       matchError = "No TMDB API key configured. Set it in Settings to enable auto-matching.";
@@ -173,9 +285,18 @@ export async function POST(request: Request) {
       addedGroups,
       addedFiles,
       skippedGroups,
+      missingGroups,
+      skippedPaths,
+      scannedAt: now,
       matched: matchResult.matched,
       ambiguous: matchResult.ambiguous,
+      unmatched: matchResult.unmatched,
+      matchFailed: matchResult.failed,
+      matchCancelled: matchResult.cancelled,
+      matchOffline: matchResult.offline,
       matchError,
+      // Moved folders whose old, now missing group can be merged into the new one
+      duplicateGroups: findDuplicateGroups().length,
     });
   } catch (error) {
     const message = error instanceof Error ? error.message : "Scan failed";
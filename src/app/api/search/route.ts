@@ -1,5 +1,11 @@
 import { NextRequest, NextResponse } from "next/server";
-import { searchMulti, searchMovies, searchTV } from "@/lib/tmdb";
+import {
+  searchMulti,
+  searchMovies,
+  searchTV,
+  parseTmdbReference,
+  lookupTmdbReference,
+} from "@/lib/tmdb";
 
 export async function GET(request: NextRequest) {
   const searchParams = request.nextUrl.searchParams;
@@ -14,8 +20,12 @@ export async function GET(request: NextRequest) {
   try {
     const yearNum = year ? parseInt(year, 10) : undefined;
     let results;
+    const reference = parseTmdbReference(query);
 
-    if (mediaType === "movie") {
+    if (reference) {
+      // Pasted TMDB URL / tmdb:id / IMDB id → resolve directly
+      results = await lookupTmdbReference(reference, mediaType);
+    } else if (mediaType === "movie") {
       results = await searchMovies(query, yearNum);
     } else if (mediaType === "tv") {
       results = await searchTV(query, yearNum);
@@ -23,19 +33,28 @@ export async function GET(request: NextRequest) {
       results = await searchMulti(query, yearNum);
     }
 
-    // Normalize results for frontend
-    const normalized = results.slice(0, 10).map((r) => ({
-      tmdbId: r.id,
-      mediaType: r.media_type || "movie",
-      title: r.title || r.name || "",
-      year: parseInt(
-        (r.release_date || r.first_air_date || "").slice(0, 4),
-        10
-      ) || null,
-      posterPath: r.poster_path,
-      overview: r.overview?.slice(0, 500) || null,
-      confidence: 1.0,
-    }));
+    // Normalize results for frontend, dropping repeated (tmdbId, mediaType) pairs
+    const seen = new Set<string>();
+    const normalized = results
+      .filter((r) => {
+        const key = `${r.media_type || "movie"}:${r.id}`;
+        if (seen.has(key)) return false;
+        seen.add(key);
+        return true;
+      })
+      .slice(0, 10)
+      .map((r) => ({
+        tmdbId: r.id,
+        mediaType: r.media_type || "movie",
+        title: r.title || r.name || "",
+        year: parseInt(
+          (r.release_date || r.first_air_date || "").slice(0, 4),
+          10
+        ) || null,
+        posterPath: r.poster_path,
+        overview: r.overview?.slice(0, 500) || null,
+        confidence: 1.0,
+      }));
 
     return NextResponse.json({ results: normalized });
   } catch (error) {
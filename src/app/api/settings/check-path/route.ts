@@ -0,0 +1,14 @@
+import { NextResponse } from "next/server";
+import { scanPathError } from "@/lib/scanner";
+
+// Checks a scan path typed in the settings form before it's saved
+export async function POST(request: Request) {
+  const body = await request.json().catch(() => ({}));
+  const dir = typeof body.path === "string" ? body.path.trim() : "";
+  if (!dir) {
+    return NextResponse.json({ error: "path is required" }, { status: 400 });
+  }
+
+  const error = scanPathError(dir);
+  return NextResponse.json(error ? { ok: false, error } : { ok: true });
+}
@@ -1,6 +1,9 @@
 import { NextResponse } from "next/server";
 import { db, DATA_DIR } from "@/lib/db";
 import { settings } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+import { validateSettings } from "@/lib/settings";
+import { parseScanPaths, scanPathError } from "@/lib/scanner";
 import fs from "fs";
 import path from "path";
 import os from "os";
@@ -52,6 +55,21 @@ export async function GET() {
 export async function PUT(request: Request) {
   const body = await request.json();
 
+  const fieldErrors = validateSettings(body);
+  // The form sends every field on save, so only check the scan paths when they
+  // changed; an unmounted share mustn't block saving anything else
+  const savedScanPath = db.select().from(settings).where(eq(settings.key, "scan_path")).get()?.value ?? "";
+  if (typeof body.scan_path === "string" && body.scan_path !== savedScanPath) {
+    const pathErrors = parseScanPaths(body.scan_path)
+      .map((p) => [p, scanPathError(p)])
+      .filter(([, error]) => error)
+      .map(([p, error]) => `${p}: ${error}`);
+    if (pathErrors.length > 0) fieldErrors.scan_path = pathErrors.join("; ");
+  }
+  if (Object.keys(fieldErrors).length > 0) {
+    return NextResponse.json({ error: "Some settings are invalid", fieldErrors }, { status: 400 });
+  }
+
   // Handle data_dir separately — written to external config file
   if ("data_dir" in body) {
     const config = readConfig();
@@ -0,0 +1,11 @@
+import { NextResponse } from "next/server";
+import { validateApiKey } from "@/lib/tmdb";
+
+// Validates the key typed in the settings form (falls back to the stored key)
+export async function POST(request: Request) {
+  const body = await request.json().catch(() => ({}));
+  const apiKey = typeof body.apiKey === "string" ? body.apiKey : undefined;
+
+  const result = await validateApiKey(apiKey);
+  return NextResponse.json(result);
+}
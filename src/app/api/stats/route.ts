@@ -0,0 +1,6 @@
+import { NextResponse } from "next/server";
+import { getLibraryStats } from "@/lib/stats";
+
+export async function GET() {
+  return NextResponse.json(getLibraryStats());
+}
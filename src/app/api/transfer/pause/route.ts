@@ -0,0 +1,13 @@
+import { NextResponse } from "next/server";
+import { pauseTransfers, resumeTransfers, isTransfersPaused } from "@/lib/transfer";
+
+// POST pauses the active batch, DELETE resumes it
+export async function POST() {
+  pauseTransfers();
+  return NextResponse.json({ paused: isTransfersPaused() });
+}
+
+export async function DELETE() {
+  resumeTransfers();
+  return NextResponse.json({ paused: isTransfersPaused() });
+}
@@ -0,0 +1,37 @@
+import { NextResponse } from "next/server";
+import {
+  collectTransferJobIds,
+  checkFreeSpace,
+  parseEligibleStatuses,
+  TRANSFER_ELIGIBLE_STATUSES,
+} from "@/lib/transfer";
+
+export async function POST(request: Request) {
+  try {
+    const { jobIds, groupIds, destinationId, statuses: rawStatuses } = (await request.json()) as {
+      jobIds?: number[];
+      groupIds?: number[];
+      destinationId: number;
+      statuses?: unknown;
+    };
+
+    if (!destinationId) {
+      return NextResponse.json({ error: "destinationId is required" }, { status: 400 });
+    }
+
+    const statuses = parseEligibleStatuses(rawStatuses);
+    if (!statuses) {
+      return NextResponse.json(
+        { error: `statuses must be a non-empty list of: ${TRANSFER_ELIGIBLE_STATUSES.join(", ")}` },
+        { status: 400 }
+      );
+    }
+
+    const jobIdArray = collectTransferJobIds(jobIds, groupIds, statuses);
+    const space = await checkFreeSpace(jobIdArray, destinationId);
+    return NextResponse.json({ jobCount: jobIdArray.length, ...space });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Preview failed";
+    return NextResponse.json({ error: message }, { status: 500 });
+  }
+}
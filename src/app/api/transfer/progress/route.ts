@@ -1,6 +1,7 @@
 import { db } from "@/lib/db";
 import { jobs } from "@/lib/db/schema";
 import { inArray } from "drizzle-orm";
+import { getTransferRate } from "@/lib/transfer";
 
 export async function GET(request: Request) {
   const url = new URL(request.url);
@@ -43,7 +44,9 @@ export async function GET(request: Request) {
               .all();
           }
 
-          const data = JSON.stringify(transferJobs);
+          const data = JSON.stringify(
+            transferJobs.map((j) => ({ ...j, bytesPerSecond: getTransferRate(j.id) }))
+          );
           controller.enqueue(encoder.encode(`data: ${data}\n\n`));
 
           // Check if all done (no queued or transferring jobs remain)
@@ -1,8 +1,15 @@
 import { NextResponse } from "next/server";
-import { queueTransfers } from "@/lib/transfer";
+import {
+  queueTransfers,
+  collectTransferJobIds,
+  checkFreeSpace,
+  parseEligibleStatuses,
+  describeStatuses,
+  TRANSFER_ELIGIBLE_STATUSES,
+} from "@/lib/transfer";
 import { db } from "@/lib/db";
 import { jobs } from "@/lib/db/schema";
-import { eq, inArray } from "drizzle-orm";
+import { inArray } from "drizzle-orm";
 
 export async function POST(request: Request) {
   try {
@@ -12,6 +19,8 @@ export async function POST(request: Request) {
       groupIds?: number[];
       destinationId: number;
     };
+    // Job statuses to pick up from the groups; confirmed only unless asked
+    const statuses = parseEligibleStatuses(body.statuses);
 
     if ((!jobIds?.length && !groupIds?.length) || !destinationId) {
       return NextResponse.json(
@@ -20,33 +29,29 @@ export async function POST(request: Request) {
       );
     }
 
-    // Collect all job IDs to transfer
-    const allJobIds = new Set<number>(jobIds || []);
-
-    // Expand groupIds to their confirmed child jobs
-    if (groupIds?.length) {
-      for (const gid of groupIds) {
-        const groupJobs = db
-          .select()
-          .from(jobs)
-          .where(eq(jobs.groupId, gid))
-          .all();
-        for (const j of groupJobs) {
-          if (j.status === "confirmed") {
-            allJobIds.add(j.id);
-          }
-        }
-      }
+    if (!statuses) {
+      return NextResponse.json(
+        { error: `statuses must be a non-empty list of: ${TRANSFER_ELIGIBLE_STATUSES.join(", ")}` },
+        { status: 400 }
+      );
     }
 
-    if (allJobIds.size === 0) {
+    const jobIdArray = collectTransferJobIds(jobIds, groupIds, statuses);
+
+    if (jobIdArray.length === 0) {
       return NextResponse.json(
-        { error: "No confirmed jobs to transfer" },
+        { error: `No ${describeStatuses(statuses)} jobs to transfer` },
         { status: 400 }
       );
     }
 
-    const jobIdArray = [...allJobIds];
+    const space = await checkFreeSpace(jobIdArray, destinationId);
+    if (!space.fits) {
+      return NextResponse.json(
+        { error: `Not enough free space on destination: ${space.summary.toLowerCase()}` },
+        { status: 400 }
+      );
+    }
 
     // Reset any old completed/failed jobs so they don't pollute progress totals
     db.update(jobs)
@@ -0,0 +1,46 @@
+import { NextResponse } from "next/server";
+import {
+  collectTransferJobIds,
+  buildTransferScript,
+  parseEligibleStatuses,
+  describeStatuses,
+  TRANSFER_ELIGIBLE_STATUSES,
+} from "@/lib/transfer";
+import type { ScriptFormat } from "@/lib/transfer";
+
+export async function POST(request: Request) {
+  try {
+    const { jobIds, groupIds, destinationId, format, statuses: rawStatuses } = (await request.json()) as {
+      jobIds?: number[];
+      groupIds?: number[];
+      destinationId: number;
+      format?: ScriptFormat;
+      statuses?: unknown;
+    };
+
+    if (!destinationId) {
+      return NextResponse.json({ error: "destinationId is required" }, { status: 400 });
+    }
+    if (format && format !== "rsync" && format !== "robocopy") {
+      return NextResponse.json({ error: "format must be rsync or robocopy" }, { status: 400 });
+    }
+
+    const statuses = parseEligibleStatuses(rawStatuses);
+    if (!statuses) {
+      return NextResponse.json(
+        { error: `statuses must be a non-empty list of: ${TRANSFER_ELIGIBLE_STATUSES.join(", ")}` },
+        { status: 400 }
+      );
+    }
+
+    const jobIdArray = collectTransferJobIds(jobIds, groupIds, statuses);
+    if (jobIdArray.length === 0) {
+      return NextResponse.json({ error: `No ${describeStatuses(statuses)} jobs to export` }, { status: 400 });
+    }
+
+    return NextResponse.json(buildTransferScript(jobIdArray, destinationId, format || "rsync"));
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Script export failed";
+    return NextResponse.json({ error: message }, { status: 500 });
+  }
+}
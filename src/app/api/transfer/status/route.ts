@@ -2,6 +2,8 @@ import { NextResponse } from "next/server";
 import { db } from "@/lib/db";
 import { jobs } from "@/lib/db/schema";
 import { inArray } from "drizzle-orm";
+import { getTransferRate, isTransfersPaused } from "@/lib/transfer";
+import { getPostTransferHookState } from "@/lib/hooks";
 
 export async function GET() {
   const transferJobs = db
@@ -22,5 +24,10 @@ export async function GET() {
     (j) => j.status === "queued" || j.status === "transferring"
   );
 
-  return NextResponse.json({ active, jobs: transferJobs });
+  return NextResponse.json({
+    active,
+    paused: isTransfersPaused(),
+    postTransferHook: getPostTransferHookState(),
+    jobs: transferJobs.map((j) => ({ ...j, bytesPerSecond: getTransferRate(j.id) })),
+  });
 }
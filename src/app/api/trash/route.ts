@@ -0,0 +1,8 @@
+import { NextResponse } from "next/server";
+import { purgeTrash } from "@/lib/trash";
+
+// Empty the trash: permanently delete every soft-deleted group and job
+export async function DELETE() {
+  const removed = purgeTrash();
+  return NextResponse.json({ success: true, ...removed });
+}
@@ -1,8 +1,15 @@
 "use client";
 
-import { useCallback, useEffect } from "react";
-import { useAppStore } from "@/lib/store";
-import { fetchGroups, fetchSettings, triggerScan, triggerMatch } from "@/lib/api";
+import { useCallback, useEffect, useState } from "react";
+import { useAppStore, parseViewState } from "@/lib/store";
+import {
+  fetchGroups,
+  fetchSettings,
+  fetchLabels,
+  updateSettings,
+  triggerScan,
+  triggerMatch,
+} from "@/lib/api";
 import { Header } from "@/components/Header";
 import { Filters } from "@/components/Filters";
 import { QueueTable } from "@/components/QueueTable";
@@ -12,6 +19,8 @@ import { TransferDrawer } from "@/components/TransferDrawer";
 import { Pagination } from "@/components/Pagination";
 import { ToastContainer, useToastStore } from "@/components/Toast";
 import { KeyboardShortcuts } from "@/components/KeyboardShortcuts";
+import { notifyDesktop } from "@/lib/notify";
+import { applyUiScale, parseUiScale } from "@/lib/theme";
 
 export default function Dashboard() {
   const {
@@ -19,11 +28,18 @@ export default function Dashboard() {
     setLoading,
     statusFilter,
     mediaTypeFilter,
+    changeFilter,
+    labelFilter,
+    issueFilter,
+    trashView,
     searchQuery,
     sortBy,
     sortDir,
     page,
   } = useAppStore();
+  const pageSize = useAppStore((s) => s.settings.page_size);
+  const uiScale = useAppStore((s) => s.settings.ui_scale);
+  const [viewRestored, setViewRestored] = useState(false);
 
   const loadGroups = useCallback(async () => {
     setLoading(true);
@@ -32,27 +48,74 @@ export default function Dashboard() {
       sortBy,
       sortDir,
     };
+    if (pageSize) params.limit = pageSize;
     if (statusFilter) params.status = statusFilter;
     if (mediaTypeFilter) params.mediaType = mediaTypeFilter;
+    if (changeFilter) params.changed = changeFilter;
+    if (labelFilter != null) params.label = String(labelFilter);
+    if (issueFilter) params.issue = issueFilter;
+    if (trashView) params.trash = "1";
     if (searchQuery) params.search = searchQuery;
 
     const data = await fetchGroups(params);
     setGroups(data.groups, data.total);
     setLoading(false);
-  }, [page, sortBy, sortDir, statusFilter, mediaTypeFilter, searchQuery, setGroups, setLoading]);
 
+    // A restored page can point past the end if the library shrank
+    const lastPage = Math.max(1, Math.ceil(data.total / data.limit));
+    if (page > lastPage) useAppStore.getState().setPage(lastPage);
+  }, [page, pageSize, sortBy, sortDir, statusFilter, mediaTypeFilter, changeFilter, labelFilter, issueFilter, trashView, searchQuery, setGroups, setLoading]);
+
+  // Load settings once and restore the saved view before the first group fetch
   useEffect(() => {
-    loadGroups();
-    fetchSettings().then((s) => useAppStore.getState().setSettings(s));
-  }, [loadGroups]);
+    fetchSettings().then((s) => {
+      useAppStore.getState().setSettings(s);
+      useAppStore.setState(parseViewState(s.view_state));
+      setViewRestored(true);
+    });
+    fetchLabels().then((labels) => useAppStore.getState().setLabels(labels));
+  }, []);
+
+  useEffect(() => {
+    applyUiScale(parseUiScale(uiScale));
+  }, [uiScale]);
+
+  useEffect(() => {
+    if (viewRestored) loadGroups();
+  }, [viewRestored, loadGroups]);
 
   // Debounced search
   useEffect(() => {
+    if (!viewRestored) return;
     const timer = setTimeout(() => {
       loadGroups();
     }, 300);
     return () => clearTimeout(timer);
-  }, [searchQuery, loadGroups]);
+  }, [viewRestored, searchQuery, loadGroups]);
+
+  // Persist sort/filter/page so the next launch opens the same view
+  useEffect(() => {
+    if (!viewRestored) return;
+    const timer = setTimeout(() => {
+      const viewState = JSON.stringify({
+        sortBy,
+        sortDir,
+        statusFilter,
+        mediaTypeFilter,
+        changeFilter,
+        labelFilter,
+        issueFilter,
+        searchQuery,
+        page,
+      });
+      if (viewState === useAppStore.getState().settings.view_state) return;
+      useAppStore.setState((state) => ({
+        settings: { ...state.settings, view_state: viewState },
+      }));
+      updateSettings({ view_state: viewState });
+    }, 500);
+    return () => clearTimeout(timer);
+  }, [viewRestored, sortBy, sortDir, statusFilter, mediaTypeFilter, changeFilter, labelFilter, issueFilter, searchQuery, page]);
 
   const handleScan = useCallback(async () => {
     const { setScanning, settings } = useAppStore.getState();
@@ -61,14 +124,48 @@ export default function Dashboard() {
       const result = await triggerScan(settings.scan_path || undefined);
       if (result.error) {
         useToastStore.getState().addToast(result.error, "error");
+        notifyDesktop("Scan failed", result.error);
       } else {
+        useAppStore.setState((state) => ({
+          settings: { ...state.settings, last_scan_at: result.scannedAt },
+        }));
         let msg = `Added ${result.addedGroups ?? 0} groups (${result.addedFiles ?? 0} files).`;
-        if (result.matched > 0 || result.ambiguous > 0) {
+        if (result.missingGroups > 0) {
+          msg += ` ${result.missingGroups} missing on disk.`;
+        }
+        if (result.duplicateGroups > 0) {
+          msg += ` ${result.duplicateGroups} look${result.duplicateGroups === 1 ? "s" : ""} moved; filter Missing to merge.`;
+        }
+        const skipped: { path: string; reason: string }[] = result.skippedPaths ?? [];
+        if (skipped.length > 0) {
+          const denied = skipped.filter((s) => s.reason === "permission denied").length;
+          msg += denied === skipped.length
+            ? ` ${skipped.length} ${skipped.length === 1 ? "path" : "paths"} skipped (permission denied).`
+            : ` ${skipped.length} ${skipped.length === 1 ? "path" : "paths"} skipped (unreadable).`;
+        }
+        if (result.matchOffline) {
+          msg += " Offline — scanned but not matched; use Match once TMDB is reachable.";
+        } else if (result.matchCancelled) {
+          msg += ` Match cancelled after ${result.matched + result.ambiguous + result.unmatched} groups.`;
+        } else if (result.matched > 0 || result.ambiguous > 0 || result.unmatched > 0) {
           msg += ` Matched ${result.matched}, ambiguous ${result.ambiguous}.`;
+          if (result.unmatched > 0) msg += ` ${result.unmatched} had no TMDB results.`;
         }
+        if (result.matchFailed > 0) msg += ` ${result.matchFailed} couldn't reach TMDB; filter Match failed to retry.`;
+        const attempted = (result.matched ?? 0) + (result.ambiguous ?? 0) + (result.unmatched ?? 0);
+        notifyDesktop(
+          "Scan complete",
+          attempted > 0
+            ? `Matched ${result.matched} of ${attempted} groups`
+            : `Added ${result.addedGroups ?? 0} groups`
+        );
         if (result.matchError) {
           msg += ` ${result.matchError}`;
           useToastStore.getState().addToast(msg, "warning");
+        } else if (skipped.length > 0 || result.matchFailed > 0) {
+          useToastStore.getState().addToast(msg, "warning");
+        } else if (result.matchOffline) {
+          useToastStore.getState().addToast(msg, "info");
         } else {
           useToastStore.getState().addToast(msg, "success");
         }
@@ -76,6 +173,7 @@ export default function Dashboard() {
       await loadGroups();
     } catch {
       useToastStore.getState().addToast("Scan failed", "error");
+      notifyDesktop("Scan failed", "The scan request did not complete");
     }
     setScanning(false);
   }, [loadGroups]);
@@ -87,12 +185,28 @@ export default function Dashboard() {
       const result = await triggerMatch();
       if (result.error) {
         useToastStore.getState().addToast(result.error, "error");
+      } else if (result.offline) {
+        useToastStore
+          .getState()
+          .addToast("TMDB is unreachable — nothing was matched. Try again once you're back online.", "warning");
+      } else if (result.cancelled) {
+        useToastStore
+          .getState()
+          .addToast(
+            `Match cancelled after ${result.matched + result.ambiguous + result.unmatched} groups`,
+            "info"
+          );
       } else {
+        const noResults = result.unmatched ? ` ${result.unmatched} had no TMDB results.` : "";
+        const failed = result.failed ? ` ${result.failed} couldn't reach TMDB.` : "";
         useToastStore
           .getState()
           .addToast(
-            `Matched ${result.matched ?? 0} groups, ${result.ambiguous ?? 0} ambiguous.`,
-            "success"
+            `Matched ${result.matched ?? 0} groups, ${result.ambiguous ?? 0} ambiguous.${noResults}${failed}`,
+            result.failed ? "warning" : "success",
+            result.failed
+              ? { label: "Show failed", onClick: () => useAppStore.getState().setIssueFilter("match_error") }
+              : undefined
           );
       }
       await loadGroups();
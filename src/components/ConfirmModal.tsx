@@ -0,0 +1,66 @@
+"use client";
+
+import { useEffect } from "react";
+
+export function ConfirmModal({
+  title,
+  children,
+  confirmLabel,
+  destructive = false,
+  onConfirm,
+  onClose,
+}: {
+  title: string;
+  children?: React.ReactNode;
+  confirmLabel: string;
+  destructive?: boolean;
+  onConfirm: () => void;
+  onClose: () => void;
+}) {
+  useEffect(() => {
+    const handler = (e: KeyboardEvent) => {
+      if (e.key === "Escape") onClose();
+    };
+    window.addEventListener("keydown", handler);
+    return () => window.removeEventListener("keydown", handler);
+  }, [onClose]);
+
+  return (
+    <div
+      className="fixed inset-0 z-50 flex items-center justify-center bg-black/60"
+      onClick={onClose}
+    >
+      <div
+        className="bg-bg-secondary border border-border rounded-lg shadow-xl w-[25rem] flex flex-col"
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div className="px-4 py-3 border-b border-border">
+          <h2 className="text-sm font-semibold text-text-primary">{title}</h2>
+        </div>
+
+        {children && <div className="px-4 py-3 text-sm text-text-secondary">{children}</div>}
+
+        <div className="flex justify-end gap-2 px-4 py-3 border-t border-border">
+          <button
+            onClick={onClose}
+            className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors"
+          >
+            Cancel
+          </button>
+          {/* Focused so Enter confirms */}
+          <button
+            onClick={onConfirm}
+            autoFocus
+            className={`px-3 py-1.5 text-sm rounded-md text-white transition-colors ${
+              destructive
+                ? "bg-status-failed hover:opacity-90"
+                : "bg-accent hover:bg-accent-hover"
+            }`}
+          >
+            {confirmLabel}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
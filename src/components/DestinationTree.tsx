@@ -0,0 +1,82 @@
+"use client";
+
+interface TreeNode {
+  name: string;
+  children: Map<string, TreeNode>;
+  isFile: boolean;
+}
+
+// Folders first, then files; numeric-aware so "Season 10" sorts after "Season 9"
+function sortedChildren(node: TreeNode): TreeNode[] {
+  return [...node.children.values()].sort((a, b) => {
+    if (a.isFile !== b.isFile) return a.isFile ? 1 : -1;
+    return a.name.localeCompare(b.name, undefined, { numeric: true });
+  });
+}
+
+/** Nest relative destination paths ("Show (2020)/Season 01/Show S01E01.mkv") into a folder tree. */
+function buildTree(paths: string[]): TreeNode {
+  const root: TreeNode = { name: "", children: new Map(), isFile: false };
+  for (const filePath of paths) {
+    const parts = filePath.split(/[/\\]/).filter(Boolean);
+    let node = root;
+    parts.forEach((part, i) => {
+      const isFile = i === parts.length - 1;
+      let child = node.children.get(part);
+      if (!child) {
+        child = { name: part, children: new Map(), isFile };
+        node.children.set(part, child);
+      }
+      node = child;
+    });
+  }
+  return root;
+}
+
+function countFiles(node: TreeNode): number {
+  if (node.isFile) return 1;
+  let count = 0;
+  for (const child of node.children.values()) count += countFiles(child);
+  return count;
+}
+
+function TreeRows({ node, depth }: { node: TreeNode; depth: number }) {
+  return (
+    <>
+      {sortedChildren(node).map((child) => (
+        <div key={child.name}>
+          <div
+            className="flex items-center gap-1.5 py-0.5 font-mono text-xs"
+            style={{ paddingLeft: `${depth * 0.875}rem` }}
+          >
+            {child.isFile ? (
+              <span className="text-text-secondary truncate" title={child.name}>
+                {child.name}
+              </span>
+            ) : (
+              <>
+                <span className="text-text-primary truncate" title={child.name}>
+                  {child.name}/
+                </span>
+                <span className="text-micro text-text-muted flex-shrink-0">
+                  {countFiles(child)}
+                </span>
+              </>
+            )}
+          </div>
+          {!child.isFile && <TreeRows node={child} depth={depth + 1} />}
+        </div>
+      ))}
+    </>
+  );
+}
+
+/** How a group's files will be laid out under the destination root. */
+export function DestinationTree({ paths }: { paths: string[] }) {
+  if (paths.length === 0) return null;
+  return (
+    <div className="p-2 rounded bg-bg-tertiary/30 max-h-80 overflow-auto">
+      <TreeRows node={buildTree(paths)} depth={0} />
+    </div>
+  );
+}
@@ -1,9 +1,20 @@
 "use client";
 
 import { useState, useEffect, useCallback } from "react";
-import { fetchSeasons, fetchSeasonEpisodes, updateJob } from "@/lib/api";
+import { fetchSeasons, fetchSeasonEpisodes, updateJob, updateGroup } from "@/lib/api";
 import { useToastStore } from "./Toast";
-import type { JobWithPreview } from "@/lib/store";
+import { useAppStore, type JobWithPreview } from "@/lib/store";
+
+const PROVIDER_LABELS: Record<string, string> = {
+  tmdb: "TMDB",
+  tvdb: "TheTVDB",
+};
+
+const ORDER_LABELS: Record<string, string> = {
+  aired: "Aired",
+  dvd: "DVD",
+  absolute: "Absolute",
+};
 
 interface TmdbSeason {
   season_number: number;
@@ -21,27 +32,51 @@ interface TmdbEpisode {
 interface Props {
   job: JobWithPreview;
   groupId: number;
+  episodeOrder?: string;
   onClose: () => void;
   onSaved: () => void;
 }
 
-export function EpisodeResolveModal({ job, groupId, onClose, onSaved }: Props) {
+export function EpisodeResolveModal({
+  job,
+  groupId,
+  episodeOrder = "aired",
+  onClose,
+  onSaved,
+}: Props) {
   const [seasons, setSeasons] = useState<TmdbSeason[]>([]);
   const [episodes, setEpisodes] = useState<TmdbEpisode[]>([]);
   const [selectedSeason, setSelectedSeason] = useState<number | null>(null);
   const [loadingSeasons, setLoadingSeasons] = useState(true);
   const [loadingEpisodes, setLoadingEpisodes] = useState(false);
   const [saving, setSaving] = useState<number | null>(null);
+  const defaultProvider = useAppStore((s) => s.settings.metadata_provider) || "tmdb";
+  const [provider, setProvider] = useState(defaultProvider);
+  const [lookupError, setLookupError] = useState<string | null>(null);
+  const [order, setOrder] = useState(episodeOrder);
 
-  // Load seasons on mount
+  // The ordering is remembered per group so the next lookup starts from it
+  const handleOrderChange = async (next: string) => {
+    setOrder(next);
+    await updateGroup(groupId, { episodeOrder: next });
+  };
+
+  // Load seasons on mount and whenever the provider changes
   useEffect(() => {
     let cancelled = false;
     (async () => {
       setLoadingSeasons(true);
-      const data = await fetchSeasons(groupId);
+      setLookupError(null);
+      const data = await fetchSeasons(groupId, provider, order);
       if (cancelled) return;
       setSeasons(data.seasons || []);
+      setLookupError(data.error || null);
       setLoadingSeasons(false);
+      if (!data.seasons?.length) {
+        setEpisodes([]);
+        setSelectedSeason(null);
+        return;
+      }
 
       // Default to the job's current season, or Season 0 for specials
       const defaultSeason =
@@ -51,7 +86,7 @@ export function EpisodeResolveModal({ job, groupId, onClose, onSaved }: Props) {
       setSelectedSeason(match ? defaultSeason : available[0]?.season_number ?? null);
     })();
     return () => { cancelled = true; };
-  }, [groupId, job.parsedSeason, job.fileCategory]);
+  }, [groupId, provider, order, job.parsedSeason, job.fileCategory]);
 
   // Load episodes when season changes
   useEffect(() => {
@@ -59,13 +94,13 @@ export function EpisodeResolveModal({ job, groupId, onClose, onSaved }: Props) {
     let cancelled = false;
     (async () => {
       setLoadingEpisodes(true);
-      const data = await fetchSeasonEpisodes(groupId, selectedSeason);
+      const data = await fetchSeasonEpisodes(groupId, selectedSeason, provider, order);
       if (cancelled) return;
       setEpisodes(data.episodes || []);
       setLoadingEpisodes(false);
     })();
     return () => { cancelled = true; };
-  }, [groupId, selectedSeason]);
+  }, [groupId, selectedSeason, provider, order]);
 
   const handleUse = useCallback(
     async (ep: TmdbEpisode) => {
@@ -111,7 +146,7 @@ export function EpisodeResolveModal({ job, groupId, onClose, onSaved }: Props) {
       onClick={onClose}
     >
       <div
-        className="bg-bg-secondary border border-border rounded-lg shadow-xl w-[520px] max-h-[80vh] flex flex-col"
+        className="bg-bg-secondary border border-border rounded-lg shadow-xl w-[32.5rem] max-h-[80vh] flex flex-col"
         onClick={(e) => e.stopPropagation()}
       >
         {/* Header */}
@@ -130,7 +165,7 @@ export function EpisodeResolveModal({ job, groupId, onClose, onSaved }: Props) {
         <div className="flex-1 overflow-y-auto p-4 space-y-4">
           {/* Current identification */}
           <div className="p-3 rounded-md bg-bg-tertiary/50 space-y-1">
-            <p className="text-[10px] uppercase tracking-wider text-text-muted">
+            <p className="text-micro uppercase tracking-wider text-text-muted">
               Current Identification
             </p>
             <p className="text-xs text-text-muted font-mono truncate">
@@ -153,9 +188,56 @@ export function EpisodeResolveModal({ job, groupId, onClose, onSaved }: Props) {
             </div>
           </div>
 
+          {/* Provider selector */}
+          <div className="space-y-1">
+            <label className="text-micro uppercase tracking-wider text-text-muted">
+              Episode Source
+            </label>
+            <div className="flex gap-1">
+              {Object.entries(PROVIDER_LABELS).map(([id, label]) => (
+                <button
+                  key={id}
+                  onClick={() => setProvider(id)}
+                  className={`px-3 py-1 text-xs rounded-md transition-colors ${
+                    provider === id
+                      ? "bg-accent text-white"
+                      : "bg-bg-tertiary text-text-secondary hover:bg-bg-hover"
+                  }`}
+                >
+                  {label}
+                </button>
+              ))}
+            </div>
+          </div>
+
+          {/* Ordering selector */}
+          <div className="space-y-1">
+            <label className="text-micro uppercase tracking-wider text-text-muted">
+              Episode Order
+            </label>
+            <div className="flex gap-1">
+              {Object.entries(ORDER_LABELS).map(([id, label]) => (
+                <button
+                  key={id}
+                  onClick={() => handleOrderChange(id)}
+                  className={`px-3 py-1 text-xs rounded-md transition-colors ${
+                    order === id
+                      ? "bg-accent text-white"
+                      : "bg-bg-tertiary text-text-secondary hover:bg-bg-hover"
+                  }`}
+                >
+                  {label}
+                </button>
+              ))}
+            </div>
+            {lookupError && (
+              <p className="text-xs text-error">{lookupError}</p>
+            )}
+          </div>
+
           {/* Season selector */}
           <div className="space-y-1">
-            <label className="text-[10px] uppercase tracking-wider text-text-muted">
+            <label className="text-micro uppercase tracking-wider text-text-muted">
               Season
             </label>
             {loadingSeasons ? (
@@ -177,7 +259,7 @@ export function EpisodeResolveModal({ job, groupId, onClose, onSaved }: Props) {
 
           {/* Episodes list */}
           <div className="space-y-1">
-            <p className="text-[10px] uppercase tracking-wider text-text-muted">
+            <p className="text-micro uppercase tracking-wider text-text-muted">
               Episodes
             </p>
             {loadingEpisodes ? (
@@ -209,7 +291,7 @@ export function EpisodeResolveModal({ job, groupId, onClose, onSaved }: Props) {
                           {ep.name}
                         </p>
                         {ep.air_date && (
-                          <p className="text-text-muted text-[10px]">
+                          <p className="text-text-muted text-micro">
                             {ep.air_date}
                           </p>
                         )}
@@ -1,35 +1,262 @@
 "use client";
 
 import { useAppStore } from "@/lib/store";
-import { bulkAction } from "@/lib/api";
+import {
+  bulkAction,
+  triggerMatch,
+  fetchGroup,
+  emptyTrash,
+  fetchDuplicateGroups,
+  mergeDuplicateGroups,
+  mergeGroups,
+  type DuplicateGroupPair,
+} from "@/lib/api";
+import { useToastStore } from "./Toast";
+import { ConfirmModal } from "./ConfirmModal";
+import { useState } from "react";
+
+// Toast action that opens a group in the match panel
+function detailsAction(groupId: number) {
+  return {
+    label: "Details",
+    onClick: () => {
+      fetchGroup(groupId).then((data) => {
+        if (!data.error) useAppStore.getState().setActiveGroup(data);
+      });
+    },
+  };
+}
 
 const STATUSES = [
-  "scanned", "matched", "ambiguous", "confirmed",
+  "scanned", "matched", "ambiguous", "unmatched", "confirmed",
   "transferring", "completed", "failed", "skipped",
 ];
 
 const MEDIA_TYPES = ["movie", "tv", "unknown"];
 
+// Bulk actions that ask for confirmation first
+const CONFIRM_ACTIONS: Record<string, { verb: string; label: string }> = {
+  delete: { verb: "Move", label: "Move to Trash" },
+  purge: { verb: "Permanently delete", label: "Delete Forever" },
+  skip: { verb: "Skip", label: "Skip" },
+  // Not a bulk action on the selection: purges everything in the trash
+  emptyTrash: { verb: "Permanently delete", label: "Empty Trash" },
+};
+
+const CHANGE_FILTERS = [
+  { value: "any", label: "New or missing" },
+  { value: "new", label: "New since last scan" },
+  { value: "missing", label: "Missing on disk" },
+];
+
+// Per-file gaps that would make a TV group name badly, groups whose last
+// match attempt couldn't reach TMDB, and groups with a file that failed to transfer
+const ISSUE_FILTERS = [
+  { value: "unresolved", label: "Missing season/episode" },
+  { value: "no_title", label: "Missing episode title" },
+  { value: "match_error", label: "Match failed" },
+  { value: "transfer_failed", label: "Transfer failed" },
+];
+
 export function Filters({ onRefresh }: { onRefresh: () => void }) {
   const {
     statusFilter,
     setStatusFilter,
     mediaTypeFilter,
     setMediaTypeFilter,
+    changeFilter,
+    setChangeFilter,
+    labelFilter,
+    setLabelFilter,
+    issueFilter,
+    setIssueFilter,
+    labels,
+    trashView,
+    setTrashView,
     searchQuery,
     setSearchQuery,
     selectedGroupIds,
     clearSelection,
+    groups,
   } = useAppStore();
+  const [retitleOpen, setRetitleOpen] = useState(false);
+  const [retitleTitle, setRetitleTitle] = useState("");
+  const [retitleYear, setRetitleYear] = useState("");
+  const [pendingBulk, setPendingBulk] = useState<string | null>(null);
+  const [duplicates, setDuplicates] = useState<DuplicateGroupPair[] | null>(null);
+  const [mergeOpen, setMergeOpen] = useState(false);
+  const [mergeTargetId, setMergeTargetId] = useState<number | null>(null);
+  const [mergeConflicts, setMergeConflicts] = useState<{ id: number; title: string }[] | null>(null);
+
+  const requestBulk = (action: string) => {
+    if (CONFIRM_ACTIONS[action]) setPendingBulk(action);
+    else handleBulk(action);
+  };
+
+  const handleRetitle = async () => {
+    const ids = Object.keys(selectedGroupIds).map(Number);
+    const title = retitleTitle.trim();
+    if (ids.length === 0 || !title) return;
+    const result = await bulkAction("retitle", {
+      groupIds: ids,
+      title,
+      year: parseInt(retitleYear, 10) || null,
+    });
+    setRetitleOpen(false);
+    setRetitleTitle("");
+    setRetitleYear("");
+    clearSelection();
+    onRefresh();
+
+    const retitled = ids.length - (result.lockedSkipped || 0);
+    if (result.matchError) {
+      useToastStore.getState().addToast(result.matchError, "error", detailsAction(ids[0]));
+    } else if (result.offline) {
+      useToastStore.getState().addToast(
+        `Retitled ${retitled} group${retitled !== 1 ? "s" : ""}; TMDB is unreachable, so they weren't matched`,
+        "warning"
+      );
+    } else {
+      useToastStore.getState().addToast(
+        `Retitled ${retitled} group${retitled !== 1 ? "s" : ""}${result.lockedSkipped ? ` (${result.lockedSkipped} locked, unchanged)` : ""}: ${result.matched} matched, ${result.ambiguous} ambiguous, ${result.unmatched} with no results`,
+        "success"
+      );
+    }
+  };
+
+  const handleMerge = async (force = false) => {
+    const ids = Object.keys(selectedGroupIds).map(Number);
+    const targetId = mergeTargetId !== null && ids.includes(mergeTargetId) ? mergeTargetId : ids[0];
+    const sourceIds = ids.filter((id) => id !== targetId);
+    if (!targetId || sourceIds.length === 0) return;
+    const result = await mergeGroups(targetId, sourceIds, force);
+    if (result.conflicts) {
+      setMergeConflicts(result.conflicts);
+      return;
+    }
+    setMergeConflicts(null);
+    if (result.error) {
+      useToastStore.getState().addToast(result.error, "error", detailsAction(targetId));
+      return;
+    }
+    setMergeOpen(false);
+    setMergeTargetId(null);
+    clearSelection();
+    onRefresh();
+    useToastStore.getState().addToast(
+      `Merged ${result.merged} group${result.merged !== 1 ? "s" : ""} (${result.filesMoved} file${result.filesMoved !== 1 ? "s" : ""}) into ${result.target.tmdbTitle || result.target.folderName}`,
+      "success"
+    );
+  };
+
+  const handleFindDuplicates = async () => {
+    const { pairs } = await fetchDuplicateGroups();
+    if (pairs.length === 0) {
+      useToastStore.getState().addToast("No moved folders with a leftover missing group", "info");
+      return;
+    }
+    setDuplicates(pairs);
+  };
+
+  const handleMergeDuplicates = async (pairs: DuplicateGroupPair[]) => {
+    setDuplicates(null);
+    const { merged } = await mergeDuplicateGroups(pairs.map((p) => p.staleId));
+    onRefresh();
+    const titles = merged.map((m) => m.title).slice(0, 3).join(", ");
+    const more = merged.length > 3 ? ` and ${merged.length - 3} more` : "";
+    useToastStore.getState().addToast(
+      `Merged ${merged.length} duplicate group${merged.length !== 1 ? "s" : ""}${titles ? `: ${titles}${more}` : ""}`,
+      "success"
+    );
+  };
+
+  const handleRetryFailed = async () => {
+    const result = await triggerMatch({ failedOnly: true });
+    onRefresh();
+    if (result.error) {
+      useToastStore.getState().addToast(result.error, "error");
+      return;
+    }
+    if (result.offline) {
+      useToastStore.getState().addToast("TMDB is still unreachable; nothing was retried", "warning");
+      return;
+    }
+    const still = result.failed ? ` ${result.failed} still couldn't reach TMDB.` : "";
+    useToastStore.getState().addToast(
+      `Retried ${result.matched + result.ambiguous + result.unmatched + result.failed} groups: ${result.matched} matched, ${result.ambiguous} ambiguous.${still}`,
+      result.failed ? "warning" : "success"
+    );
+  };
 
   const handleBulk = async (action: string) => {
     const ids = Object.keys(selectedGroupIds).map(Number);
     if (ids.length === 0) return;
-    await bulkAction(action, { groupIds: ids });
+    const result = await bulkAction(action, { groupIds: ids });
     clearSelection();
     onRefresh();
+
+    if (result.lockedSkipped > 0) {
+      useToastStore.getState().addToast(
+        `${result.lockedSkipped} locked group${result.lockedSkipped !== 1 ? "s" : ""} kept ${result.lockedSkipped !== 1 ? "their" : "its"} match`,
+        "info"
+      );
+    }
+
+    if (result.unmatchedSkipped > 0) {
+      useToastStore.getState().addToast(
+        `${result.unmatchedSkipped} group${result.unmatchedSkipped !== 1 ? "s have" : " has"} no match to confirm; pick one from the match panel first`,
+        "error"
+      );
+    }
+
+    if (action === "skip") {
+      useToastStore.getState().addToast(
+        `Skipped ${ids.length} group${ids.length !== 1 ? "s" : ""}`,
+        "info",
+        {
+          label: "Undo",
+          onClick: async () => {
+            await bulkAction("unskip", { groupIds: ids, snapshot: result.previous });
+            onRefresh();
+          },
+        }
+      );
+    }
+
+    if (action === "delete") {
+      useToastStore.getState().addToast(
+        `Moved ${ids.length} group${ids.length !== 1 ? "s" : ""} to trash`,
+        "info",
+        {
+          label: "Undo",
+          onClick: async () => {
+            await bulkAction("restore", { groupIds: ids });
+            onRefresh();
+          },
+        }
+      );
+    }
+  };
+
+  const handleEmptyTrash = async () => {
+    const result = await emptyTrash();
+    onRefresh();
+    useToastStore.getState().addToast(
+      `Permanently deleted ${result.groups} group${result.groups !== 1 ? "s" : ""} and ${result.jobs} file${result.jobs !== 1 ? "s" : ""}`,
+      "success"
+    );
   };
 
+  const selectedIds = Object.keys(selectedGroupIds).map(Number);
+  const statusBreakdown = Object.entries(
+    groups
+      .filter((g) => selectedGroupIds[g.id])
+      .reduce<Record<string, number>>((acc, g) => {
+        acc[g.status] = (acc[g.status] || 0) + 1;
+        return acc;
+      }, {})
+  ).sort(([, a], [, b]) => b - a);
+
   return (
     <div className="flex flex-col gap-3 px-6 py-3 border-b border-border bg-bg-secondary/50">
       <div className="flex items-center gap-3 flex-wrap">
@@ -70,6 +297,87 @@ export function Filters({ onRefresh }: { onRefresh: () => void }) {
           ))}
         </select>
 
+        {/* Changes since last scan */}
+        <select
+          value={changeFilter || ""}
+          onChange={(e) => setChangeFilter(e.target.value || null)}
+          className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
+        >
+          <option value="">All groups</option>
+          {CHANGE_FILTERS.map((c) => (
+            <option key={c.value} value={c.value}>
+              {c.label}
+            </option>
+          ))}
+        </select>
+
+        <select
+          value={issueFilter || ""}
+          onChange={(e) => setIssueFilter(e.target.value || null)}
+          className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
+        >
+          <option value="">Any completeness</option>
+          {ISSUE_FILTERS.map((c) => (
+            <option key={c.value} value={c.value}>
+              {c.label}
+            </option>
+          ))}
+        </select>
+
+        {labels.length > 0 && (
+          <select
+            value={labelFilter ?? ""}
+            onChange={(e) => setLabelFilter(e.target.value ? Number(e.target.value) : null)}
+            className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
+          >
+            <option value="">All labels</option>
+            {labels.map((l) => (
+              <option key={l.id} value={l.id}>
+                {l.name}
+              </option>
+            ))}
+          </select>
+        )}
+
+        <button
+          onClick={() => setTrashView(!trashView)}
+          className={`px-3 py-1.5 text-sm rounded-md border transition-colors ${
+            trashView
+              ? "bg-accent/20 border-accent text-text-primary"
+              : "bg-bg-tertiary border-border text-text-secondary hover:text-text-primary"
+          }`}
+        >
+          Trash
+        </button>
+        {trashView && (
+          <button
+            onClick={() => requestBulk("emptyTrash")}
+            className="px-3 py-1.5 text-sm rounded-md bg-status-failed text-white hover:opacity-90 transition-opacity"
+          >
+            Empty Trash
+          </button>
+        )}
+
+        {(changeFilter === "missing" || changeFilter === "any") && !trashView && (
+          <button
+            onClick={handleFindDuplicates}
+            className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary border border-border text-text-secondary hover:text-text-primary transition-colors"
+            title="Merge missing groups into the group their moved folder was rescanned as"
+          >
+            Merge Duplicates
+          </button>
+        )}
+
+        {issueFilter === "match_error" && !trashView && (
+          <button
+            onClick={handleRetryFailed}
+            className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary border border-border text-text-secondary hover:text-text-primary transition-colors"
+            title="Match again every group whose last attempt couldn't reach TMDB"
+          >
+            Retry Failed Matches
+          </button>
+        )}
+
         <div className="flex-1" />
 
         {/* Bulk actions */}
@@ -78,30 +386,65 @@ export function Filters({ onRefresh }: { onRefresh: () => void }) {
             <span className="text-xs text-text-muted">
               {Object.keys(selectedGroupIds).length} selected:
             </span>
-            <button
-              onClick={() => handleBulk("confirm")}
-              className="px-2.5 py-1 text-xs rounded bg-status-confirmed text-white hover:opacity-90 transition-opacity"
-            >
-              Confirm
-            </button>
-            <button
-              onClick={() => handleBulk("skip")}
-              className="px-2.5 py-1 text-xs rounded bg-status-skipped text-white hover:opacity-90 transition-opacity"
-            >
-              Skip
-            </button>
-            <button
-              onClick={() => handleBulk("rematch")}
-              className="px-2.5 py-1 text-xs rounded bg-status-ambiguous text-white hover:opacity-90 transition-opacity"
-            >
-              Rematch
-            </button>
-            <button
-              onClick={() => handleBulk("delete")}
-              className="px-2.5 py-1 text-xs rounded bg-status-failed text-white hover:opacity-90 transition-opacity"
-            >
-              Delete
-            </button>
+            {trashView ? (
+              <>
+                <button
+                  onClick={() => requestBulk("restore")}
+                  className="px-2.5 py-1 text-xs rounded bg-status-confirmed text-white hover:opacity-90 transition-opacity"
+                >
+                  Restore
+                </button>
+                <button
+                  onClick={() => requestBulk("purge")}
+                  className="px-2.5 py-1 text-xs rounded bg-status-failed text-white hover:opacity-90 transition-opacity"
+                >
+                  Delete Forever
+                </button>
+              </>
+            ) : (
+              <>
+                <button
+                  onClick={() => requestBulk("confirm")}
+                  className="px-2.5 py-1 text-xs rounded bg-status-confirmed text-white hover:opacity-90 transition-opacity"
+                >
+                  Confirm
+                </button>
+                <button
+                  onClick={() => requestBulk("skip")}
+                  className="px-2.5 py-1 text-xs rounded bg-status-skipped text-white hover:opacity-90 transition-opacity"
+                >
+                  Skip
+                </button>
+                <button
+                  onClick={() => requestBulk("rematch")}
+                  className="px-2.5 py-1 text-xs rounded bg-status-ambiguous text-white hover:opacity-90 transition-opacity"
+                >
+                  Rematch
+                </button>
+                {selectedIds.length > 1 && (
+                  <button
+                    onClick={() => setMergeOpen(!mergeOpen)}
+                    className="px-2.5 py-1 text-xs rounded bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors"
+                    title="Move every file of the selected groups into one of them"
+                  >
+                    Merge&hellip;
+                  </button>
+                )}
+                <button
+                  onClick={() => setRetitleOpen(!retitleOpen)}
+                  className="px-2.5 py-1 text-xs rounded bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors"
+                  title="Set the same title on every selected group and rematch"
+                >
+                  Retitle&hellip;
+                </button>
+                <button
+                  onClick={() => requestBulk("delete")}
+                  className="px-2.5 py-1 text-xs rounded bg-status-failed text-white hover:opacity-90 transition-opacity"
+                >
+                  Delete
+                </button>
+              </>
+            )}
             <button
               onClick={clearSelection}
               className="px-2.5 py-1 text-xs rounded bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors"
@@ -111,6 +454,173 @@ export function Filters({ onRefresh }: { onRefresh: () => void }) {
           </div>
         )}
       </div>
+
+      {retitleOpen && Object.keys(selectedGroupIds).length > 0 && (
+        <form
+          onSubmit={(e) => {
+            e.preventDefault();
+            handleRetitle();
+          }}
+          className="flex items-center gap-2 justify-end"
+        >
+          <input
+            type="text"
+            placeholder="Title"
+            value={retitleTitle}
+            onChange={(e) => setRetitleTitle(e.target.value)}
+            autoFocus
+            className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent w-64"
+          />
+          <input
+            type="number"
+            placeholder="Year"
+            value={retitleYear}
+            onChange={(e) => setRetitleYear(e.target.value)}
+            className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent w-24"
+          />
+          <button
+            type="submit"
+            disabled={!retitleTitle.trim()}
+            className="px-2.5 py-1.5 text-xs rounded bg-accent text-white hover:bg-accent-hover disabled:opacity-50 transition-colors"
+          >
+            Apply &amp; Match
+          </button>
+          <button
+            type="button"
+            onClick={() => setRetitleOpen(false)}
+            className="px-2.5 py-1.5 text-xs rounded bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors"
+          >
+            Cancel
+          </button>
+        </form>
+      )}
+
+      {mergeOpen && selectedIds.length > 1 && (
+        <form
+          onSubmit={(e) => {
+            e.preventDefault();
+            handleMerge();
+          }}
+          className="flex items-center gap-2 justify-end"
+        >
+          <span className="text-xs text-text-muted">Merge into</span>
+          <select
+            value={mergeTargetId !== null && selectedIds.includes(mergeTargetId) ? mergeTargetId : selectedIds[0]}
+            onChange={(e) => setMergeTargetId(Number(e.target.value))}
+            className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent w-64"
+          >
+            {groups
+              .filter((g) => selectedGroupIds[g.id])
+              .map((g) => (
+                <option key={g.id} value={g.id}>
+                  {g.tmdbTitle || g.parsedTitle || g.folderName}
+                  {g.tmdbId ? " (matched)" : ""}
+                </option>
+              ))}
+          </select>
+          <button
+            type="submit"
+            className="px-2.5 py-1.5 text-xs rounded bg-accent text-white hover:bg-accent-hover transition-colors"
+          >
+            Merge
+          </button>
+          <button
+            type="button"
+            onClick={() => setMergeOpen(false)}
+            className="px-2.5 py-1.5 text-xs rounded bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors"
+          >
+            Cancel
+          </button>
+        </form>
+      )}
+
+      {mergeConflicts && (
+        <ConfirmModal
+          title="Merge groups matched to different titles?"
+          confirmLabel="Merge Anyway"
+          destructive
+          onConfirm={() => handleMerge(true)}
+          onClose={() => setMergeConflicts(null)}
+        >
+          <p className="mb-2">
+            These groups are matched to another title. Their files will take on the target group&apos;s
+            match and the groups themselves will be deleted:
+          </p>
+          <ul className="list-disc pl-5 space-y-1">
+            {mergeConflicts.map((c) => (
+              <li key={c.id} className="text-text-primary">{c.title}</li>
+            ))}
+          </ul>
+        </ConfirmModal>
+      )}
+
+      {pendingBulk && (
+        <ConfirmModal
+          title={
+            pendingBulk === "emptyTrash"
+              ? `${CONFIRM_ACTIONS.emptyTrash.verb} everything in the trash?`
+              : `${CONFIRM_ACTIONS[pendingBulk].verb} ${selectedIds.length} group${
+                  selectedIds.length !== 1 ? "s" : ""
+                }${pendingBulk === "delete" ? " to trash" : ""}?`
+          }
+          confirmLabel={CONFIRM_ACTIONS[pendingBulk].label}
+          destructive={pendingBulk !== "skip"}
+          onConfirm={() => {
+            const action = pendingBulk;
+            setPendingBulk(null);
+            if (action === "emptyTrash") handleEmptyTrash();
+            else handleBulk(action);
+          }}
+          onClose={() => setPendingBulk(null)}
+        >
+          {pendingBulk === "emptyTrash" ? (
+            <p>Every group and file in the trash is deleted from ReelName. Files on disk are not touched.</p>
+          ) : (
+            <ul className="space-y-0.5">
+              {statusBreakdown.map(([status, count]) => (
+                <li key={status} className="flex justify-between">
+                  <span className="capitalize">{status}</span>
+                  <span className="font-mono text-text-muted">{count}</span>
+                </li>
+              ))}
+            </ul>
+          )}
+          {(pendingBulk === "purge" || pendingBulk === "emptyTrash") && (
+            <p className="mt-2 text-xs text-error">This cannot be undone.</p>
+          )}
+        </ConfirmModal>
+      )}
+
+      {duplicates && (
+        <ConfirmModal
+          title={`Merge ${duplicates.length} duplicate group${duplicates.length !== 1 ? "s" : ""}?`}
+          confirmLabel="Merge"
+          onConfirm={() => handleMergeDuplicates(duplicates)}
+          onClose={() => setDuplicates(null)}
+        >
+          <p className="mb-2">
+            Matches, episode fixes, and labels are copied to the new group; the missing one goes to the trash.
+          </p>
+          <ul className="space-y-1.5 max-h-60 overflow-y-auto">
+            {duplicates.map((pair) => (
+              <li key={pair.staleId}>
+                <div className="text-text-primary">
+                  {pair.title}
+                  <span className="ml-1 text-text-muted">
+                    ({pair.reason === "folder" ? "same folder name" : "same TMDB match"})
+                  </span>
+                </div>
+                <div className="font-mono text-micro text-text-muted truncate" title={pair.staleFolder}>
+                  {pair.staleFolder}
+                </div>
+                <div className="font-mono text-micro text-text-muted truncate" title={pair.liveFolder}>
+                  &rarr; {pair.liveFolder}
+                </div>
+              </li>
+            ))}
+          </ul>
+        </ConfirmModal>
+      )}
     </div>
   );
 }
@@ -1,6 +1,17 @@
 "use client";
 
+import { useEffect, useState } from "react";
 import { useAppStore } from "@/lib/store";
+import { cancelMatch, fetchLibraryStats } from "@/lib/api";
+import type { MatchProgress } from "@/lib/matcher";
+import type { LibraryStats } from "@/lib/stats";
+
+const STAT_STATUSES = ["scanned", "matched", "ambiguous", "confirmed", "skipped"] as const;
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+  return `${(bytes / (1024 * 1024 * 1024)).toFixed(2)} GB`;
+}
 
 export function Header({
   onScan,
@@ -19,6 +30,27 @@ export function Header({
     setTransferDrawerOpen,
   } = useAppStore();
 
+  const [matchProgress, setMatchProgress] = useState<MatchProgress | null>(null);
+  const [statsOpen, setStatsOpen] = useState(false);
+  const [stats, setStats] = useState<LibraryStats | null>(null);
+
+  // The group list reloads after every scan, match and transfer, so refetch with it
+  useEffect(() => {
+    if (!statsOpen) return;
+    fetchLibraryStats().then(setStats);
+  }, [statsOpen, groups]);
+
+  // Scans and matches both end in a match pass; follow it while working
+  useEffect(() => {
+    if (!scanning) {
+      setMatchProgress(null);
+      return;
+    }
+    const es = new EventSource("/api/match/progress");
+    es.onmessage = (event) => setMatchProgress(JSON.parse(event.data));
+    return () => es.close();
+  }, [scanning]);
+
   const totalFiles = groups.reduce((sum, g) => sum + g.totalFileCount, 0);
   const selectedCount = Object.keys(selectedGroupIds).length;
 
@@ -44,6 +76,69 @@ export function Header({
       </div>
 
       <div className="flex items-center gap-3">
+        {matchProgress?.running && (
+          <div className="flex items-center gap-2 text-xs text-text-muted" title={matchProgress.currentTitle || ""}>
+            <span className="max-w-48 truncate">
+              Matching {matchProgress.currentTitle}
+            </span>
+            <div className="w-24 h-1.5 rounded-full bg-bg-tertiary overflow-hidden">
+              <div
+                className="h-full bg-accent transition-all"
+                style={{
+                  width: `${matchProgress.total ? (matchProgress.completed / matchProgress.total) * 100 : 0}%`,
+                }}
+              />
+            </div>
+            <span className="font-mono">
+              {matchProgress.completed}/{matchProgress.total}
+            </span>
+            <button
+              onClick={() => cancelMatch()}
+              className="text-text-muted hover:text-error"
+              title="Cancel matching"
+            >
+              &times;
+            </button>
+          </div>
+        )}
+        <div className="relative">
+          <button
+            onClick={() => setStatsOpen(!statsOpen)}
+            className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary text-text-secondary hover:bg-bg-hover hover:text-text-primary transition-colors"
+          >
+            Stats
+          </button>
+          {statsOpen && stats && (
+            <div className="absolute right-0 top-full mt-2 z-40 w-64 p-3 rounded-lg border border-border bg-bg-secondary shadow-xl text-xs space-y-3">
+              <div className="space-y-1">
+                {STAT_STATUSES.map((status) => (
+                  <div key={status} className="flex justify-between">
+                    <span className="text-text-muted capitalize">{status}</span>
+                    <span className="font-mono text-text-primary">{stats.groupsByStatus[status] ?? 0}</span>
+                  </div>
+                ))}
+                <div className="flex justify-between">
+                  <span className="text-text-muted">Unmatched</span>
+                  <span className="font-mono text-status-unmatched">{stats.unmatchedGroups}</span>
+                </div>
+              </div>
+              <div className="space-y-1 pt-2 border-t border-border">
+                {Object.entries(stats.sizeByMediaType).map(([type, size]) => (
+                  <div key={type} className="flex justify-between">
+                    <span className="text-text-muted">{type === "tv" ? "TV" : type === "movie" ? "Movies" : "Unknown"}</span>
+                    <span className="font-mono text-text-primary">{formatSize(size)}</span>
+                  </div>
+                ))}
+              </div>
+              <div className="flex justify-between pt-2 border-t border-border">
+                <span className="text-text-muted">Pending transfer</span>
+                <span className="font-mono text-text-primary">
+                  {formatSize(stats.pendingTransferSize)} ({stats.pendingTransferCount})
+                </span>
+              </div>
+            </div>
+          )}
+        </div>
         <button
           onClick={() => setTransferDrawerOpen(!transferDrawerOpen)}
           className="px-3 py-1.5 text-sm rounded-md bg-bg-tertiary text-text-secondary hover:bg-bg-hover hover:text-text-primary transition-colors"
@@ -1,7 +1,7 @@
 "use client";
 
 import { useEffect } from "react";
-import { useAppStore } from "@/lib/store";
+import { useAppStore, getTotalPages } from "@/lib/store";
 import { fetchGroup } from "@/lib/api";
 
 export function KeyboardShortcuts({
@@ -21,6 +21,10 @@ export function KeyboardShortcuts({
     setSettingsOpen,
     matchPanelOpen,
     setMatchPanelOpen,
+    page,
+    setPage,
+    totalGroups,
+    settings,
   } = useAppStore();
 
   useEffect(() => {
@@ -73,6 +77,15 @@ export function KeyboardShortcuts({
         return;
       }
 
+      // PageUp/PageDown - previous/next page of groups
+      if (e.key === "PageUp" || e.key === "PageDown") {
+        e.preventDefault();
+        const totalPages = getTotalPages(totalGroups, settings.page_size);
+        const target = e.key === "PageDown" ? page + 1 : page - 1;
+        if (target >= 1 && target <= totalPages) setPage(target);
+        return;
+      }
+
       // Arrow up/down - navigate groups
       if (e.key === "ArrowDown" || e.key === "ArrowUp") {
         e.preventDefault();
@@ -102,6 +115,10 @@ export function KeyboardShortcuts({
     setActiveGroup,
     setMatchPanelOpen,
     setSettingsOpen,
+    page,
+    setPage,
+    totalGroups,
+    settings.page_size,
     onRefresh,
     onScan,
   ]);
@@ -1,28 +1,90 @@
 "use client";
 
 import { useAppStore } from "@/lib/store";
-import { updateGroup, searchTmdb } from "@/lib/api";
+import { candidatePosterSize, confidenceColor } from "@/lib/theme";
+import { detectMixedContent } from "@/lib/mixed";
+import { DestinationTree } from "./DestinationTree";
+import {
+  updateGroup,
+  updateJob,
+  bulkAction,
+  fetchGroup,
+  searchTmdb,
+  posterUrl,
+  revealInFileManager,
+  alignEpisodesByTitle,
+  researchGroupCandidates,
+  splitGroup,
+  createLabel,
+  setGroupLabels,
+} from "@/lib/api";
 import { useToastStore } from "./Toast";
-import { useState } from "react";
+import { useEffect, useRef, useState } from "react";
 import { StatusBadge, FileCategoryBadge } from "./StatusBadge";
 import { EpisodeResolveModal } from "./EpisodeResolveModal";
-import type { MatchCandidate } from "@/lib/db/schema";
+import { normalizeQuality, parseFileName, parseFolderSeason } from "@/lib/parser";
+import type { MatchCandidate, Label } from "@/lib/db/schema";
 import type { JobWithPreview } from "@/lib/store";
 
-const TMDB_IMG_BASE = "https://image.tmdb.org/t/p/w185";
+const FILE_CATEGORIES = [
+  { value: "movie", label: "Movie" },
+  { value: "episode", label: "Episode" },
+  { value: "special", label: "Special" },
+  { value: "extra", label: "Extra" },
+  { value: "metadata", label: "Artwork" },
+];
+
+const SEARCH_TYPES = [
+  { value: "unknown", label: "Auto" },
+  { value: "movie", label: "Movie" },
+  { value: "tv", label: "TV" },
+];
+
+const EXTRA_TYPES = [
+  { value: "behind_the_scenes", label: "Behind the Scenes" },
+  { value: "deleted_scenes", label: "Deleted Scenes" },
+  { value: "featurettes", label: "Featurettes" },
+  { value: "interviews", label: "Interviews" },
+  { value: "scenes", label: "Scenes" },
+  { value: "shorts", label: "Shorts" },
+  { value: "trailers", label: "Trailers" },
+  { value: "other", label: "Other" },
+];
 
 export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
   const {
     activeGroup,
     matchPanelOpen,
     setMatchPanelOpen,
+    setActiveGroup,
     updateGroup: updateStoreGroup,
+    labels: allLabels,
+    setLabels,
   } = useAppStore();
   const [manualQuery, setManualQuery] = useState("");
   const [searchResults, setSearchResults] = useState<MatchCandidate[]>([]);
   const [searching, setSearching] = useState(false);
   const [resolveJob, setResolveJob] = useState<JobWithPreview | null>(null);
   const [editing, setEditing] = useState(false);
+  const [pathEditJobId, setPathEditJobId] = useState<number | null>(null);
+  const [pathDraft, setPathDraft] = useState("");
+  const [aligning, setAligning] = useState(false);
+  const [searchType, setSearchType] = useState("unknown");
+  const [researching, setResearching] = useState(false);
+  const [newLabelName, setNewLabelName] = useState("");
+  const [showTree, setShowTree] = useState(false);
+  // Files picked for "Move to new group"
+  const [splitJobIds, setSplitJobIds] = useState<number[]>([]);
+
+  // The search constraint is per group; start each group on its own type
+  const activeGroupId = activeGroup?.id;
+  const activeMediaType = activeGroup?.mediaType;
+  useEffect(() => {
+    setSearchType(activeMediaType || "unknown");
+  }, [activeGroupId, activeMediaType]);
+  useEffect(() => {
+    setSplitJobIds([]);
+  }, [activeGroupId]);
   const [editFields, setEditFields] = useState({
     parsedTitle: "",
     parsedYear: "",
@@ -32,7 +94,7 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
 
   if (!activeGroup) {
     return (
-      <div className="w-[420px] border-l border-border bg-bg-secondary flex flex-col h-full overflow-hidden">
+      <div className="w-[26.25rem] border-l border-border bg-bg-secondary flex flex-col h-full overflow-hidden">
         <div className="flex items-center justify-between px-4 py-3 border-b border-border">
           <h2 className="text-sm font-semibold text-text-primary">
             Match Details
@@ -70,9 +132,25 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
       matchConfidence: candidate.confidence,
       mediaType: candidate.mediaType as "movie" | "tv" | "unknown",
     };
-    await updateGroup(activeGroup.id, updates);
+    const result = await updateGroup(activeGroup.id, updates);
+    if (result.error) {
+      useToastStore.getState().addToast(result.error, "error");
+      return;
+    }
     updateStoreGroup(activeGroup.id, updates);
-    onRefresh();
+    // The PATCH fetched episode titles for TV matches; reload so the file list shows them
+    await reloadActiveGroup();
+  };
+
+  const handleToggleLock = async () => {
+    const updates = { locked: !activeGroup.locked };
+    const result = await updateGroup(activeGroup.id, updates);
+    if (result.error) {
+      useToastStore.getState().addToast(result.error, "error");
+      return;
+    }
+    updateStoreGroup(activeGroup.id, updates);
+    await reloadActiveGroup();
   };
 
   const handleManualSearch = async () => {
@@ -80,7 +158,7 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
     setSearching(true);
     const data = await searchTmdb(
       manualQuery,
-      activeGroup.mediaType !== "unknown" ? activeGroup.mediaType : undefined,
+      searchType !== "unknown" ? searchType : undefined,
       activeGroup.parsedYear ?? undefined
     );
     setSearchResults(data.results || []);
@@ -113,16 +191,179 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
   };
 
   const handleSkip = async () => {
-    await updateGroup(activeGroup.id, { status: "skipped" });
-    updateStoreGroup(activeGroup.id, { status: "skipped" });
+    const groupId = activeGroup.id;
+    // The bulk skip returns each file's prior status so Undo can restore them
+    const { previous } = await bulkAction("skip", { groupIds: [groupId] });
+    updateStoreGroup(groupId, { status: "skipped" });
     setMatchPanelOpen(false);
     onRefresh();
+    useToastStore.getState().addToast("Group skipped", "info", {
+      label: "Undo",
+      onClick: async () => {
+        await bulkAction("unskip", { groupIds: [groupId], snapshot: previous });
+        onRefresh();
+      },
+    });
+  };
+
+  // Re-fetch so preview names reflect the job's new category/season
+  const reloadActiveGroup = async () => {
+    const data = await fetchGroup(activeGroup.id);
+    if (!data.error) setActiveGroup(data);
+    onRefresh();
+  };
+
+  const handleJobCategoryChange = async (job: JobWithPreview, category: string) => {
+    const updates: Record<string, unknown> = { fileCategory: category };
+    if (category === "special") {
+      updates.parsedSeason = 0;
+    } else if (job.fileCategory === "special") {
+      // Leaving Specials: take the season back from the file name, or from a
+      // season folder like "Season 02" for packs numbered "01.mkv"
+      const folderName = job.sourcePath.split(/[\\/]/).slice(-2, -1)[0] ?? "";
+      const folderSeason = parseFolderSeason(folderName);
+      updates.parsedSeason =
+        parseFileName(job.fileName).season ?? (folderSeason ? folderSeason : null);
+    }
+    if (category === "extra") {
+      updates.extraType = job.extraType || "other";
+    } else {
+      updates.extraType = null;
+    }
+    await updateJob(job.id, updates);
+    await reloadActiveGroup();
+  };
+
+  const handleJobExtraTypeChange = async (job: JobWithPreview, extraType: string) => {
+    await updateJob(job.id, { extraType });
+    await reloadActiveGroup();
+  };
+
+  const startPathEdit = (job: JobWithPreview) => {
+    setPathEditJobId(job.id);
+    setPathDraft(job.destinationOverride || job.previewName || "");
+  };
+
+  // null clears the override so the job goes back to the naming template
+  const handleSaveDestination = async (job: JobWithPreview, destinationOverride: string | null) => {
+    const result = await updateJob(job.id, { destinationOverride });
+    if (result.error) {
+      useToastStore.getState().addToast(result.error, "error");
+      return;
+    }
+    setPathEditJobId(null);
+    await reloadActiveGroup();
+  };
+
+  const handleSearchTypeChange = async (mediaType: string) => {
+    setSearchType(mediaType);
+    setResearching(true);
+    try {
+      const result = await researchGroupCandidates(activeGroup.id, mediaType);
+      if (result.error) {
+        useToastStore.getState().addToast(result.error, "error");
+        return;
+      }
+      await reloadActiveGroup();
+    } finally {
+      setResearching(false);
+    }
+  };
+
+  const applyLabels = async (labelIds: number[]) => {
+    const result = await setGroupLabels(activeGroup.id, labelIds);
+    if (result.error) {
+      useToastStore.getState().addToast(result.error, "error");
+      return;
+    }
+    setActiveGroup({ ...activeGroup, labels: result.labels });
+    onRefresh();
+  };
+
+  const handleToggleLabel = (label: Label) => {
+    const current = (activeGroup.labels || []).map((l) => l.id);
+    applyLabels(
+      current.includes(label.id) ? current.filter((id) => id !== label.id) : [...current, label.id]
+    );
+  };
+
+  const handleAddLabel = async () => {
+    const name = newLabelName.trim();
+    if (!name) return;
+    const label = await createLabel(name);
+    if (label.error) {
+      useToastStore.getState().addToast(label.error, "error");
+      return;
+    }
+    if (!allLabels.some((l) => l.id === label.id)) {
+      setLabels([...allLabels, label].sort((a, b) => a.name.localeCompare(b.name)));
+    }
+    setNewLabelName("");
+    const current = (activeGroup.labels || []).map((l) => l.id);
+    if (!current.includes(label.id)) await applyLabels([...current, label.id]);
+  };
+
+  const handleAlignByTitle = async () => {
+    setAligning(true);
+    try {
+      const result = await alignEpisodesByTitle(activeGroup.id);
+      if (result.error) {
+        useToastStore.getState().addToast(result.error, "error");
+        return;
+      }
+      const skipped = result.skipped ? `, ${result.skipped} left unchanged` : "";
+      useToastStore
+        .getState()
+        .addToast(`Aligned ${result.aligned} file${result.aligned !== 1 ? "s" : ""} by episode title${skipped}`, "success");
+      await reloadActiveGroup();
+    } finally {
+      setAligning(false);
+    }
+  };
+
+  const handleSplit = async (jobIds: number[]) => {
+    const result = await splitGroup(activeGroup.id, jobIds);
+    if (result.error) {
+      useToastStore.getState().addToast(result.error, "error");
+      return;
+    }
+    useToastStore
+      .getState()
+      .addToast(`Moved ${jobIds.length} file${jobIds.length !== 1 ? "s" : ""} to new group "${result.group.folderName}"`, "success");
+    setSplitJobIds([]);
+    await reloadActiveGroup();
+  };
+
+  const toggleSplitJob = (jobId: number) => {
+    setSplitJobIds((ids) => (ids.includes(jobId) ? ids.filter((id) => id !== jobId) : [...ids, jobId]));
+  };
+
+  const handleReveal = async (opts: { groupId?: number; jobId?: number }) => {
+    const result = await revealInFileManager(opts);
+    if (result.error) {
+      useToastStore.getState().addToast(result.error, "error");
+    }
   };
 
   const candidates = activeGroup.candidates || [];
+  const mixed = detectMixedContent(activeGroup.jobs);
+  // Locked, trashed and in-flight groups can't be split
+  const canSplit =
+    !activeGroup.locked &&
+    !activeGroup.deletedAt &&
+    activeGroup.status !== "queued" &&
+    activeGroup.status !== "transferring" &&
+    activeGroup.jobs.length > 1;
+  const destinationPaths = activeGroup.jobs
+    .map((j) => j.destinationOverride || j.previewName)
+    .filter((p): p is string => !!p);
+  // Don't repeat a manual result that's already listed as a candidate
+  const newSearchResults = searchResults.filter(
+    (r) => !candidates.some((c) => c.tmdbId === r.tmdbId && c.mediaType === r.mediaType)
+  );
 
   return (
-      <div className="w-[420px] border-l border-border bg-bg-secondary flex flex-col h-full overflow-hidden">
+      <div className="w-[26.25rem] border-l border-border bg-bg-secondary flex flex-col h-full overflow-hidden">
 
           {/* Header */}
           <div className="flex items-center justify-between px-4 py-3 border-b border-border">
@@ -157,12 +398,49 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
                     Edit
                   </button>
                 )}
+                <button
+                  onClick={() => handleReveal({ groupId: activeGroup.id })}
+                  className="text-xs text-accent hover:text-accent-hover"
+                  title={activeGroup.folderPath}
+                >
+                  Open Folder
+                </button>
+                <button
+                  onClick={handleToggleLock}
+                  className={`text-xs ${
+                    activeGroup.locked ? "text-info hover:text-accent-hover" : "text-accent hover:text-accent-hover"
+                  }`}
+                  title={
+                    activeGroup.locked
+                      ? "Locked: bulk rematches and rescans leave this match alone"
+                      : "Protect this match from bulk rematches and rescans"
+                  }
+                >
+                  {activeGroup.locked ? "Unlock" : "Lock"}
+                </button>
               </div>
 
+              {mixed && !activeGroup.deletedAt && (
+                <div className="p-2 rounded border border-warning bg-warning/10 text-xs text-warning space-y-1">
+                  <p>
+                    <span className="font-semibold">Mixed content — review.</span> {mixed.reason}; one
+                    match would name them all after the same title.
+                  </p>
+                  {canSplit && (
+                    <button
+                      onClick={() => handleSplit(mixed.suggestedJobIds)}
+                      className="text-accent hover:text-accent-hover"
+                    >
+                      Split {mixed.suggestedJobIds.length} file{mixed.suggestedJobIds.length !== 1 ? "s" : ""} into a new group
+                    </button>
+                  )}
+                </div>
+              )}
+
               {editing ? (
                 <div className="space-y-2 p-2 rounded bg-bg-tertiary/50">
                   <div>
-                    <label className="text-[10px] uppercase tracking-wider text-text-muted">
+                    <label className="text-micro uppercase tracking-wider text-text-muted">
                       Title
                     </label>
                     <input
@@ -177,7 +455,7 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
                     />
                   </div>
                   <div>
-                    <label className="text-[10px] uppercase tracking-wider text-text-muted">
+                    <label className="text-micro uppercase tracking-wider text-text-muted">
                       Year
                     </label>
                     <input
@@ -207,42 +485,135 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
                   </div>
                 </div>
               ) : (
-                <div>
-                  <p className="text-sm text-text-primary font-medium">
-                    {activeGroup.tmdbTitle ||
-                      activeGroup.parsedTitle ||
-                      activeGroup.folderName}
-                  </p>
-                  <div className="flex gap-4 text-xs text-text-muted mt-1">
-                    {(activeGroup.tmdbYear || activeGroup.parsedYear) && (
-                      <span>
-                        Year:{" "}
-                        {activeGroup.tmdbYear || activeGroup.parsedYear}
-                      </span>
-                    )}
-                    <span className="uppercase">{activeGroup.mediaType}</span>
+                <div className="flex gap-3">
+                  {activeGroup.tmdbPosterPath && (
+                    <img
+                      src={posterUrl(activeGroup.tmdbPosterPath)}
+                      alt={activeGroup.tmdbTitle || ""}
+                      className="w-16 h-24 rounded object-cover flex-shrink-0"
+                    />
+                  )}
+                  <div className="min-w-0">
+                    <p className="text-sm text-text-primary font-medium">
+                      {activeGroup.tmdbTitle ||
+                        activeGroup.parsedTitle ||
+                        activeGroup.folderName}
+                    </p>
+                    <div className="flex gap-4 text-xs text-text-muted mt-1">
+                      {(activeGroup.tmdbYear || activeGroup.parsedYear) && (
+                        <span>
+                          Year:{" "}
+                          {activeGroup.tmdbYear || activeGroup.parsedYear}
+                        </span>
+                      )}
+                      <span className="uppercase">{activeGroup.mediaType}</span>
+                    </div>
                   </div>
                 </div>
               )}
             </div>
 
+            {/* Labels */}
+            <div className="space-y-1">
+              <h3 className="text-xs font-semibold uppercase tracking-wider text-text-muted">
+                Labels
+              </h3>
+              <div className="flex flex-wrap items-center gap-1">
+                {allLabels.map((label) => {
+                  const applied = activeGroup.labels?.some((l) => l.id === label.id);
+                  return (
+                    <button
+                      key={label.id}
+                      onClick={() => handleToggleLabel(label)}
+                      className={`px-1.5 py-0.5 rounded text-micro font-medium border transition-opacity ${
+                        applied ? "text-white" : "text-text-secondary opacity-60 hover:opacity-100"
+                      }`}
+                      style={
+                        applied
+                          ? { backgroundColor: label.color, borderColor: label.color }
+                          : { borderColor: label.color }
+                      }
+                    >
+                      {label.name}
+                    </button>
+                  );
+                })}
+                <input
+                  type="text"
+                  value={newLabelName}
+                  onChange={(e) => setNewLabelName(e.target.value)}
+                  onKeyDown={(e) => e.key === "Enter" && handleAddLabel()}
+                  placeholder="+ label"
+                  className="w-20 px-1.5 py-0.5 text-micro rounded bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                />
+              </div>
+            </div>
+
             {/* Episode list */}
             {activeGroup.jobs.length > 0 && (
               <div className="space-y-1">
-                <h3 className="text-xs font-semibold uppercase tracking-wider text-text-muted">
-                  Files ({activeGroup.jobs.length})
-                </h3>
+                <div className="flex items-center justify-between">
+                  <h3 className="text-xs font-semibold uppercase tracking-wider text-text-muted">
+                    Files ({activeGroup.jobs.length})
+                  </h3>
+                  {activeGroup.tmdbId &&
+                    activeGroup.mediaType === "tv" &&
+                    activeGroup.jobs.some(
+                      (j) => j.parsedEpisode == null && j.fileCategory !== "extra" && j.fileCategory !== "metadata"
+                    ) && (
+                      <button
+                        onClick={handleAlignByTitle}
+                        disabled={aligning}
+                        className="text-xs text-accent hover:text-accent-hover disabled:opacity-50"
+                        title="Assign season/episode to unnumbered files by comparing their names to episode titles"
+                      >
+                        {aligning ? "Matching..." : "Match by episode title"}
+                      </button>
+                    )}
+                </div>
+                {splitJobIds.length > 0 && (
+                  <div className="flex items-center gap-2 text-xs">
+                    <button
+                      onClick={() => handleSplit(splitJobIds)}
+                      disabled={splitJobIds.length >= activeGroup.jobs.length}
+                      className="px-2 py-0.5 rounded bg-accent text-white hover:bg-accent-hover disabled:opacity-50 transition-colors"
+                      title={
+                        splitJobIds.length >= activeGroup.jobs.length
+                          ? "Leave at least one file in this group"
+                          : "Create a new, unmatched group from the checked files"
+                      }
+                    >
+                      Move {splitJobIds.length} to new group
+                    </button>
+                    <button
+                      onClick={() => setSplitJobIds([])}
+                      className="text-text-muted hover:text-text-primary"
+                    >
+                      Clear
+                    </button>
+                  </div>
+                )}
                 <div className="max-h-80 overflow-y-auto space-y-1">
                   {activeGroup.jobs.map((job) => {
                     const canResolve = !!activeGroup.tmdbId && activeGroup.mediaType === "tv";
                     return (
+                    <div key={job.id}>
                     <div
-                      key={job.id}
                       onClick={canResolve ? () => setResolveJob(job) : undefined}
                       className={`flex items-center gap-2 py-1 px-2 rounded bg-bg-tertiary/30 text-xs${
                         canResolve ? " cursor-pointer hover:bg-bg-hover/50 transition-colors" : ""
                       }`}
                     >
+                      {canSplit && (
+                        <input
+                          type="checkbox"
+                          checked={splitJobIds.includes(job.id)}
+                          onClick={(e) => e.stopPropagation()}
+                          onChange={() => toggleSplitJob(job.id)}
+                          className="accent-accent flex-shrink-0"
+                          title="Pick to move into a new group"
+                        />
+                      )}
                       <FileCategoryBadge
                         category={job.fileCategory || "episode"}
                       />
@@ -253,9 +624,110 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
                           {String(job.parsedEpisode).padStart(2, "0")}
                         </span>
                       ) : null}
-                      <span className="text-text-muted truncate flex-1">
+                      <span
+                        className="text-text-muted truncate flex-1"
+                        title={job.previewName || job.fileName}
+                      >
                         {job.tmdbEpisodeTitle || job.fileName}
                       </span>
+                      {job.destinationOverride && (
+                        <span
+                          className="flex-shrink-0 text-micro text-warning"
+                          title={`Destination set manually: ${job.destinationOverride}`}
+                        >
+                          manual
+                        </span>
+                      )}
+                      {job.mediaResolution &&
+                        job.parsedQuality &&
+                        normalizeQuality(job.parsedQuality) !== job.mediaResolution && (
+                          <span
+                            className="flex-shrink-0 text-micro text-warning"
+                            title={`Filename says ${job.parsedQuality}, ffprobe found ${job.mediaResolution}`}
+                          >
+                            {job.mediaResolution}
+                          </span>
+                        )}
+                      {job.fileCategory === "extra" && (
+                        <select
+                          value={job.extraType || "other"}
+                          onClick={(e) => e.stopPropagation()}
+                          onChange={(e) => handleJobExtraTypeChange(job, e.target.value)}
+                          className="flex-shrink-0 px-1 py-0.5 text-micro rounded bg-bg-tertiary border border-border text-text-secondary focus:outline-none focus:border-accent"
+                          title="Extra type"
+                        >
+                          {EXTRA_TYPES.map((t) => (
+                            <option key={t.value} value={t.value}>
+                              {t.label}
+                            </option>
+                          ))}
+                        </select>
+                      )}
+                      <button
+                        onClick={(e) => {
+                          e.stopPropagation();
+                          handleReveal({ jobId: job.id });
+                        }}
+                        className="flex-shrink-0 text-text-muted hover:text-accent"
+                        title="Reveal file"
+                      >
+                        &#8599;
+                      </button>
+                      <button
+                        onClick={(e) => {
+                          e.stopPropagation();
+                          startPathEdit(job);
+                        }}
+                        className="flex-shrink-0 text-text-muted hover:text-accent"
+                        title="Edit destination path"
+                      >
+                        &#9998;
+                      </button>
+                      <select
+                        value={job.fileCategory || "episode"}
+                        onClick={(e) => e.stopPropagation()}
+                        onChange={(e) => handleJobCategoryChange(job, e.target.value)}
+                        className="flex-shrink-0 px-1 py-0.5 text-micro rounded bg-bg-tertiary border border-border text-text-secondary focus:outline-none focus:border-accent"
+                        title="File category"
+                      >
+                        {FILE_CATEGORIES.map((c) => (
+                          <option key={c.value} value={c.value}>
+                            {c.label}
+                          </option>
+                        ))}
+                      </select>
+                    </div>
+                    {pathEditJobId === job.id && (
+                      <div className="flex items-center gap-1 px-2 py-1">
+                        <input
+                          type="text"
+                          value={pathDraft}
+                          onChange={(e) => setPathDraft(e.target.value)}
+                          onKeyDown={(e) => {
+                            if (e.key === "Enter") handleSaveDestination(job, pathDraft);
+                            if (e.key === "Escape") setPathEditJobId(null);
+                          }}
+                          autoFocus
+                          placeholder="Relative path under the destination"
+                          className="flex-1 min-w-0 px-2 py-1 text-xs font-mono rounded bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
+                        />
+                        <button
+                          onClick={() => handleSaveDestination(job, pathDraft)}
+                          className="px-2 py-1 text-xs rounded bg-accent text-white hover:bg-accent-hover transition-colors"
+                        >
+                          Save
+                        </button>
+                        {job.destinationOverride && (
+                          <button
+                            onClick={() => handleSaveDestination(job, null)}
+                            className="px-2 py-1 text-xs rounded bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors"
+                            title="Go back to the naming template"
+                          >
+                            Clear
+                          </button>
+                        )}
+                      </div>
+                    )}
                     </div>
                     );
                   })}
@@ -263,12 +735,50 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
               </div>
             )}
 
+            {/* Destination layout */}
+            {destinationPaths.length > 0 && (
+              <div className="space-y-1">
+                <button
+                  onClick={() => setShowTree(!showTree)}
+                  className="text-xs font-semibold uppercase tracking-wider text-text-muted hover:text-text-primary"
+                >
+                  {showTree ? "\u25BE" : "\u25B8"} Destination Layout
+                </button>
+                {showTree && <DestinationTree paths={destinationPaths} />}
+              </div>
+            )}
+
             {/* TMDB Candidates */}
-            {candidates.length > 0 && (
+            {(candidates.length > 0 || activeGroup.parsedTitle) && (
               <div className="space-y-2">
-                <h3 className="text-xs font-semibold uppercase tracking-wider text-text-muted">
-                  Candidates
-                </h3>
+                <div className="flex items-center justify-between">
+                  <h3 className="text-xs font-semibold uppercase tracking-wider text-text-muted">
+                    Candidates
+                  </h3>
+                  {activeGroup.parsedTitle && (
+                    <div className="flex rounded-md border border-border overflow-hidden" title="Search as">
+                      {SEARCH_TYPES.map((t) => (
+                        <button
+                          key={t.value}
+                          onClick={() => handleSearchTypeChange(t.value)}
+                          disabled={researching}
+                          className={`px-2 py-0.5 text-micro transition-colors disabled:opacity-50 ${
+                            searchType === t.value
+                              ? "bg-accent text-white"
+                              : "bg-bg-tertiary text-text-secondary hover:bg-bg-hover"
+                          }`}
+                        >
+                          {t.label}
+                        </button>
+                      ))}
+                    </div>
+                  )}
+                </div>
+                {candidates.length === 0 && (
+                  <p className="text-xs text-text-muted">
+                    {researching ? "Searching..." : "No candidates found"}
+                  </p>
+                )}
                 {candidates.map((c) => (
                   <CandidateCard
                     key={c.id}
@@ -290,7 +800,7 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
                   value={manualQuery}
                   onChange={(e) => setManualQuery(e.target.value)}
                   onKeyDown={(e) => e.key === "Enter" && handleManualSearch()}
-                  placeholder="Search TMDB..."
+                  placeholder="Search TMDB, or paste a TMDB/IMDB link..."
                   className="flex-1 px-3 py-1.5 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
                 />
                 <button
@@ -301,7 +811,7 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
                   {searching ? "..." : "Search"}
                 </button>
               </div>
-              {searchResults.map((r, i) => (
+              {newSearchResults.map((r, i) => (
                 <CandidateCard
                   key={`search-${i}`}
                   candidate={r}
@@ -325,7 +835,8 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
               Skip
             </button>
             {activeGroup.status === "matched" ||
-            activeGroup.status === "ambiguous" ? (
+            activeGroup.status === "ambiguous" ||
+            activeGroup.status === "unmatched" ? (
               <button
                 onClick={() =>
                   candidates[0] && handleConfirmMatch(candidates[0])
@@ -343,14 +854,49 @@ export function MatchPanel({ onRefresh }: { onRefresh: () => void }) {
             <EpisodeResolveModal
               job={resolveJob}
               groupId={activeGroup.id}
+              episodeOrder={activeGroup.episodeOrder}
               onClose={() => setResolveJob(null)}
-              onSaved={onRefresh}
+              onSaved={reloadActiveGroup}
             />
           )}
       </div>
   );
 }
 
+/**
+ * Candidate poster that isn't requested until its card scrolls into view, so
+ * opening a group with many candidates doesn't fetch every poster at once.
+ */
+function LazyPoster({ posterPath, alt }: { posterPath: string; alt: string }) {
+  const size = useAppStore((s) => candidatePosterSize(s.settings.poster_size));
+  const ref = useRef<HTMLDivElement>(null);
+  const [visible, setVisible] = useState(false);
+
+  useEffect(() => {
+    const el = ref.current;
+    if (!el || visible) return;
+    const observer = new IntersectionObserver(
+      (entries) => {
+        if (entries.some((e) => e.isIntersecting)) {
+          setVisible(true);
+          observer.disconnect();
+        }
+      },
+      { rootMargin: "100px" }
+    );
+    observer.observe(el);
+    return () => observer.disconnect();
+  }, [visible]);
+
+  return (
+    <div ref={ref} className="w-12 h-18 rounded bg-bg-tertiary flex-shrink-0 overflow-hidden">
+      {visible && (
+        <img src={posterUrl(posterPath, size)} alt={alt} className="w-full h-full object-cover" />
+      )}
+    </div>
+  );
+}
+
 function CandidateCard({
   candidate,
   onConfirm,
@@ -358,14 +904,12 @@ function CandidateCard({
   candidate: MatchCandidate;
   onConfirm: () => void;
 }) {
+  const autoMatchThreshold = useAppStore((s) => s.settings.auto_match_threshold);
+
   return (
     <div className="flex gap-3 p-2 rounded-lg bg-bg-tertiary/50 hover:bg-bg-hover/50 transition-colors">
       {candidate.posterPath ? (
-        <img
-          src={`${TMDB_IMG_BASE}${candidate.posterPath}`}
-          alt={candidate.title}
-          className="w-12 h-18 rounded object-cover flex-shrink-0"
-        />
+        <LazyPoster posterPath={candidate.posterPath} alt={candidate.title} />
       ) : (
         <div className="w-12 h-18 rounded bg-bg-tertiary flex items-center justify-center text-text-muted text-xs flex-shrink-0">
           ?
@@ -379,13 +923,7 @@ function CandidateCard({
           {candidate.year && <span>{candidate.year}</span>}
           <span className="uppercase">{candidate.mediaType}</span>
           <span
-            className={`font-mono ${
-              candidate.confidence >= 0.85
-                ? "text-success"
-                : candidate.confidence >= 0.5
-                ? "text-warning"
-                : "text-error"
-            }`}
+            className={`font-mono ${confidenceColor(candidate.confidence, autoMatchThreshold)}`}
           >
             {(candidate.confidence * 100).toFixed(0)}%
           </span>
@@ -1,48 +1,115 @@
 "use client";
 
-import { useAppStore } from "@/lib/store";
+import { useEffect, useState } from "react";
+import { useAppStore, getTotalPages, DEFAULT_PAGE_SIZE } from "@/lib/store";
+import { updateSettings } from "@/lib/api";
+
+const PAGE_SIZE_OPTIONS = [25, 50, 100, 200];
 
 export function Pagination({ onRefresh }: { onRefresh: () => void }) {
-  const { page, setPage, totalGroups } = useAppStore();
-  const limit = 50;
-  const totalPages = Math.max(1, Math.ceil(totalGroups / limit));
+  const { page, setPage, totalGroups, settings, setSettings } = useAppStore();
+  const limit = parseInt(settings.page_size || "", 10) || DEFAULT_PAGE_SIZE;
+  const totalPages = getTotalPages(totalGroups, settings.page_size);
+  const [pageInput, setPageInput] = useState(String(page));
+
+  useEffect(() => {
+    setPageInput(String(page));
+  }, [page]);
 
-  if (totalPages <= 1) return null;
+  if (totalGroups === 0) return null;
 
-  const handlePrev = () => {
-    if (page > 1) {
-      setPage(page - 1);
+  const goToPage = (target: number) => {
+    const clamped = Math.min(Math.max(1, target), totalPages);
+    setPageInput(String(clamped));
+    if (clamped !== page) {
+      setPage(clamped);
       onRefresh();
     }
   };
 
-  const handleNext = () => {
-    if (page < totalPages) {
-      setPage(page + 1);
-      onRefresh();
+  const handlePageInputSubmit = () => {
+    const target = parseInt(pageInput, 10);
+    if (Number.isNaN(target)) {
+      setPageInput(String(page));
+      return;
     }
+    goToPage(target);
+  };
+
+  // Changing the size reloads via the page_size dependency in the dashboard
+  const handlePageSizeChange = async (size: string) => {
+    setSettings({ ...settings, page_size: size });
+    setPage(1);
+    await updateSettings({ page_size: size });
   };
 
+
   return (
     <div className="flex items-center justify-between px-6 py-2 border-t border-border bg-bg-secondary/50 text-xs text-text-muted">
       <span>
         Page {page} of {totalPages} ({totalGroups} group{totalGroups !== 1 ? "s" : ""})
       </span>
-      <div className="flex gap-2">
+      <div className="flex items-center gap-2">
+        <label className="flex items-center gap-1.5">
+          Per page
+          <select
+            value={String(limit)}
+            onChange={(e) => handlePageSizeChange(e.target.value)}
+            className="px-1.5 py-1 rounded bg-bg-tertiary border border-border text-text-secondary focus:outline-none focus:border-accent"
+          >
+            {!PAGE_SIZE_OPTIONS.includes(limit) && (
+              <option value={String(limit)}>{limit}</option>
+            )}
+            {PAGE_SIZE_OPTIONS.map((size) => (
+              <option key={size} value={String(size)}>
+                {size}
+              </option>
+            ))}
+          </select>
+        </label>
+        <button
+          onClick={() => goToPage(1)}
+          disabled={page <= 1}
+          className="px-2.5 py-1 rounded bg-bg-tertiary text-text-secondary hover:bg-bg-hover disabled:opacity-30 disabled:cursor-not-allowed transition-colors"
+          title="First page"
+        >
+          First
+        </button>
         <button
-          onClick={handlePrev}
+          onClick={() => goToPage(page - 1)}
           disabled={page <= 1}
           className="px-2.5 py-1 rounded bg-bg-tertiary text-text-secondary hover:bg-bg-hover disabled:opacity-30 disabled:cursor-not-allowed transition-colors"
+          title="Previous page (Page Up)"
         >
           Prev
         </button>
+        <input
+          type="number"
+          min={1}
+          max={totalPages}
+          value={pageInput}
+          onChange={(e) => setPageInput(e.target.value)}
+          onKeyDown={(e) => e.key === "Enter" && handlePageInputSubmit()}
+          onBlur={handlePageInputSubmit}
+          className="w-14 px-1.5 py-1 rounded bg-bg-tertiary border border-border text-text-secondary text-center focus:outline-none focus:border-accent"
+          title="Go to page"
+        />
         <button
-          onClick={handleNext}
+          onClick={() => goToPage(page + 1)}
           disabled={page >= totalPages}
           className="px-2.5 py-1 rounded bg-bg-tertiary text-text-secondary hover:bg-bg-hover disabled:opacity-30 disabled:cursor-not-allowed transition-colors"
+          title="Next page (Page Down)"
         >
           Next
         </button>
+        <button
+          onClick={() => goToPage(totalPages)}
+          disabled={page >= totalPages}
+          className="px-2.5 py-1 rounded bg-bg-tertiary text-text-secondary hover:bg-bg-hover disabled:opacity-30 disabled:cursor-not-allowed transition-colors"
+          title="Last page"
+        >
+          Last
+        </button>
       </div>
     </div>
   );
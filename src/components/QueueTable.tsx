@@ -1,8 +1,27 @@
 "use client";
 
+import { useEffect, useMemo, useRef, useState } from "react";
 import { useAppStore, type GroupWithJobs, type JobWithPreview } from "@/lib/store";
+import { confidenceColor, parseUiScale, scaled } from "@/lib/theme";
 import { fetchGroup } from "@/lib/api";
-import { StatusBadge, MediaTypeBadge, FileCategoryBadge } from "./StatusBadge";
+import { detectMixedContent } from "@/lib/mixed";
+import { StatusBadge, MediaTypeBadge, FileCategoryBadge, HdrBadge } from "./StatusBadge";
+
+// Fixed row heights (at UI scale 1) let us window the table without measuring each row
+const GROUP_ROW_HEIGHT = 56;
+const FILE_ROW_HEIGHT = 64;
+// Extra pixels rendered above/below the viewport so fast scrolling doesn't flash blank rows
+const OVERSCAN_PX = 600;
+// Small tables render every row; windowing only kicks in past this many rows
+const VIRTUALIZE_THRESHOLD = 200;
+
+type TableRow =
+  | { kind: "group"; group: GroupWithJobs; isExpanded: boolean }
+  | { kind: "file"; job: JobWithPreview };
+
+function rowHeight(row: TableRow, uiScale: number): number {
+  return scaled(row.kind === "group" ? GROUP_ROW_HEIGHT : FILE_ROW_HEIGHT, uiScale);
+}
 
 function formatSize(bytes: number): string {
   if (bytes < 1024) return `${bytes} B`;
@@ -22,10 +41,70 @@ export function QueueTable({ onRefresh }: { onRefresh: () => void }) {
     clearSelection,
     activeGroupId,
     setActiveGroup,
+    expandedGroupIds,
+    toggleGroupExpanded,
+    expandAll,
+    collapseAll,
     sortBy,
     sortDir,
     setSorting,
   } = useAppStore();
+  const lastScanAt = useAppStore((s) => s.settings.last_scan_at);
+  const uiScale = parseUiScale(useAppStore((s) => s.settings.ui_scale));
+
+  const containerRef = useRef<HTMLDivElement>(null);
+  const [scrollTop, setScrollTop] = useState(0);
+  const [viewportHeight, setViewportHeight] = useState(0);
+
+  useEffect(() => {
+    const el = containerRef.current;
+    if (!el) return;
+    setViewportHeight(el.clientHeight);
+    const observer = new ResizeObserver(() => setViewportHeight(el.clientHeight));
+    observer.observe(el);
+    return () => observer.disconnect();
+  }, []);
+
+  // Flatten groups and their expanded file rows into one list with running offsets
+  const { rows, offsets, totalHeight } = useMemo(() => {
+    const rows: TableRow[] = [];
+    for (const group of groups) {
+      const isExpanded = !!expandedGroupIds[group.id] || activeGroupId === group.id;
+      rows.push({ kind: "group", group, isExpanded });
+      if (isExpanded) {
+        for (const job of group.jobs) rows.push({ kind: "file", job });
+      }
+    }
+    const offsets: number[] = [];
+    let totalHeight = 0;
+    for (const row of rows) {
+      offsets.push(totalHeight);
+      totalHeight += rowHeight(row, uiScale);
+    }
+    return { rows, offsets, totalHeight };
+  }, [groups, expandedGroupIds, activeGroupId, uiScale]);
+
+  const virtualize = rows.length > VIRTUALIZE_THRESHOLD;
+  let startIndex = 0;
+  let endIndex = rows.length;
+  if (virtualize) {
+    const top = Math.max(0, scrollTop - OVERSCAN_PX);
+    const bottom = scrollTop + viewportHeight + OVERSCAN_PX;
+    // Binary search for the first row that ends below the window top
+    let lo = 0;
+    let hi = rows.length - 1;
+    while (lo < hi) {
+      const mid = (lo + hi) >> 1;
+      if (offsets[mid] + rowHeight(rows[mid], uiScale) <= top) lo = mid + 1;
+      else hi = mid;
+    }
+    startIndex = lo;
+    endIndex = startIndex;
+    while (endIndex < rows.length && offsets[endIndex] < bottom) endIndex++;
+  }
+  const topPadding = virtualize && rows.length > 0 ? offsets[startIndex] : 0;
+  const bottomPadding =
+    virtualize && endIndex < rows.length ? totalHeight - offsets[endIndex] : 0;
 
   const handleGroupClick = async (group: GroupWithJobs) => {
     if (activeGroupId === group.id) {
@@ -58,7 +137,11 @@ export function QueueTable({ onRefresh }: { onRefresh: () => void }) {
     groups.length > 0 && groups.every((g) => !!selectedGroupIds[g.id]);
 
   return (
-    <div className="flex-1 overflow-auto">
+    <div
+      ref={containerRef}
+      className="flex-1 overflow-auto"
+      onScroll={(e) => virtualize && setScrollTop(e.currentTarget.scrollTop)}
+    >
       <table className="w-full text-sm">
         <thead className="sticky top-0 bg-bg-secondary z-10">
           <tr className="border-b border-border text-left text-text-muted text-xs uppercase tracking-wider">
@@ -72,12 +155,30 @@ export function QueueTable({ onRefresh }: { onRefresh: () => void }) {
                 className="accent-accent"
               />
             </th>
+            <th className="px-1 py-3 w-10">
+              <div className="flex items-center gap-0.5 normal-case tracking-normal">
+                <button
+                  onClick={expandAll}
+                  className="px-1 rounded hover:text-text-primary hover:bg-bg-hover transition-colors"
+                  title="Expand all"
+                >
+                  &#x25BE;
+                </button>
+                <button
+                  onClick={collapseAll}
+                  className="px-1 rounded hover:text-text-primary hover:bg-bg-hover transition-colors"
+                  title="Collapse all"
+                >
+                  &#x25B8;
+                </button>
+              </div>
+            </th>
             <th className="px-4 py-3 w-16">Type</th>
             <th
               className="px-4 py-3 cursor-pointer hover:text-text-primary"
-              onClick={() => handleSort("folderName")}
+              onClick={() => handleSort("title")}
             >
-              Title <SortIndicator column="folderName" />
+              Title <SortIndicator column="title" />
             </th>
             <th
               className="px-4 py-3 w-24 cursor-pointer hover:text-text-primary"
@@ -85,6 +186,12 @@ export function QueueTable({ onRefresh }: { onRefresh: () => void }) {
             >
               Size <SortIndicator column="totalFileSize" />
             </th>
+            <th
+              className="px-4 py-3 w-20 cursor-pointer hover:text-text-primary"
+              onClick={() => handleSort("matchConfidence")}
+            >
+              Match <SortIndicator column="matchConfidence" />
+            </th>
             <th
               className="px-4 py-3 w-28 cursor-pointer hover:text-text-primary"
               onClick={() => handleSort("status")}
@@ -97,7 +204,7 @@ export function QueueTable({ onRefresh }: { onRefresh: () => void }) {
           {loading ? (
             <tr>
               <td
-                colSpan={5}
+                colSpan={7}
                 className="px-4 py-12 text-center text-text-muted"
               >
                 <span className="animate-spin inline-block w-5 h-5 border-2 border-text-muted/30 border-t-text-muted rounded-full mr-2" />
@@ -107,7 +214,7 @@ export function QueueTable({ onRefresh }: { onRefresh: () => void }) {
           ) : groups.length === 0 ? (
             <tr>
               <td
-                colSpan={5}
+                colSpan={7}
                 className="px-4 py-12 text-center text-text-muted"
               >
                 No groups found. Configure a scan path in Settings and click
@@ -115,17 +222,32 @@ export function QueueTable({ onRefresh }: { onRefresh: () => void }) {
               </td>
             </tr>
           ) : (
-            groups.map((group) => (
-                <GroupRow
-                  key={group.id}
-                  group={group}
-                  isExpanded={activeGroupId === group.id}
-                  isSelected={!!selectedGroupIds[group.id]}
-                  isActive={activeGroupId === group.id}
-                  onToggleSelect={() => toggleGroupSelection(group.id)}
-                  onClick={() => handleGroupClick(group)}
-                />
-            ))
+            <>
+              {topPadding > 0 && (
+                <tr aria-hidden style={{ height: topPadding }} />
+              )}
+              {rows.slice(startIndex, endIndex).map((row) =>
+                row.kind === "group" ? (
+                  <GroupRow
+                    key={`g-${row.group.id}`}
+                    group={row.group}
+                    height={rowHeight(row, uiScale)}
+                    isExpanded={row.isExpanded}
+                    isSelected={!!selectedGroupIds[row.group.id]}
+                    isActive={activeGroupId === row.group.id}
+                    isNew={!!lastScanAt && row.group.createdAt >= lastScanAt}
+                    onToggleSelect={() => toggleGroupSelection(row.group.id)}
+                    onToggleExpand={() => toggleGroupExpanded(row.group.id)}
+                    onClick={() => handleGroupClick(row.group)}
+                  />
+                ) : (
+                  <FileRow key={`j-${row.job.id}`} job={row.job} height={rowHeight(row, uiScale)} />
+                )
+              )}
+              {bottomPadding > 0 && (
+                <tr aria-hidden style={{ height: bottomPadding }} />
+              )}
+            </>
           )}
         </tbody>
       </table>
@@ -135,81 +257,147 @@ export function QueueTable({ onRefresh }: { onRefresh: () => void }) {
 
 function GroupRow({
   group,
+  height,
   isExpanded,
   isSelected,
   isActive,
+  isNew,
   onToggleSelect,
+  onToggleExpand,
   onClick,
 }: {
   group: GroupWithJobs;
+  height: number;
   isExpanded: boolean;
   isSelected: boolean;
   isActive: boolean;
+  isNew: boolean;
   onToggleSelect: () => void;
+  onToggleExpand: () => void;
   onClick: () => void;
 }) {
   const year = group.tmdbYear || group.parsedYear;
+  const autoMatchThreshold = useAppStore((s) => s.settings.auto_match_threshold);
+  const mixed = useMemo(() => detectMixedContent(group.jobs), [group.jobs]);
 
   return (
-    <>
-      {/* Group header row */}
-      <tr
-        className={`border-b border-border/50 cursor-pointer transition-colors ${
-          isActive
-            ? "bg-accent/10"
-            : isSelected
-            ? "bg-bg-hover/50"
-            : "hover:bg-bg-hover/30"
-        }`}
-        onClick={onClick}
-      >
-        <td className="px-4 py-2.5" onClick={(e) => e.stopPropagation()}>
-          <input
-            type="checkbox"
-            checked={isSelected}
-            onChange={onToggleSelect}
-            className="accent-accent"
-          />
-        </td>
-        <td className="px-4 py-2.5">
-          <MediaTypeBadge type={group.mediaType} />
-        </td>
-        <td className="px-4 py-2.5 max-w-0">
-          <div className="flex items-baseline gap-2">
-            <span className="font-medium text-text-primary truncate">
-              {group.tmdbTitle || group.parsedTitle || group.folderName}
+    <tr
+      style={{ height }}
+      className={`border-b border-border/50 cursor-pointer transition-colors ${
+        isActive
+          ? "bg-accent/10"
+          : isSelected
+          ? "bg-bg-hover/50"
+          : "hover:bg-bg-hover/30"
+      }`}
+      onClick={onClick}
+    >
+      <td className="px-4 py-2.5" onClick={(e) => e.stopPropagation()}>
+        <input
+          type="checkbox"
+          checked={isSelected}
+          onChange={onToggleSelect}
+          className="accent-accent"
+        />
+      </td>
+      <td className="px-1 py-2.5" onClick={(e) => e.stopPropagation()}>
+        {group.jobs.length > 0 && (
+          <button
+            onClick={onToggleExpand}
+            className="px-1 text-xs text-text-muted hover:text-text-primary transition-colors"
+            title={isExpanded ? "Collapse" : "Expand"}
+          >
+            {isExpanded ? "\u25BE" : "\u25B8"}
+          </button>
+        )}
+      </td>
+      <td className="px-4 py-2.5">
+        <MediaTypeBadge type={group.mediaType} />
+      </td>
+      <td className="px-4 py-2.5 max-w-0">
+        <div className="flex items-baseline gap-2">
+          <span className="font-medium text-text-primary truncate">
+            {group.tmdbTitle || group.parsedTitle || group.folderName}
+          </span>
+          {year && (
+            <span className="text-xs text-text-muted">({year})</span>
+          )}
+          <span className="text-xs text-text-muted">
+            {group.totalFileCount} {group.totalFileCount === 1 ? "file" : "files"}
+          </span>
+          {isNew && (
+            <span className="text-micro font-semibold uppercase tracking-wider text-success">
+              New
             </span>
-            {year && (
-              <span className="text-xs text-text-muted">({year})</span>
-            )}
-            <span className="text-xs text-text-muted">
-              {group.totalFileCount} {group.totalFileCount === 1 ? "file" : "files"}
+          )}
+          {group.missingAt && (
+            <span
+              className="text-micro font-semibold uppercase tracking-wider text-error"
+              title={`Not found on disk since ${new Date(group.missingAt).toLocaleString()}`}
+            >
+              Missing
             </span>
-          </div>
-          {group.tmdbTitle && group.folderName !== group.tmdbTitle && (
-            <div className="text-[11px] text-text-muted font-mono truncate">
-              {group.folderName}
-            </div>
           )}
-        </td>
-        <td className="px-4 py-2.5 text-text-secondary text-xs">
-          {formatSize(group.totalFileSize)}
-        </td>
-        <td className="px-4 py-2.5">
-          <StatusBadge status={group.status} />
-        </td>
-      </tr>
-
-      {/* Expanded file rows */}
-      {isExpanded &&
-        group.jobs.map((job) => (
-          <FileRow key={job.id} job={job} />
-        ))}
-    </>
+          {mixed && (
+            <span
+              className="text-micro font-semibold uppercase tracking-wider text-warning"
+              title={`Mixed content: ${mixed.reason}`}
+            >
+              Mixed
+            </span>
+          )}
+          {group.matchError && (
+            <span
+              className="text-micro font-semibold uppercase tracking-wider text-warning"
+              title={`Couldn't reach TMDB: ${group.matchError}`}
+            >
+              Match failed
+            </span>
+          )}
+          {group.locked && (
+            <span
+              className="text-micro font-semibold uppercase tracking-wider text-info"
+              title="Locked: rematches and rescans leave this match alone"
+            >
+              Locked
+            </span>
+          )}
+          {group.labels?.map((label) => (
+            <span
+              key={label.id}
+              className="px-1.5 rounded text-micro font-medium text-white flex-shrink-0"
+              style={{ backgroundColor: label.color }}
+            >
+              {label.name}
+            </span>
+          ))}
+        </div>
+        {group.tmdbTitle && group.folderName !== group.tmdbTitle && (
+          <div className="text-mini text-text-muted font-mono truncate">
+            {group.folderName}
+          </div>
+        )}
+      </td>
+      <td className="px-4 py-2.5 text-text-secondary text-xs">
+        {formatSize(group.totalFileSize)}
+      </td>
+      <td
+        className={`px-4 py-2.5 text-xs font-mono ${
+          group.matchConfidence != null
+            ? confidenceColor(group.matchConfidence, autoMatchThreshold)
+            : "text-text-secondary"
+        }`}
+      >
+        {group.matchConfidence != null ? `${Math.round(group.matchConfidence * 100)}%` : "—"}
+      </td>
+      <td className="px-4 py-2.5">
+        <StatusBadge status={group.status} />
+      </td>
+    </tr>
   );
 }
 
-function FileRow({ job }: { job: JobWithPreview }) {
+function FileRow({ job, height }: { job: JobWithPreview; height: number }) {
   const seLabel =
     job.parsedSeason != null && job.parsedEpisode != null
       ? `S${String(job.parsedSeason).padStart(2, "0")}E${String(
@@ -218,10 +406,17 @@ function FileRow({ job }: { job: JobWithPreview }) {
       : null;
 
   return (
-    <tr className="border-b border-border/20 bg-bg-primary/30">
+    <tr
+      style={{ height }}
+      className="border-b border-border/20 bg-bg-primary/30"
+    >
       <td className="px-4 py-1.5" />
+      <td className="px-1 py-1.5" />
       <td className="px-4 py-1.5">
-        <FileCategoryBadge category={job.fileCategory || "episode"} />
+        <div className="flex items-center gap-1">
+          <FileCategoryBadge category={job.fileCategory || "episode"} />
+          {job.parsedHdr && <HdrBadge hdr={job.parsedHdr} />}
+        </div>
       </td>
       <td className="px-4 py-1.5 max-w-0">
         <div className="flex items-center gap-2">
@@ -235,13 +430,13 @@ function FileRow({ job }: { job: JobWithPreview }) {
               {job.fileName}
             </span>
             {job.tmdbEpisodeTitle && (
-              <span className="text-[11px] text-text-muted block">
+              <span className="text-mini text-text-muted truncate block">
                 {job.tmdbEpisodeTitle}
               </span>
             )}
             {job.previewName && (
               <span
-                className="font-mono text-[11px] text-accent/70 truncate block"
+                className="font-mono text-mini text-accent/70 truncate block"
                 title={job.previewName}
               >
                 &rarr; {job.previewName}
@@ -253,6 +448,7 @@ function FileRow({ job }: { job: JobWithPreview }) {
       <td className="px-4 py-1.5 text-text-muted text-xs">
         {formatSize(job.fileSize)}
       </td>
+      <td className="px-4 py-1.5" />
       <td className="px-4 py-1.5">
         <StatusBadge status={job.status} />
       </td>
@@ -1,20 +1,195 @@
 "use client";
 
 import { useAppStore } from "@/lib/store";
-import { updateSettings } from "@/lib/api";
-import { useState, useEffect } from "react";
+import {
+  updateSettings,
+  fetchPosterCacheStats,
+  clearPosterCache,
+  validateTmdbKey,
+  checkScanPath,
+  runDatabaseMaintenance,
+  reparseLibrary,
+  databaseBackupUrl,
+  restoreDatabaseBackup,
+} from "@/lib/api";
+import { useToastStore } from "./Toast";
+import { ConfirmModal } from "./ConfirmModal";
+import { requestNotificationPermission } from "@/lib/notify";
+import { candidatePosterSize, CANDIDATE_POSTER_SIZES, parseUiScale, UI_SCALES } from "@/lib/theme";
+import { validateSettings, type SettingsErrors } from "@/lib/settings";
+import { useState, useEffect, useRef } from "react";
 import { motion, AnimatePresence } from "framer-motion";
 
+const TMDB_LANGUAGES = [
+  { value: "en-US", label: "English (US)" },
+  { value: "en-GB", label: "English (UK)" },
+  { value: "de-DE", label: "Deutsch" },
+  { value: "fr-FR", label: "Français" },
+  { value: "es-ES", label: "Español (España)" },
+  { value: "es-MX", label: "Español (México)" },
+  { value: "it-IT", label: "Italiano" },
+  { value: "nl-NL", label: "Nederlands" },
+  { value: "pt-BR", label: "Português (Brasil)" },
+  { value: "pt-PT", label: "Português (Portugal)" },
+  { value: "sv-SE", label: "Svenska" },
+  { value: "da-DK", label: "Dansk" },
+  { value: "nb-NO", label: "Norsk" },
+  { value: "fi-FI", label: "Suomi" },
+  { value: "pl-PL", label: "Polski" },
+  { value: "cs-CZ", label: "Čeština" },
+  { value: "ru-RU", label: "Русский" },
+  { value: "ja-JP", label: "日本語" },
+  { value: "ko-KR", label: "한국어" },
+  { value: "zh-CN", label: "中文 (简体)" },
+  { value: "zh-TW", label: "中文 (繁體)" },
+];
+
+function FieldError({ error }: { error?: string | null }) {
+  if (!error) return null;
+  return <p className="mt-1 text-xs text-error">{error}</p>;
+}
+
 export function SettingsModal() {
   const { settingsOpen, setSettingsOpen, settings, setSettings } = useAppStore();
   const [form, setForm] = useState<Record<string, string>>({});
+  // Scan roots are stored newline-separated in the scan_path setting
+  const [scanPaths, setScanPaths] = useState<string[]>([""]);
+  const [posterCache, setPosterCache] = useState<{ files: number; bytes: number } | null>(null);
+  const [keyCheck, setKeyCheck] = useState<
+    { state: "checking" } | { state: "ok" } | { state: "error"; error: string } | null
+  >(null);
 
   useEffect(() => {
     setForm({ ...settings });
+    const paths = (settings.scan_path || "").split("\n").filter((p) => p.trim());
+    setScanPaths(paths.length > 0 ? paths : [""]);
   }, [settings, settingsOpen]);
 
+  useEffect(() => {
+    if (settingsOpen) fetchPosterCacheStats().then(setPosterCache);
+  }, [settingsOpen]);
+
+  // Scan paths are checked on the server as they're typed; other fields locally
+  const [pathErrors, setPathErrors] = useState<Record<string, string>>({});
+  const [serverErrors, setServerErrors] = useState<SettingsErrors>({});
+
+  useEffect(() => {
+    if (!settingsOpen) return;
+    const paths = [...new Set(scanPaths.map((p) => p.trim()).filter(Boolean))];
+    const timer = setTimeout(async () => {
+      const results = await Promise.all(
+        paths.map((p) => checkScanPath(p).catch(() => ({ ok: true, error: undefined })))
+      );
+      const next: Record<string, string> = {};
+      paths.forEach((p, i) => {
+        if (!results[i].ok) next[p] = results[i].error || "Folder not found";
+      });
+      setPathErrors(next);
+    }, 400);
+    return () => clearTimeout(timer);
+  }, [scanPaths, settingsOpen]);
+
+  useEffect(() => {
+    setServerErrors({});
+  }, [form, scanPaths]);
+
+  const errors: SettingsErrors = { ...validateSettings(form), ...serverErrors };
+  const hasErrors =
+    Object.keys(errors).length > 0 || scanPaths.some((p) => pathErrors[p.trim()]);
+
+  const handleValidateKey = async () => {
+    setKeyCheck({ state: "checking" });
+    const result = await validateTmdbKey(form.tmdb_api_key || "");
+    setKeyCheck(
+      result.ok ? { state: "ok" } : { state: "error", error: result.error || "Validation failed" }
+    );
+  };
+
+  const handleClearPosterCache = async () => {
+    await clearPosterCache();
+    setPosterCache({ files: 0, bytes: 0 });
+    useToastStore.getState().addToast("Poster cache cleared", "success");
+  };
+
+  const [maintenance, setMaintenance] = useState<"vacuum" | "integrity" | "restore" | "reparse" | null>(null);
+  const restoreInputRef = useRef<HTMLInputElement>(null);
+  const [pendingRestore, setPendingRestore] = useState<File | null>(null);
+
+  const handleRestore = async (file: File) => {
+    setPendingRestore(null);
+    setMaintenance("restore");
+    const result = await restoreDatabaseBackup(file).catch(() => ({ error: "Restore request failed" }));
+    setMaintenance(null);
+    if (result.error) {
+      useToastStore.getState().addToast(`Restore failed: ${result.error}`, "error");
+      return;
+    }
+    // Everything in the store came from the old database
+    window.location.reload();
+  };
+
+  const handleMaintenance = async (action: "vacuum" | "integrity") => {
+    setMaintenance(action);
+    const result = await runDatabaseMaintenance(action).catch(() => ({
+      error: "Maintenance request failed",
+    } as Awaited<ReturnType<typeof runDatabaseMaintenance>>));
+    setMaintenance(null);
+
+    const { addToast } = useToastStore.getState();
+    if (result.error) {
+      addToast(result.error, "error");
+    } else if (action === "vacuum") {
+      const mb = (bytes = 0) => (bytes / (1024 * 1024)).toFixed(1);
+      addToast(`Database compacted: ${mb(result.bytesBefore)} MB → ${mb(result.bytesAfter)} MB`, "success");
+    } else if (result.ok) {
+      addToast("Database integrity check passed", "success");
+    } else {
+      const problems = result.problems || [];
+      addToast(
+        `Integrity check found ${problems.length} problem${problems.length !== 1 ? "s" : ""}: ${problems.slice(0, 3).join("; ")}`,
+        "error"
+      );
+    }
+  };
+
+  const handleReparse = async () => {
+    setMaintenance("reparse");
+    const result = await reparseLibrary().catch(() => ({ error: "Re-parse request failed" }));
+    setMaintenance(null);
+
+    const { addToast } = useToastStore.getState();
+    if (result.error) {
+      addToast(result.error, "error");
+      return;
+    }
+    let msg = `Re-parsed names: ${result.files ?? 0} file${result.files !== 1 ? "s" : ""} in ${result.groups ?? 0} group${result.groups !== 1 ? "s" : ""} changed.`;
+    if (result.reset) msg += ` ${result.reset} with a new title need matching again.`;
+    if (result.renumbered && result.titlesRefreshed !== result.renumbered) {
+      msg += ` Episode titles for ${(result.renumbered ?? 0) - (result.titlesRefreshed ?? 0)} renumbered group(s) couldn't be fetched.`;
+    }
+    if (result.lockedSkipped) msg += ` ${result.lockedSkipped} locked left alone.`;
+    addToast(msg, result.reset ? "info" : "success");
+  };
+
+  const handleNotificationsToggle = async (enabled: boolean) => {
+    if (enabled && !(await requestNotificationPermission())) {
+      setForm({ ...form, desktop_notifications: "false" });
+      return;
+    }
+    setForm({ ...form, desktop_notifications: enabled ? "true" : "false" });
+  };
+
   const handleSave = async () => {
-    const updated = await updateSettings(form);
+    const scan_path = scanPaths
+      .map((p) => p.trim())
+      .filter(Boolean)
+      .join("\n");
+    const updated = await updateSettings({ ...form, scan_path });
+    if (updated.fieldErrors) {
+      setServerErrors(updated.fieldErrors);
+      useToastStore.getState().addToast(updated.error || "Some settings are invalid", "error");
+      return;
+    }
     setSettings(updated);
     setSettingsOpen(false);
   };
@@ -38,7 +213,7 @@ export function SettingsModal() {
             exit={{ opacity: 0, scale: 0.95 }}
             className="fixed inset-0 z-50 flex items-center justify-center p-4"
           >
-            <div className="bg-bg-secondary border border-border rounded-xl w-full max-w-md p-6 space-y-4">
+            <div className="bg-bg-secondary border border-border rounded-xl w-full max-w-md max-h-[90vh] overflow-y-auto p-6 space-y-4">
               <div className="flex items-center justify-between">
                 <h2 className="text-lg font-semibold text-text-primary">Settings</h2>
                 <button
@@ -52,31 +227,232 @@ export function SettingsModal() {
               <div className="space-y-3">
                 <div>
                   <label className="block text-xs font-medium text-text-muted mb-1">
-                    Scan Path
+                    Scan Paths
                   </label>
+                  <div className="space-y-2">
+                    {scanPaths.map((scanPath, i) => (
+                      <div key={i} className="flex items-center gap-2">
+                        <input
+                          type="text"
+                          value={scanPath}
+                          onChange={(e) =>
+                            setScanPaths(scanPaths.map((p, j) => (j === i ? e.target.value : p)))
+                          }
+                          placeholder="/path/to/media/folder"
+                          className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                        />
+                        {scanPaths.length > 1 && (
+                          <button
+                            onClick={() => setScanPaths(scanPaths.filter((_, j) => j !== i))}
+                            className="text-text-muted hover:text-error text-lg leading-none"
+                            title="Remove path"
+                          >
+                            &times;
+                          </button>
+                        )}
+                      </div>
+                    ))}
+                  </div>
+                  {scanPaths.map((scanPath, i) =>
+                    pathErrors[scanPath.trim()] ? (
+                      <FieldError key={i} error={`${scanPath.trim()}: ${pathErrors[scanPath.trim()]}`} />
+                    ) : null
+                  )}
+                  <FieldError error={errors.scan_path} />
+                  <button
+                    onClick={() => setScanPaths([...scanPaths, ""])}
+                    className="mt-1 text-xs text-accent hover:text-accent-hover"
+                  >
+                    + Add path
+                  </button>
+                </div>
+
+                <label className="flex items-center gap-2 text-sm text-text-primary">
                   <input
-                    type="text"
-                    value={form.scan_path || ""}
-                    onChange={(e) => setForm({ ...form, scan_path: e.target.value })}
-                    placeholder="/path/to/media/folder"
-                    className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                    type="checkbox"
+                    checked={form.group_by_parsed_title === "true"}
+                    onChange={(e) =>
+                      setForm({ ...form, group_by_parsed_title: e.target.checked ? "true" : "false" })
+                    }
+                    className="accent-accent"
                   />
+                  Group by title parsed from file names
+                  <span className="text-xs text-text-muted">
+                    (for flat dumps; ignores folder names)
+                  </span>
+                </label>
+
+                <div className="grid grid-cols-2 gap-3 items-end">
+                  <div>
+                    <label className="block text-xs font-medium text-text-muted mb-1">
+                      Max Scan Depth
+                    </label>
+                    <input
+                      type="number"
+                      min="1"
+                      step="1"
+                      value={form.scan_max_depth || ""}
+                      onChange={(e) => setForm({ ...form, scan_max_depth: e.target.value })}
+                      placeholder="8"
+                      className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                    />
+                    <FieldError error={errors.scan_max_depth} />
+                  </div>
+                  <label className="flex items-center gap-2 text-sm text-text-primary py-2">
+                    <input
+                      type="checkbox"
+                      checked={form.scan_follow_symlinks === "true"}
+                      onChange={(e) =>
+                        setForm({ ...form, scan_follow_symlinks: e.target.checked ? "true" : "false" })
+                      }
+                      className="accent-accent"
+                    />
+                    Follow symlinks
+                  </label>
+                </div>
+
+                <div className="grid grid-cols-2 gap-3">
+                  <div>
+                    <label className="block text-xs font-medium text-text-muted mb-1">
+                      Min Episode Files for TV
+                    </label>
+                    <input
+                      type="number"
+                      min="1"
+                      step="1"
+                      value={form.scan_min_episode_files || ""}
+                      onChange={(e) => setForm({ ...form, scan_min_episode_files: e.target.value })}
+                      placeholder="1"
+                      className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                    />
+                    <FieldError error={errors.scan_min_episode_files} />
+                  </div>
+                  <div>
+                    <label className="block text-xs font-medium text-text-muted mb-1">
+                      Min Unnumbered Files for TV
+                    </label>
+                    <input
+                      type="number"
+                      min="1"
+                      step="1"
+                      value={form.scan_min_unnumbered_episodes || ""}
+                      onChange={(e) => setForm({ ...form, scan_min_unnumbered_episodes: e.target.value })}
+                      placeholder="3"
+                      className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                    />
+                    <FieldError error={errors.scan_min_unnumbered_episodes} />
+                  </div>
                 </div>
 
                 <div>
                   <label className="block text-xs font-medium text-text-muted mb-1">
                     TMDB API Key
                   </label>
+                  <div className="flex items-center gap-2">
+                    <input
+                      type="password"
+                      value={form.tmdb_api_key || ""}
+                      onChange={(e) => {
+                        setForm({ ...form, tmdb_api_key: e.target.value });
+                        setKeyCheck(null);
+                      }}
+                      placeholder="v3 API key or v4 Read Access Token"
+                      className="flex-1 px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                    />
+                    <button
+                      onClick={handleValidateKey}
+                      disabled={keyCheck?.state === "checking"}
+                      className="px-3 py-2 text-sm rounded-md bg-bg-tertiary text-text-secondary hover:bg-bg-hover disabled:opacity-50 transition-colors"
+                    >
+                      {keyCheck?.state === "checking" ? "..." : "Test"}
+                    </button>
+                    {keyCheck?.state === "ok" && (
+                      <span className="text-success text-lg leading-none" title="Key is valid">
+                        &#x2713;
+                      </span>
+                    )}
+                    {keyCheck?.state === "error" && (
+                      <span className="text-error text-lg leading-none" title={keyCheck.error}>
+                        &#x2717;
+                      </span>
+                    )}
+                  </div>
+                  {keyCheck?.state === "error" && (
+                    <p className="mt-1 text-xs text-error">{keyCheck.error}</p>
+                  )}
+                  <p className="mt-1 text-xs text-text-muted">
+                    Get one at themoviedb.org/settings/api. Either the API key or the Read Access Token works.
+                  </p>
+                </div>
+
+                <div className="grid grid-cols-2 gap-3">
+                  <div>
+                    <label className="block text-xs font-medium text-text-muted mb-1">
+                      TMDB Language
+                    </label>
+                    <select
+                      value={form.tmdb_language || "en-US"}
+                      onChange={(e) => setForm({ ...form, tmdb_language: e.target.value })}
+                      className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
+                    >
+                      {!TMDB_LANGUAGES.some((l) => l.value === (form.tmdb_language || "en-US")) && (
+                        <option value={form.tmdb_language}>{form.tmdb_language}</option>
+                      )}
+                      {TMDB_LANGUAGES.map((l) => (
+                        <option key={l.value} value={l.value}>
+                          {l.label}
+                        </option>
+                      ))}
+                    </select>
+                  </div>
+                  <div>
+                    <label className="block text-xs font-medium text-text-muted mb-1">
+                      TMDB Region
+                    </label>
+                    <input
+                      type="text"
+                      value={form.tmdb_region || ""}
+                      onChange={(e) =>
+                        setForm({ ...form, tmdb_region: e.target.value.toUpperCase() })
+                      }
+                      placeholder="e.g. DE"
+                      maxLength={2}
+                      className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                    />
+                  </div>
+                  <p className="col-span-2 -mt-2 text-xs text-text-muted">
+                    Titles and episode names fall back to English when no translation exists.
+                  </p>
+                </div>
+
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    Episode Metadata Source
+                  </label>
+                  <select
+                    value={form.metadata_provider || "tmdb"}
+                    onChange={(e) => setForm({ ...form, metadata_provider: e.target.value })}
+                    className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
+                  >
+                    <option value="tmdb">TMDB</option>
+                    <option value="tvdb">TheTVDB</option>
+                  </select>
+                  <p className="mt-1 text-xs text-text-muted">
+                    Default source for episode lists when resolving episodes. Can be switched per lookup.
+                  </p>
+                </div>
+
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    TVDB API Key
+                  </label>
                   <input
                     type="password"
-                    value={form.tmdb_api_key || ""}
-                    onChange={(e) => setForm({ ...form, tmdb_api_key: e.target.value })}
-                    placeholder="Enter your TMDB API key"
+                    value={form.tvdb_api_key || ""}
+                    onChange={(e) => setForm({ ...form, tvdb_api_key: e.target.value })}
+                    placeholder="Optional — needed for TheTVDB episode lists"
                     className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
                   />
-                  <p className="mt-1 text-xs text-text-muted">
-                    Get one at themoviedb.org/settings/api
-                  </p>
                 </div>
 
                 <div>
@@ -88,12 +464,162 @@ export function SettingsModal() {
                     min="0"
                     max="1"
                     step="0.05"
-                    value={form.auto_match_threshold || "0.85"}
+                    value={form.auto_match_threshold ?? "0.85"}
                     onChange={(e) =>
                       setForm({ ...form, auto_match_threshold: e.target.value })
                     }
                     className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
                   />
+                  <FieldError error={errors.auto_match_threshold} />
+                  <p className="mt-1 text-xs text-text-muted">
+                    Picks the top candidate and marks the group Matched.
+                  </p>
+                </div>
+
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    Auto-Confirm Above
+                  </label>
+                  <input
+                    type="number"
+                    min="0"
+                    max="1"
+                    step="0.05"
+                    value={form.auto_confirm_above || ""}
+                    onChange={(e) =>
+                      setForm({ ...form, auto_confirm_above: e.target.value })
+                    }
+                    placeholder="Off"
+                    className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                  />
+                  <FieldError error={errors.auto_confirm_above} />
+                  <p className="mt-1 text-xs text-text-muted">
+                    Matches at or above this skip review and go straight to Confirmed. TV shows need every episode numbered.
+                  </p>
+                </div>
+
+                <label className="flex items-center gap-2 text-sm text-text-primary">
+                  <input
+                    type="checkbox"
+                    checked={form.probe_media === "true"}
+                    onChange={(e) =>
+                      setForm({ ...form, probe_media: e.target.checked ? "true" : "false" })
+                    }
+                    className="accent-accent"
+                  />
+                  Probe media with ffprobe
+                  <span className="text-xs text-text-muted">
+                    (real resolution, duration, codecs)
+                  </span>
+                </label>
+
+                <label className="flex items-center gap-2 text-sm text-text-primary">
+                  <input
+                    type="checkbox"
+                    checked={form.resume_transfers !== "false"}
+                    onChange={(e) =>
+                      setForm({ ...form, resume_transfers: e.target.checked ? "true" : "false" })
+                    }
+                    className="accent-accent"
+                  />
+                  Resume interrupted transfers
+                  <span className="text-xs text-text-muted">
+                    (local and SFTP)
+                  </span>
+                </label>
+
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    After Transfer: Run Command
+                  </label>
+                  <input
+                    type="text"
+                    value={form.post_transfer_command || ""}
+                    onChange={(e) => setForm({ ...form, post_transfer_command: e.target.value })}
+                    placeholder="curl -X POST http://jellyfin:8096/Library/Refresh?api_key=..."
+                    className="w-full px-3 py-2 text-sm font-mono rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                  />
+                  <p className="text-xs text-text-muted mt-1">
+                    Runs in a shell when a batch finishes. {"{count}"} and {"{destination}"} are
+                    replaced with the files transferred and the destination name, already quoted
+                    (also in $REELNAME_COUNT and $REELNAME_DESTINATION).
+                  </p>
+                </div>
+
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    After Transfer: Webhook URL
+                  </label>
+                  <input
+                    type="text"
+                    value={form.post_transfer_webhook || ""}
+                    onChange={(e) => setForm({ ...form, post_transfer_webhook: e.target.value })}
+                    placeholder="https://example.com/hooks/reelname"
+                    className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                  />
+                  <p className="text-xs text-text-muted mt-1">
+                    Receives a JSON POST summarizing each finished batch.
+                  </p>
+                  <FieldError error={errors.post_transfer_webhook} />
+                </div>
+
+                <label className="flex items-center gap-2 text-sm text-text-primary">
+                  <input
+                    type="checkbox"
+                    checked={form.desktop_notifications === "true"}
+                    onChange={(e) => handleNotificationsToggle(e.target.checked)}
+                    className="accent-accent"
+                  />
+                  Desktop notifications
+                  <span className="text-xs text-text-muted">
+                    (scan and transfer completion)
+                  </span>
+                </label>
+
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    Max Visible Notifications
+                  </label>
+                  <input
+                    type="number"
+                    min="1"
+                    max="20"
+                    step="1"
+                    value={form.toast_max_visible || "5"}
+                    onChange={(e) =>
+                      setForm({ ...form, toast_max_visible: e.target.value })
+                    }
+                    className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
+                  />
+                  <p className="mt-1 text-xs text-text-muted">
+                    Older notifications collapse into a &quot;+N more&quot; chip.
+                  </p>
+                  <FieldError error={errors.toast_max_visible} />
+                </div>
+
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    UI Scale
+                  </label>
+                  <select
+                    value={String(parseUiScale(form.ui_scale))}
+                    onChange={(e) => setForm({ ...form, ui_scale: e.target.value })}
+                    className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
+                  >
+                    {!UI_SCALES.includes(parseUiScale(form.ui_scale)) && (
+                      <option value={String(parseUiScale(form.ui_scale))}>
+                        {Math.round(parseUiScale(form.ui_scale) * 100)}%
+                      </option>
+                    )}
+                    {UI_SCALES.map((scale) => (
+                      <option key={scale} value={String(scale)}>
+                        {Math.round(scale * 100)}%
+                      </option>
+                    ))}
+                  </select>
+                  <p className="mt-1 text-xs text-text-muted">
+                    Enlarges text and spacing throughout the app for high-DPI displays.
+                  </p>
                 </div>
 
                 <hr className="border-border" />
@@ -122,6 +648,57 @@ export function SettingsModal() {
                   </p>
                 </div>
 
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    Poster Cache Size (MB)
+                  </label>
+                  <div className="flex gap-2">
+                    <input
+                      type="number"
+                      min="0"
+                      step="50"
+                      value={form.poster_cache_max_mb || "200"}
+                      onChange={(e) =>
+                        setForm({ ...form, poster_cache_max_mb: e.target.value })
+                      }
+                      className="flex-1 px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
+                    />
+                    <button
+                      onClick={handleClearPosterCache}
+                      className="px-3 py-2 text-sm rounded-md bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors"
+                    >
+                      Clear
+                    </button>
+                  </div>
+                  <FieldError error={errors.poster_cache_max_mb} />
+                  {posterCache && (
+                    <p className="mt-1 text-xs text-text-muted">
+                      {posterCache.files} poster{posterCache.files !== 1 ? "s" : ""} cached (
+                      {(posterCache.bytes / (1024 * 1024)).toFixed(1)} MB). Oldest are evicted past the limit.
+                    </p>
+                  )}
+                </div>
+
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    Candidate Poster Size
+                  </label>
+                  <select
+                    value={candidatePosterSize(form.poster_size)}
+                    onChange={(e) => setForm({ ...form, poster_size: e.target.value })}
+                    className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent"
+                  >
+                    {CANDIDATE_POSTER_SIZES.map((size) => (
+                      <option key={size} value={size}>
+                        {size.slice(1)}px wide
+                      </option>
+                    ))}
+                  </select>
+                  <p className="mt-1 text-xs text-text-muted">
+                    Smaller posters load faster on a slow connection.
+                  </p>
+                </div>
+
                 <hr className="border-border" />
 
                 <div>
@@ -151,13 +728,14 @@ export function SettingsModal() {
                   </label>
                   <input
                     type="text"
-                    value={form.specials_folder_name || "Specials"}
+                    value={form.specials_folder_name ?? "Specials"}
                     onChange={(e) =>
                       setForm({ ...form, specials_folder_name: e.target.value })
                     }
                     placeholder="Specials"
                     className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
                   />
+                  <FieldError error={errors.specials_folder_name} />
                 </div>
 
                 <div>
@@ -166,13 +744,158 @@ export function SettingsModal() {
                   </label>
                   <input
                     type="text"
-                    value={form.extras_folder_name || "Extras"}
+                    value={form.extras_folder_name ?? "Extras"}
                     onChange={(e) =>
                       setForm({ ...form, extras_folder_name: e.target.value })
                     }
                     placeholder="Extras"
                     className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
                   />
+                  <FieldError error={errors.extras_folder_name} />
+                </div>
+
+                <div className="grid grid-cols-2 gap-3">
+                  <div>
+                    <label className="block text-xs font-medium text-text-muted mb-1">
+                      Season Digits
+                    </label>
+                    <input
+                      type="number"
+                      min="1"
+                      max="4"
+                      step="1"
+                      value={form.season_pad_width || ""}
+                      onChange={(e) =>
+                        setForm({ ...form, season_pad_width: e.target.value })
+                      }
+                      placeholder="2"
+                      className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                    />
+                  </div>
+                  <div>
+                    <label className="block text-xs font-medium text-text-muted mb-1">
+                      Episode Digits
+                    </label>
+                    <input
+                      type="number"
+                      min="1"
+                      max="4"
+                      step="1"
+                      value={form.episode_pad_width || ""}
+                      onChange={(e) =>
+                        setForm({ ...form, episode_pad_width: e.target.value })
+                      }
+                      placeholder="2"
+                      className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent"
+                    />
+                  </div>
+                  <div className="col-span-2 -mt-3">
+                    <FieldError error={errors.season_pad_width || errors.episode_pad_width} />
+                  </div>
+                  <p className="col-span-2 -mt-2 text-xs text-text-muted">
+                    Zero-padding for S01E07; longer numbers are never cut (E125 stays E125).
+                  </p>
+                </div>
+
+                <label className="flex items-center gap-2 text-sm text-text-primary">
+                  <input
+                    type="checkbox"
+                    checked={form.use_collections === "true"}
+                    onChange={(e) =>
+                      setForm({ ...form, use_collections: e.target.checked ? "true" : "false" })
+                    }
+                    className="accent-accent"
+                  />
+                  Group movies into collection folders
+                  <span className="text-xs text-text-muted">
+                    (e.g. John Wick Collection/)
+                  </span>
+                </label>
+
+                <label className="flex items-center gap-2 text-sm text-text-primary">
+                  <input
+                    type="checkbox"
+                    checked={form.require_year_for_movies === "true"}
+                    onChange={(e) =>
+                      setForm({ ...form, require_year_for_movies: e.target.checked ? "true" : "false" })
+                    }
+                    className="accent-accent"
+                  />
+                  Require a year for movies
+                  <span className="text-xs text-text-muted">
+                    (yearless matches go to review)
+                  </span>
+                </label>
+
+                <hr className="border-border" />
+
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    Library
+                  </label>
+                  <button
+                    onClick={handleReparse}
+                    disabled={maintenance !== null}
+                    className="px-3 py-2 text-sm rounded-md bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors disabled:opacity-50"
+                  >
+                    {maintenance === "reparse" ? "Re-parsing..." : "Re-parse Names"}
+                  </button>
+                  <p className="mt-1 text-xs text-text-muted">
+                    Re-reads titles, years, seasons and episodes from the stored folder and file
+                    names without rescanning the disk. Matches are kept unless a group&apos;s title
+                    changed; locked groups are left alone.
+                  </p>
+                </div>
+
+                <div>
+                  <label className="block text-xs font-medium text-text-muted mb-1">
+                    Database
+                  </label>
+                  <div className="flex gap-2">
+                    <button
+                      onClick={() => handleMaintenance("vacuum")}
+                      disabled={maintenance !== null}
+                      className="px-3 py-2 text-sm rounded-md bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors disabled:opacity-50"
+                    >
+                      {maintenance === "vacuum" ? "Compacting..." : "Compact"}
+                    </button>
+                    <button
+                      onClick={() => handleMaintenance("integrity")}
+                      disabled={maintenance !== null}
+                      className="px-3 py-2 text-sm rounded-md bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors disabled:opacity-50"
+                    >
+                      {maintenance === "integrity" ? "Checking..." : "Check Integrity"}
+                    </button>
+                    <a
+                      href={databaseBackupUrl()}
+                      download
+                      className="px-3 py-2 text-sm rounded-md bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors"
+                    >
+                      Back Up
+                    </a>
+                    <button
+                      onClick={() => restoreInputRef.current?.click()}
+                      disabled={maintenance !== null}
+                      className="px-3 py-2 text-sm rounded-md bg-bg-tertiary text-text-secondary hover:bg-bg-hover transition-colors disabled:opacity-50"
+                    >
+                      {maintenance === "restore" ? "Restoring..." : "Restore..."}
+                    </button>
+                    <input
+                      ref={restoreInputRef}
+                      type="file"
+                      accept=".db,.sqlite,.sqlite3"
+                      className="hidden"
+                      onChange={(e) => {
+                        setPendingRestore(e.target.files?.[0] ?? null);
+                        e.target.value = "";
+                      }}
+                    />
+                  </div>
+                  <p className="mt-1 text-xs text-text-muted">
+                    Compacting reclaims space after large deletes. Back up before big reorganizations;
+                    restoring replaces all groups, settings, and destinations. Compact and restore are
+                    unavailable while transfers or matching run.
+                  </p>
                 </div>
               </div>
 
@@ -185,13 +908,28 @@ export function SettingsModal() {
                 </button>
                 <button
                   onClick={handleSave}
-                  className="px-4 py-2 text-sm rounded-md bg-accent text-white hover:bg-accent-hover transition-colors"
+                  disabled={hasErrors}
+                  title={hasErrors ? "Fix the highlighted settings first" : undefined}
+                  className="px-4 py-2 text-sm rounded-md bg-accent text-white hover:bg-accent-hover transition-colors disabled:opacity-50 disabled:hover:bg-accent"
                 >
                   Save
                 </button>
               </div>
             </div>
           </motion.div>
+          {pendingRestore && (
+            <ConfirmModal
+              title="Restore database?"
+              confirmLabel="Restore"
+              destructive
+              onConfirm={() => handleRestore(pendingRestore)}
+              onClose={() => setPendingRestore(null)}
+            >
+              All groups, settings, and destinations will be replaced with the contents of{" "}
+              <span className="text-text-primary">{pendingRestore.name}</span>. The current database
+              is kept as reelname.db.before-restore.
+            </ConfirmModal>
+          )}
         </>
       )}
     </AnimatePresence>
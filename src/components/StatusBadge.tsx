@@ -4,6 +4,7 @@ const STATUS_CONFIG: Record<string, { label: string; color: string }> = {
   scanned: { label: "Scanned", color: "bg-status-scanned" },
   matched: { label: "Matched", color: "bg-status-matched" },
   ambiguous: { label: "Ambiguous", color: "bg-status-ambiguous" },
+  unmatched: { label: "Unmatched", color: "bg-status-unmatched" },
   confirmed: { label: "Confirmed", color: "bg-status-confirmed" },
   transferring: { label: "Transferring", color: "bg-status-transferring" },
   completed: { label: "Completed", color: "bg-status-completed" },
@@ -22,6 +23,7 @@ const FILE_CATEGORY_CONFIG: Record<string, { label: string; color: string }> = {
   movie: { label: "Movie", color: "bg-info/70" },
   special: { label: "Special", color: "bg-warning/70" },
   extra: { label: "Extra", color: "bg-bg-tertiary" },
+  metadata: { label: "Artwork", color: "bg-bg-tertiary" },
 };
 
 export function StatusBadge({ status }: { status: string }) {
@@ -39,18 +41,29 @@ export function MediaTypeBadge({ type }: { type: string }) {
   const config = MEDIA_TYPE_CONFIG[type] || { label: type, color: "bg-bg-tertiary" };
   return (
     <span
-      className={`inline-flex items-center px-1.5 py-0.5 rounded text-[10px] font-bold uppercase tracking-wider text-white ${config.color}`}
+      className={`inline-flex items-center px-1.5 py-0.5 rounded text-micro font-bold uppercase tracking-wider text-white ${config.color}`}
     >
       {config.label}
     </span>
   );
 }
 
+export function HdrBadge({ hdr }: { hdr: string }) {
+  return (
+    <span
+      className="inline-flex items-center px-1.5 py-0.5 rounded text-micro font-bold tracking-wider text-white bg-warning/80"
+      title="High dynamic range"
+    >
+      {hdr}
+    </span>
+  );
+}
+
 export function FileCategoryBadge({ category }: { category: string }) {
   const config = FILE_CATEGORY_CONFIG[category] || { label: category, color: "bg-bg-tertiary" };
   return (
     <span
-      className={`inline-flex items-center px-1.5 py-0.5 rounded text-[10px] font-medium text-white ${config.color}`}
+      className={`inline-flex items-center px-1.5 py-0.5 rounded text-micro font-medium text-white ${config.color}`}
     >
       {config.label}
     </span>
@@ -2,37 +2,52 @@
 
 import { create } from "zustand";
 import { motion, AnimatePresence } from "framer-motion";
-import { useEffect } from "react";
+import { useAppStore } from "@/lib/store";
+
+const DEFAULT_MAX_VISIBLE = 5;
+
+interface ToastAction {
+  label: string;
+  onClick: () => void;
+}
 
 interface Toast {
   id: string;
   message: string;
   type: "success" | "error" | "info" | "warning";
+  action?: ToastAction;
 }
 
 interface ToastStore {
   toasts: Toast[];
-  addToast: (message: string, type?: Toast["type"]) => void;
+  addToast: (message: string, type?: Toast["type"], action?: ToastAction) => void;
   removeToast: (id: string) => void;
+  removeToasts: (ids: string[]) => void;
 }
 
 export const useToastStore = create<ToastStore>((set) => ({
   toasts: [],
-  addToast: (message, type = "info") => {
+  addToast: (message, type = "info", action) => {
     const id = Math.random().toString(36).slice(2);
     set((state) => ({
-      toasts: [...state.toasts, { id, message, type }],
+      toasts: [...state.toasts, { id, message, type, action }],
     }));
+    // Errors stay until dismissed; toasts with an action linger long enough to use it
+    if (type === "error") return;
     setTimeout(() => {
       set((state) => ({
         toasts: state.toasts.filter((t) => t.id !== id),
       }));
-    }, 4000);
+    }, action ? 8000 : 4000);
   },
   removeToast: (id) =>
     set((state) => ({
       toasts: state.toasts.filter((t) => t.id !== id),
     })),
+  removeToasts: (ids) =>
+    set((state) => ({
+      toasts: state.toasts.filter((t) => !ids.includes(t.id)),
+    })),
 }));
 
 const typeStyles: Record<Toast["type"], string> = {
@@ -43,21 +58,48 @@ const typeStyles: Record<Toast["type"], string> = {
 };
 
 export function ToastContainer() {
-  const { toasts, removeToast } = useToastStore();
+  const { toasts, removeToast, removeToasts } = useToastStore();
+  const maxVisibleSetting = useAppStore((s) => s.settings.toast_max_visible);
+  const maxVisible = parseInt(maxVisibleSetting || "", 10) || DEFAULT_MAX_VISIBLE;
+
+  // Newest toasts stay visible; older ones collapse into a "+N more" chip
+  const hidden = toasts.slice(0, Math.max(0, toasts.length - maxVisible));
+  const visible = toasts.slice(hidden.length);
 
   return (
     <div className="fixed bottom-4 right-4 z-[100] flex flex-col gap-2 pointer-events-none">
+      {hidden.length > 0 && (
+        <button
+          onClick={() => removeToasts(hidden.map((t) => t.id))}
+          className="pointer-events-auto self-end px-3 py-1 rounded-lg border border-border bg-bg-secondary text-xs text-text-muted hover:text-text-primary transition-colors"
+          title="Dismiss older notifications"
+        >
+          +{hidden.length} more
+        </button>
+      )}
       <AnimatePresence>
-        {toasts.map((toast) => (
+        {visible.map((toast) => (
           <motion.div
             key={toast.id}
             initial={{ opacity: 0, y: 20, scale: 0.95 }}
             animate={{ opacity: 1, y: 0, scale: 1 }}
             exit={{ opacity: 0, y: -10, scale: 0.95 }}
-            className={`pointer-events-auto px-4 py-2.5 rounded-lg border text-sm max-w-sm ${typeStyles[toast.type]}`}
+            className={`pointer-events-auto flex items-center gap-3 px-4 py-2.5 rounded-lg border text-sm max-w-sm ${typeStyles[toast.type]}`}
             onClick={() => removeToast(toast.id)}
           >
-            {toast.message}
+            <span className="flex-1">{toast.message}</span>
+            {toast.action && (
+              <button
+                onClick={(e) => {
+                  e.stopPropagation();
+                  toast.action!.onClick();
+                  removeToast(toast.id);
+                }}
+                className="flex-shrink-0 px-2 py-0.5 text-xs font-medium rounded border border-current hover:bg-white/10 transition-colors"
+              >
+                {toast.action.label}
+              </button>
+            )}
           </motion.div>
         ))}
       </AnimatePresence>
@@ -6,11 +6,23 @@ import { useState, useEffect, useRef, useCallback } from "react";
 import {
   fetchDestinations,
   createDestination,
+  updateDestination,
   deleteDestination,
   startTransfer,
+  previewTransfer,
+  exportTransferScript,
   testSshConnection,
+  testLocalPath,
   fetchTransferStatus,
+  fetchJobEvents,
+  pauseTransfers,
+  resumeTransfers,
+  updateSettings,
 } from "@/lib/api";
+import type { Destination, JobEvent } from "@/lib/db/schema";
+import { parsePort, validateDestinationFields } from "@/lib/destinations";
+import { notifyDesktop } from "@/lib/notify";
+import { useToastStore } from "./Toast";
 
 interface TransferJob {
   id: number;
@@ -20,6 +32,7 @@ interface TransferJob {
   transferProgress: number | null;
   transferError: string | null;
   destinationPath: string | null;
+  bytesPerSecond?: number;
 }
 
 function formatSize(bytes: number): string {
@@ -36,6 +49,13 @@ function formatRate(bytesPerSec: number): string {
   return `${(bytesPerSec / (1024 * 1024)).toFixed(1)} MB/s`;
 }
 
+function formatEta(seconds: number): string {
+  if (seconds < 60) return `${Math.ceil(seconds)}s left`;
+  const minutes = Math.ceil(seconds / 60);
+  if (minutes < 60) return `${minutes}m left`;
+  return `${Math.floor(minutes / 60)}h ${minutes % 60}m left`;
+}
+
 export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
   const {
     transferDrawerOpen,
@@ -48,35 +68,96 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
 
   const [selectedDest, setSelectedDest] = useState<number | null>(null);
   const [showAddDest, setShowAddDest] = useState(false);
-  const [destForm, setDestForm] = useState({
-    name: "",
-    type: "local" as "local" | "ssh",
-    basePath: "",
-    sshHost: "",
-    sshPort: "22",
-    sshUser: "",
-    sshKeyPath: "",
-    sshKeyPassphrase: "",
-  });
+  // Set while the destination modal is editing an existing destination
+  const [editingDestId, setEditingDestId] = useState<number | null>(null);
+  const [destForm, setDestForm] = useState(EMPTY_DEST_FORM);
   const [testingConnection, setTestingConnection] = useState(false);
   const [testResult, setTestResult] = useState<{
     ok: boolean;
     error?: string;
   } | null>(null);
   const [transferring, setTransferring] = useState(false);
+  const [paused, setPaused] = useState(false);
+  // Also send matched groups that haven't been confirmed yet
+  const [includeMatched, setIncludeMatched] = useState(false);
+  const [spacePreview, setSpacePreview] = useState<{
+    fits: boolean;
+    summary: string;
+  } | null>(null);
   const [activeTransfers, setActiveTransfers] = useState<TransferJob[]>([]);
-  const [transferRates, setTransferRates] = useState<Record<number, number>>(
-    {}
-  );
-  const prevProgress = useRef<Record<number, { progress: number; time: number }>>({});
   const eventSourceRef = useRef<EventSource | null>(null);
+  const lastJobsRef = useRef<TransferJob[]>([]);
 
   useEffect(() => {
     if (transferDrawerOpen) {
-      fetchDestinations().then(setDestinations);
+      fetchDestinations().then((loaded: Destination[]) => {
+        setDestinations(loaded);
+        // Preselect the last-used destination if it still exists
+        const lastId = Number(useAppStore.getState().settings.selected_destination_id);
+        if (loaded.some((d) => d.id === lastId)) {
+          setSelectedDest((current) => current ?? lastId);
+        }
+      });
     }
   }, [transferDrawerOpen, setDestinations]);
 
+  const handleSelectDest = (id: number) => {
+    setSelectedDest(id);
+    const value = String(id);
+    if (useAppStore.getState().settings.selected_destination_id === value) return;
+    useAppStore.setState((state) => ({
+      settings: { ...state.settings, selected_destination_id: value },
+    }));
+    updateSettings({ selected_destination_id: value });
+  };
+
+  // The post-transfer hooks run on the server after the batch ends; wait for
+  // them so a failing hook can be reported without marking any job failed
+  const checkPostTransferHook = useCallback(async () => {
+    for (let attempt = 0; attempt < 90; attempt++) {
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      const { postTransferHook } = await fetchTransferStatus().catch(() => ({
+        postTransferHook: null,
+      }));
+      if (!postTransferHook || postTransferHook.running) continue;
+      if (postTransferHook.error) {
+        useToastStore.getState().addToast(postTransferHook.error, "warning");
+      }
+      return;
+    }
+  }, []);
+
+  const notifyBatchDone = useCallback((transferJobs: TransferJob[]) => {
+    checkPostTransferHook();
+    const failed = transferJobs.filter((j) => j.status === "failed").length;
+    const completed = transferJobs.filter((j) => j.status === "completed").length;
+    const { addToast } = useToastStore.getState();
+    if (failed > 0) {
+      addToast(
+        `Transfers finished: ${completed} succeeded, ${failed} failed`,
+        completed > 0 ? "warning" : "error",
+        {
+          label: "Show failed",
+          onClick: () => {
+            const { setStatusFilter, setIssueFilter } = useAppStore.getState();
+            setStatusFilter(null);
+            setIssueFilter("transfer_failed");
+          },
+        }
+      );
+      notifyDesktop(
+        "Transfers finished with errors",
+        `${failed} of ${transferJobs.length} job${transferJobs.length !== 1 ? "s" : ""} failed`
+      );
+    } else if (completed > 0) {
+      addToast(`Transfers complete: ${completed} file${completed !== 1 ? "s" : ""} transferred`, "success");
+      notifyDesktop(
+        "Transfers complete",
+        `${completed} file${completed !== 1 ? "s" : ""} transferred`
+      );
+    }
+  }, [checkPostTransferHook]);
+
   // Connect to SSE when transferring
   const startProgressStream = useCallback(() => {
     if (eventSourceRef.current) {
@@ -92,30 +173,14 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
         es.close();
         eventSourceRef.current = null;
         setTransferring(false);
+        notifyBatchDone(lastJobsRef.current);
         onRefresh();
         return;
       }
 
       const transferJobs = data as TransferJob[];
       setActiveTransfers(transferJobs);
-
-      // Calculate transfer rates
-      const now = Date.now();
-      const newRates: Record<number, number> = {};
-      for (const job of transferJobs) {
-        const prog = job.transferProgress ?? 0;
-        const transferred = prog * job.fileSize;
-        const prev = prevProgress.current[job.id];
-        if (prev && now - prev.time > 0) {
-          const prevTransferred = prev.progress * job.fileSize;
-          const elapsed = (now - prev.time) / 1000;
-          if (elapsed > 0) {
-            newRates[job.id] = (transferred - prevTransferred) / elapsed;
-          }
-        }
-        prevProgress.current[job.id] = { progress: prog, time: now };
-      }
-      setTransferRates((prev) => ({ ...prev, ...newRates }));
+      lastJobsRef.current = transferJobs;
 
       // Check if all done (no queued or actively transferring)
       const hasActive = transferJobs.some(
@@ -125,6 +190,7 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
         es.close();
         eventSourceRef.current = null;
         setTransferring(false);
+        notifyBatchDone(transferJobs);
         onRefresh();
       }
     };
@@ -143,6 +209,7 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
           } else if (status.jobs.length > 0) {
             setActiveTransfers(status.jobs);
             setTransferring(false);
+            notifyBatchDone(status.jobs);
             onRefresh();
           }
         } catch {
@@ -150,11 +217,12 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
         }
       }, 2000);
     };
-  }, [onRefresh]);
+  }, [onRefresh, notifyBatchDone]);
 
   // Mount initialization: recover in-flight transfers
   useEffect(() => {
     fetchTransferStatus().then((status) => {
+      setPaused(status.paused);
       if (status.active) {
         setActiveTransfers(status.jobs);
         setTransferring(true);
@@ -179,7 +247,31 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
     };
   }, []);
 
-  const handleAddDestination = async () => {
+  const handleEditDest = (d: Destination) => {
+    setEditingDestId(d.id);
+    setTestResult(null);
+    setDestForm({
+      name: d.name,
+      type: d.type,
+      basePath: d.basePath,
+      sshHost: d.sshHost || "",
+      sshPort: String(d.sshPort ?? 22),
+      sshUser: d.sshUser || "",
+      sshKeyPath: d.sshKeyPath || "",
+      // Left blank to keep the stored passphrase
+      sshKeyPassphrase: "",
+    });
+    setShowAddDest(true);
+  };
+
+  const closeDestModal = () => {
+    setShowAddDest(false);
+    setEditingDestId(null);
+    setTestResult(null);
+    setDestForm(EMPTY_DEST_FORM);
+  };
+
+  const handleSaveDestination = async () => {
     const data: Record<string, unknown> = {
       name: destForm.name,
       type: destForm.type,
@@ -187,28 +279,23 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
     };
     if (destForm.type === "ssh") {
       data.sshHost = destForm.sshHost;
-      data.sshPort = parseInt(destForm.sshPort, 10);
+      data.sshPort = parsePort(destForm.sshPort);
       data.sshUser = destForm.sshUser;
       data.sshKeyPath = destForm.sshKeyPath;
       if (destForm.sshKeyPassphrase) {
         data.sshKeyPassphrase = destForm.sshKeyPassphrase;
       }
     }
-    await createDestination(data);
+    const saved = editingDestId
+      ? await updateDestination(editingDestId, data)
+      : await createDestination(data);
+    if (saved.error) {
+      setTestResult({ ok: false, error: saved.error });
+      return;
+    }
     const dests = await fetchDestinations();
     setDestinations(dests);
-    setShowAddDest(false);
-    setTestResult(null);
-    setDestForm({
-      name: "",
-      type: "local",
-      basePath: "",
-      sshHost: "",
-      sshPort: "22",
-      sshUser: "",
-      sshKeyPath: "",
-      sshKeyPassphrase: "",
-    });
+    closeDestModal();
   };
 
   const handleDeleteDest = async (id: number) => {
@@ -221,13 +308,19 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
   const handleTestConnection = async () => {
     setTestingConnection(true);
     setTestResult(null);
+    if (destForm.type === "local") {
+      setTestResult(await testLocalPath(destForm.basePath));
+      setTestingConnection(false);
+      return;
+    }
     const result = await testSshConnection({
       sshHost: destForm.sshHost,
-      sshPort: parseInt(destForm.sshPort, 10),
+      sshPort: parsePort(destForm.sshPort) ?? undefined,
       sshUser: destForm.sshUser,
       sshKeyPath: destForm.sshKeyPath,
       sshKeyPassphrase: destForm.sshKeyPassphrase || undefined,
       basePath: destForm.basePath,
+      destinationId: editingDestId ?? undefined,
     });
     setTestResult(result);
     setTestingConnection(false);
@@ -238,19 +331,53 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
     if (!selectedDest || ids.length === 0) return;
     setTransferring(true);
     setActiveTransfers([]);
-    prevProgress.current = {};
-    setTransferRates({});
-    await startTransfer({ groupIds: ids }, selectedDest);
+    lastJobsRef.current = [];
+    const result = await startTransfer({ groupIds: ids, statuses: eligibleStatuses }, selectedDest);
+    if (result.error) {
+      useToastStore.getState().addToast(result.error, "error");
+      setTransferring(false);
+      return;
+    }
     // Start listening for progress
     startProgressStream();
   };
 
-  // Count confirmed groups
+  const handleExportScript = async (format: "rsync" | "robocopy") => {
+    if (!selectedDest || confirmedSelected.length === 0) return;
+    const result = await exportTransferScript(
+      { groupIds: confirmedSelected, statuses: eligibleStatuses },
+      selectedDest,
+      format
+    );
+    if (result.error) {
+      useToastStore.getState().addToast(result.error, "error");
+      return;
+    }
+    const url = URL.createObjectURL(new Blob([result.script], { type: "text/plain" }));
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = result.filename;
+    link.click();
+    URL.revokeObjectURL(url);
+    useToastStore
+      .getState()
+      .addToast(`Exported ${result.jobCount} file${result.jobCount !== 1 ? "s" : ""} to ${result.filename}`, "success");
+  };
+
+  const handleTogglePause = async () => {
+    const result = paused ? await resumeTransfers() : await pauseTransfers();
+    setPaused(result.paused);
+  };
+
+  // Count groups ready to send: confirmed, plus matched when opted in
+  const eligibleStatuses: ("confirmed" | "matched")[] = includeMatched
+    ? ["confirmed", "matched"]
+    : ["confirmed"];
   const confirmedSelected = Object.keys(selectedGroupIds)
     .map(Number)
     .filter((id) => {
       const group = groups.find((g) => g.id === id);
-      return group?.status === "confirmed";
+      return group?.status === "confirmed" || (includeMatched && group?.status === "matched");
     });
 
   const totalFiles = confirmedSelected.reduce((sum, id) => {
@@ -258,6 +385,30 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
     return sum + (group?.totalFileCount || 0);
   }, 0);
 
+  // Re-check destination free space whenever the batch or destination changes
+  const confirmedKey = confirmedSelected.join(",");
+  useEffect(() => {
+    setSpacePreview(null);
+    if (!selectedDest || !confirmedKey || transferring) return;
+    let cancelled = false;
+    previewTransfer(
+      {
+        groupIds: confirmedKey.split(",").map(Number),
+        statuses: includeMatched ? ["confirmed", "matched"] : ["confirmed"],
+      },
+      selectedDest
+    )
+      .then((result) => {
+        if (!cancelled && !result.error) setSpacePreview(result);
+      })
+      .catch(() => {
+        // Preview is advisory — the transfer route re-checks
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [selectedDest, confirmedKey, transferring, includeMatched]);
+
   // Aggregate transfer stats
   const totalTransferSize = activeTransfers.reduce(
     (s, j) => s + j.fileSize,
@@ -293,7 +444,7 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
           exit={{ y: "100%" }}
           transition={{ type: "spring", damping: 25, stiffness: 300 }}
           className="border-t border-border bg-bg-secondary"
-          style={{ height: transferring || activeTransfers.length > 0 ? "360px" : "340px" }}
+          style={{ height: transferring || activeTransfers.length > 0 ? "22.5rem" : "21.25rem" }}
         >
           <div className="flex items-center justify-between px-6 py-2 border-b border-border">
             <h2 className="text-sm font-semibold text-text-primary">
@@ -309,12 +460,26 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
                 </span>
               ) : (
                 <span className="text-text-muted font-normal">
-                  ({confirmedSelected.length} confirmed group
+                  ({confirmedSelected.length} {includeMatched ? "eligible" : "confirmed"} group
                   {confirmedSelected.length !== 1 ? "s" : ""}, {totalFiles} file
                   {totalFiles !== 1 ? "s" : ""})
                 </span>
               )}
             </h2>
+            {!transferring && activeTransfers.length === 0 && (
+              <label
+                className="ml-auto mr-4 flex items-center gap-2 text-xs text-text-muted"
+                title="Send matched groups too, without confirming them first"
+              >
+                <input
+                  type="checkbox"
+                  checked={includeMatched}
+                  onChange={(e) => setIncludeMatched(e.target.checked)}
+                  className="accent-accent"
+                />
+                Include matched
+              </label>
+            )}
             <button
               onClick={() => setTransferDrawerOpen(false)}
               className="text-text-muted hover:text-text-primary"
@@ -341,7 +506,7 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
               {destinations.map((d) => (
                 <div
                   key={d.id}
-                  onClick={() => setSelectedDest(d.id)}
+                  onClick={() => handleSelectDest(d.id)}
                   className={`flex items-center justify-between p-2 rounded cursor-pointer mb-1 transition-colors ${
                     selectedDest === d.id
                       ? "bg-accent/20 border border-accent/40"
@@ -354,15 +519,27 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
                       {d.basePath}
                     </p>
                   </div>
-                  <button
-                    onClick={(e) => {
-                      e.stopPropagation();
-                      handleDeleteDest(d.id);
-                    }}
-                    className="text-text-muted hover:text-error text-xs"
-                  >
-                    &times;
-                  </button>
+                  <div className="flex items-center gap-2 shrink-0">
+                    <button
+                      onClick={(e) => {
+                        e.stopPropagation();
+                        handleEditDest(d);
+                      }}
+                      className="text-text-muted hover:text-accent text-xs"
+                      title="Edit destination"
+                    >
+                      &#9998;
+                    </button>
+                    <button
+                      onClick={(e) => {
+                        e.stopPropagation();
+                        handleDeleteDest(d.id);
+                      }}
+                      className="text-text-muted hover:text-error text-xs"
+                    >
+                      &times;
+                    </button>
+                  </div>
                 </div>
               ))}
             </div>
@@ -372,7 +549,6 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
               {transferring || activeTransfers.length > 0 ? (
                 <TransferProgress
                   jobs={activeTransfers}
-                  rates={transferRates}
                   overallProgress={overallProgress}
                   totalSize={totalTransferSize}
                   totalTransferred={totalTransferred}
@@ -380,12 +556,14 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
                   activeCount={activeCount}
                   queuedCount={queuedCount}
                   failedCount={failedCount}
+                  paused={paused}
+                  onTogglePause={handleTogglePause}
                 />
               ) : confirmedSelected.length === 0 ? (
                 <div className="flex-1 flex items-center justify-center">
                   <p className="text-text-muted text-sm text-center">
-                    Select confirmed groups in the queue and a destination to
-                    transfer.
+                    Select {includeMatched ? "confirmed or matched" : "confirmed"} groups in the
+                    queue and a destination to transfer.
                   </p>
                 </div>
               ) : !selectedDest ? (
@@ -409,13 +587,41 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
                         {destinations.find((d) => d.id === selectedDest)?.name}
                       </span>
                     </p>
+                    {spacePreview && (
+                      <p
+                        className={`text-xs ${
+                          spacePreview.fits ? "text-text-muted" : "text-error"
+                        }`}
+                      >
+                        {spacePreview.summary}
+                        {!spacePreview.fits && " — not enough free space"}
+                      </p>
+                    )}
                     <button
                       onClick={handleTransfer}
-                      disabled={transferring}
+                      disabled={transferring || spacePreview?.fits === false}
                       className="px-6 py-2 rounded-md bg-accent text-white hover:bg-accent-hover disabled:opacity-50 transition-colors font-medium"
                     >
                       Start Transfer
                     </button>
+                    <div className="flex justify-center gap-3 text-xs">
+                      <button
+                        onClick={() => handleExportScript("rsync")}
+                        className="text-text-muted hover:text-accent transition-colors"
+                        title="Download a shell script that copies these files with rsync"
+                      >
+                        Export rsync script
+                      </button>
+                      {destinations.find((d) => d.id === selectedDest)?.type === "local" && (
+                        <button
+                          onClick={() => handleExportScript("robocopy")}
+                          className="text-text-muted hover:text-accent transition-colors"
+                          title="Download a Windows batch file that copies these files with robocopy"
+                        >
+                          Export robocopy script
+                        </button>
+                      )}
+                    </div>
                   </div>
                 </div>
               )}
@@ -442,7 +648,7 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
         </button>
       )}
 
-      {/* Add Destination Modal */}
+      {/* Add/Edit Destination Modal */}
       {showAddDest && (
         <AddDestinationModal
           destForm={destForm}
@@ -451,17 +657,26 @@ export function TransferDrawer({ onRefresh }: { onRefresh: () => void }) {
           testResult={testResult}
           onTestConnection={handleTestConnection}
           onSetTestResult={setTestResult}
-          onSave={handleAddDestination}
-          onClose={() => {
-            setShowAddDest(false);
-            setTestResult(null);
-          }}
+          editing={editingDestId !== null}
+          onSave={handleSaveDestination}
+          onClose={closeDestModal}
         />
       )}
     </>
   );
 }
 
+const EMPTY_DEST_FORM = {
+  name: "",
+  type: "local" as "local" | "ssh",
+  basePath: "",
+  sshHost: "",
+  sshPort: "22",
+  sshUser: "",
+  sshKeyPath: "",
+  sshKeyPassphrase: "",
+};
+
 const INPUT_CLASS =
   "w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent";
 
@@ -472,6 +687,7 @@ function AddDestinationModal({
   testResult,
   onTestConnection,
   onSetTestResult,
+  editing,
   onSave,
   onClose,
 }: {
@@ -490,6 +706,7 @@ function AddDestinationModal({
   testResult: { ok: boolean; error?: string } | null;
   onTestConnection: () => void;
   onSetTestResult: (r: null) => void;
+  editing: boolean;
   onSave: () => void;
   onClose: () => void;
 }) {
@@ -501,19 +718,22 @@ function AddDestinationModal({
     return () => window.removeEventListener("keydown", handler);
   }, [onClose]);
 
+  const errors = validateDestinationFields(destForm);
+  const isValid = Object.keys(errors).length === 0;
+
   return (
     <div
       className="fixed inset-0 z-50 flex items-center justify-center bg-black/60"
       onClick={onClose}
     >
       <div
-        className="bg-bg-secondary border border-border rounded-lg shadow-xl w-[440px] flex flex-col"
+        className="bg-bg-secondary border border-border rounded-lg shadow-xl w-[27.5rem] flex flex-col"
         onClick={(e) => e.stopPropagation()}
       >
         {/* Header */}
         <div className="flex items-center justify-between px-5 py-3 border-b border-border">
           <h2 className="text-sm font-semibold text-text-primary">
-            Add Destination
+            {editing ? "Edit Destination" : "Add Destination"}
           </h2>
           <button
             onClick={onClose}
@@ -526,7 +746,7 @@ function AddDestinationModal({
         {/* Form */}
         <div className="p-5 space-y-3">
           <div>
-            <label className="text-[10px] uppercase tracking-wider text-text-muted mb-1 block">
+            <label className="text-micro uppercase tracking-wider text-text-muted mb-1 block">
               Name
             </label>
             <input
@@ -540,7 +760,7 @@ function AddDestinationModal({
           </div>
 
           <div>
-            <label className="text-[10px] uppercase tracking-wider text-text-muted mb-1 block">
+            <label className="text-micro uppercase tracking-wider text-text-muted mb-1 block">
               Type
             </label>
             <select
@@ -560,7 +780,7 @@ function AddDestinationModal({
           </div>
 
           <div>
-            <label className="text-[10px] uppercase tracking-wider text-text-muted mb-1 block">
+            <label className="text-micro uppercase tracking-wider text-text-muted mb-1 block">
               Base Path
             </label>
             <input
@@ -581,7 +801,7 @@ function AddDestinationModal({
             <>
               <div className="flex gap-3">
                 <div className="flex-1">
-                  <label className="text-[10px] uppercase tracking-wider text-text-muted mb-1 block">
+                  <label className="text-micro uppercase tracking-wider text-text-muted mb-1 block">
                     Host
                   </label>
                   <input
@@ -594,22 +814,26 @@ function AddDestinationModal({
                   />
                 </div>
                 <div className="w-20">
-                  <label className="text-[10px] uppercase tracking-wider text-text-muted mb-1 block">
+                  <label className="text-micro uppercase tracking-wider text-text-muted mb-1 block">
                     Port
                   </label>
                   <input
                     placeholder="22"
+                    inputMode="numeric"
                     value={destForm.sshPort}
                     onChange={(e) =>
                       setDestForm({ ...destForm, sshPort: e.target.value })
                     }
-                    className={INPUT_CLASS}
+                    className={errors.sshPort ? INPUT_CLASS.replace("border-border", "border-error") : INPUT_CLASS}
                   />
                 </div>
               </div>
+              {errors.sshPort && (
+                <p className="-mt-2 text-xs text-error">{errors.sshPort}</p>
+              )}
 
               <div>
-                <label className="text-[10px] uppercase tracking-wider text-text-muted mb-1 block">
+                <label className="text-micro uppercase tracking-wider text-text-muted mb-1 block">
                   Username
                 </label>
                 <input
@@ -623,7 +847,7 @@ function AddDestinationModal({
               </div>
 
               <div>
-                <label className="text-[10px] uppercase tracking-wider text-text-muted mb-1 block">
+                <label className="text-micro uppercase tracking-wider text-text-muted mb-1 block">
                   SSH Key Path
                 </label>
                 <input
@@ -637,7 +861,7 @@ function AddDestinationModal({
               </div>
 
               <div>
-                <label className="text-[10px] uppercase tracking-wider text-text-muted mb-1 block">
+                <label className="text-micro uppercase tracking-wider text-text-muted mb-1 block">
                   Key Passphrase{" "}
                   <span className="normal-case tracking-normal text-text-muted/60">
                     (optional)
@@ -645,7 +869,7 @@ function AddDestinationModal({
                 </label>
                 <input
                   type="password"
-                  placeholder="Leave blank if none"
+                  placeholder={editing ? "Leave blank to keep current" : "Leave blank if none"}
                   value={destForm.sshKeyPassphrase}
                   onChange={(e) =>
                     setDestForm({
@@ -656,29 +880,38 @@ function AddDestinationModal({
                   className={INPUT_CLASS}
                 />
               </div>
-
-              <button
-                onClick={onTestConnection}
-                disabled={
-                  testingConnection || !destForm.sshHost || !destForm.sshUser
-                }
-                className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-secondary hover:bg-bg-hover disabled:opacity-50 transition-colors"
-              >
-                {testingConnection ? "Testing..." : "Test Connection"}
-              </button>
-              {testResult && (
-                <p
-                  className={`text-xs ${
-                    testResult.ok ? "text-success" : "text-error"
-                  }`}
-                >
-                  {testResult.ok
-                    ? "Connection successful"
-                    : testResult.error || "Connection failed"}
-                </p>
-              )}
             </>
           )}
+
+          <button
+            onClick={onTestConnection}
+            disabled={
+              testingConnection ||
+              (destForm.type === "ssh"
+                ? !!(errors.sshHost || errors.sshUser || errors.sshPort)
+                : !!errors.basePath)
+            }
+            className="w-full px-3 py-2 text-sm rounded-md bg-bg-tertiary border border-border text-text-secondary hover:bg-bg-hover disabled:opacity-50 transition-colors"
+          >
+            {testingConnection
+              ? "Testing..."
+              : destForm.type === "ssh"
+              ? "Test Connection"
+              : "Test Path"}
+          </button>
+          {testResult && (
+            <p
+              className={`text-xs ${
+                testResult.ok ? "text-success" : "text-error"
+              }`}
+            >
+              {testResult.ok
+                ? destForm.type === "ssh"
+                  ? "Connection successful"
+                  : "Path exists and is writable"
+                : testResult.error || "Connection failed"}
+            </p>
+          )}
         </div>
 
         {/* Footer */}
@@ -691,10 +924,11 @@ function AddDestinationModal({
           </button>
           <button
             onClick={onSave}
-            disabled={!destForm.name || !destForm.basePath}
+            disabled={!isValid}
+            title={isValid ? undefined : Object.values(errors).join("\n")}
             className="flex-1 px-3 py-2 text-sm rounded-md bg-accent text-white hover:bg-accent-hover disabled:opacity-50 transition-colors font-medium"
           >
-            Save Destination
+            {editing ? "Save Changes" : "Save Destination"}
           </button>
         </div>
       </div>
@@ -702,9 +936,30 @@ function AddDestinationModal({
   );
 }
 
+/** Summarize a job's history as "Attempt 1: connection refused", "Attempt 2: ok". */
+function describeAttempts(events: JobEvent[]): Array<{ attempt: number; outcome: string; at: string }> {
+  const attempts: Array<{ attempt: number; outcome: string; at: string }> = [];
+  let attempt = 0;
+  for (const event of events) {
+    if (event.status === "transferring") {
+      attempt++;
+      attempts.push({ attempt, outcome: "interrupted", at: event.createdAt });
+    } else if (event.status === "completed" || event.status === "failed") {
+      const outcome = event.status === "completed" ? "ok" : event.message || "failed";
+      const current = attempts[attempts.length - 1];
+      if (current && current.attempt === attempt && current.outcome === "interrupted") {
+        current.outcome = outcome;
+        current.at = event.createdAt;
+      } else {
+        attempts.push({ attempt: Math.max(attempt, 1), outcome, at: event.createdAt });
+      }
+    }
+  }
+  return attempts;
+}
+
 function TransferProgress({
   jobs,
-  rates,
   overallProgress,
   totalSize,
   totalTransferred,
@@ -712,9 +967,10 @@ function TransferProgress({
   activeCount,
   queuedCount,
   failedCount,
+  paused,
+  onTogglePause,
 }: {
   jobs: TransferJob[];
-  rates: Record<number, number>;
   overallProgress: number;
   totalSize: number;
   totalTransferred: number;
@@ -722,9 +978,29 @@ function TransferProgress({
   activeCount: number;
   queuedCount: number;
   failedCount: number;
+  paused: boolean;
+  onTogglePause: () => void;
 }) {
-  const totalRate = Object.values(rates).reduce(
-    (s, r) => s + Math.max(0, r),
+  const [history, setHistory] = useState<{ jobId: number; events: JobEvent[] } | null>(null);
+
+  const toggleHistory = async (jobId: number) => {
+    if (history?.jobId === jobId) {
+      setHistory(null);
+      return;
+    }
+    setHistory({ jobId, events: await fetchJobEvents(jobId) });
+  };
+
+  const totalRate = jobs.reduce(
+    (s, j) => s + (j.status === "transferring" ? j.bytesPerSecond ?? 0 : 0),
+    0
+  );
+  // Remaining bytes across everything not yet finished, including queued jobs
+  const remainingBytes = jobs.reduce(
+    (s, j) =>
+      j.status === "transferring" || j.status === "queued"
+        ? s + (1 - (j.transferProgress ?? 0)) * j.fileSize
+        : s,
     0
   );
 
@@ -740,7 +1016,11 @@ function TransferProgress({
             </span>
           </span>
           <span className="text-text-muted">
+            {paused && <span className="text-warning mr-2">Paused</span>}
             {totalRate > 0 && formatRate(totalRate)}
+            {totalRate > 0 && remainingBytes > 0 && (
+              <span className="ml-2">{formatEta(remainingBytes / totalRate)}</span>
+            )}
             {activeCount > 0 && (
               <span className="ml-2">
                 {activeCount} active
@@ -753,11 +1033,21 @@ function TransferProgress({
             )}
           </span>
         </div>
-        <div className="w-full h-2 bg-bg-tertiary rounded-full overflow-hidden">
-          <div
-            className="h-full bg-accent rounded-full transition-all duration-300"
-            style={{ width: `${Math.min(overallProgress * 100, 100)}%` }}
-          />
+        <div className="flex items-center gap-3">
+          <div className="flex-1 h-2 bg-bg-tertiary rounded-full overflow-hidden">
+            <div
+              className="h-full bg-accent rounded-full transition-all duration-300"
+              style={{ width: `${Math.min(overallProgress * 100, 100)}%` }}
+            />
+          </div>
+          {(activeCount > 0 || queuedCount > 0) && (
+            <button
+              onClick={onTogglePause}
+              className="px-3 py-1 text-xs rounded-md bg-bg-tertiary border border-border text-text-secondary hover:text-text-primary hover:bg-bg-hover transition-colors"
+            >
+              {paused ? "Resume" : "Pause"}
+            </button>
+          )}
         </div>
       </div>
 
@@ -766,64 +1056,86 @@ function TransferProgress({
         {jobs.map((job) => {
           const progress = job.transferProgress ?? 0;
           const transferred = progress * job.fileSize;
-          const rate = rates[job.id] ?? 0;
+          const rate = job.bytesPerSecond ?? 0;
 
           return (
-            <div
-              key={job.id}
-              className="flex items-center gap-3 py-1"
-            >
-              {/* Status icon */}
-              <span className="flex-shrink-0 w-4 text-center">
-                {job.status === "completed" ? (
-                  <span className="text-success text-xs">&#10003;</span>
-                ) : job.status === "failed" ? (
-                  <span className="text-error text-xs">&#10007;</span>
-                ) : job.status === "queued" ? (
-                  <span className="text-text-muted text-xs">&#8943;</span>
-                ) : (
-                  <span className="animate-spin inline-block w-3 h-3 border border-accent/30 border-t-accent rounded-full" />
-                )}
-              </span>
+            <div key={job.id}>
+              <div
+                className={`flex items-center gap-3 py-1 ${
+                  job.status === "failed" ? "cursor-pointer hover:bg-bg-hover rounded" : ""
+                }`}
+                onClick={job.status === "failed" ? () => toggleHistory(job.id) : undefined}
+                title={job.status === "failed" ? "Show attempts" : undefined}
+              >
+                {/* Status icon */}
+                <span className="flex-shrink-0 w-4 text-center">
+                  {job.status === "completed" ? (
+                    <span className="text-success text-xs">&#10003;</span>
+                  ) : job.status === "failed" ? (
+                    <span className="text-error text-xs">&#10007;</span>
+                  ) : job.status === "queued" ? (
+                    <span className="text-text-muted text-xs">&#8943;</span>
+                  ) : (
+                    <span className="animate-spin inline-block w-3 h-3 border border-accent/30 border-t-accent rounded-full" />
+                  )}
+                </span>
 
-              {/* File info + progress bar */}
-              <div className="flex-1 min-w-0">
-                <div className="flex items-center justify-between mb-0.5">
-                  <span className="text-xs text-text-secondary truncate mr-2">
-                    {job.fileName}
-                  </span>
-                  <span className="text-[10px] text-text-muted flex-shrink-0">
-                    {job.status === "completed" ? (
-                      formatSize(job.fileSize)
-                    ) : job.status === "failed" ? (
-                      <span className="text-error">
-                        {job.transferError || "Failed"}
-                      </span>
-                    ) : job.status === "queued" ? (
-                      <span className="text-text-muted">
-                        {formatSize(job.fileSize)} &middot; queued
-                      </span>
-                    ) : (
-                      <>
-                        {formatSize(transferred)} / {formatSize(job.fileSize)}
-                        {rate > 0 && (
-                          <span className="ml-1.5">{formatRate(rate)}</span>
-                        )}
-                      </>
-                    )}
-                  </span>
-                </div>
-                {job.status === "transferring" && (
-                  <div className="w-full h-1 bg-bg-tertiary rounded-full overflow-hidden">
-                    <div
-                      className="h-full bg-accent/70 rounded-full transition-all duration-300"
-                      style={{
-                        width: `${Math.min(progress * 100, 100)}%`,
-                      }}
-                    />
+                {/* File info + progress bar */}
+                <div className="flex-1 min-w-0">
+                  <div className="flex items-center justify-between mb-0.5">
+                    <span className="text-xs text-text-secondary truncate mr-2">
+                      {job.fileName}
+                    </span>
+                    <span className="text-micro text-text-muted flex-shrink-0">
+                      {job.status === "completed" ? (
+                        formatSize(job.fileSize)
+                      ) : job.status === "failed" ? (
+                        <span className="text-error">
+                          {job.transferError || "Failed"}
+                        </span>
+                      ) : job.status === "queued" ? (
+                        <span className="text-text-muted">
+                          {formatSize(job.fileSize)} &middot; queued
+                        </span>
+                      ) : (
+                        <>
+                          {formatSize(transferred)} / {formatSize(job.fileSize)}
+                          {rate > 0 && (
+                            <span className="ml-1.5">{formatRate(rate)}</span>
+                          )}
+                        </>
+                      )}
+                    </span>
                   </div>
-                )}
+                  {job.status === "transferring" && (
+                    <div className="w-full h-1 bg-bg-tertiary rounded-full overflow-hidden">
+                      <div
+                        className="h-full bg-accent/70 rounded-full transition-all duration-300"
+                        style={{
+                          width: `${Math.min(progress * 100, 100)}%`,
+                        }}
+                      />
+                    </div>
+                  )}
+                </div>
               </div>
+              {history?.jobId === job.id && (
+                <div className="ml-7 mb-1 space-y-0.5">
+                  {history.events.length === 0 ? (
+                    <p className="text-micro text-text-muted">No transfer history</p>
+                  ) : (
+                    describeAttempts(history.events).map((a, i) => (
+                      <p key={i} className="text-micro text-text-muted">
+                        <span className="text-text-secondary">Attempt {a.attempt}:</span>{" "}
+                        <span className={a.outcome === "ok" ? "text-success" : "text-error"}>
+                          {a.outcome}
+                        </span>
+                        <span className="ml-1.5">{new Date(a.at).toLocaleString()}</span>
+                      </p>
+                    ))
+                  )}
+                </div>
+              )}
             </div>
           );
         })}
@@ -32,6 +32,56 @@ export async function deleteGroup(id: number) {
   return res.json();
 }
 
+export interface DuplicateGroupPair {
+  staleId: number;
+  liveId: number;
+  reason: "folder" | "tmdb";
+  title: string;
+  staleFolder: string;
+  liveFolder: string;
+}
+
+export async function fetchDuplicateGroups(): Promise<{ pairs: DuplicateGroupPair[] }> {
+  const res = await fetch("/api/groups/duplicates");
+  return res.json();
+}
+
+export async function mergeDuplicateGroups(
+  staleIds: number[]
+): Promise<{ merged: Array<{ staleId: number; liveId: number; title: string; filesCarried: number }> }> {
+  const res = await fetch("/api/groups/duplicates", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ staleIds }),
+  });
+  return res.json();
+}
+
+// ── Labels ──────────────────────────────────────────────
+
+export async function fetchLabels() {
+  const res = await fetch("/api/labels");
+  return res.json();
+}
+
+export async function createLabel(name: string, color?: string) {
+  const res = await fetch("/api/labels", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ name, color }),
+  });
+  return res.json();
+}
+
+export async function setGroupLabels(groupId: number, labelIds: number[]) {
+  const res = await fetch(`/api/groups/${groupId}/labels`, {
+    method: "PUT",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ labelIds }),
+  });
+  return res.json();
+}
+
 // ── Jobs (kept for backward compat) ─────────────────────
 
 export async function fetchJobs(params?: Record<string, string>) {
@@ -59,6 +109,13 @@ export async function updateJob(id: number, updates: Record<string, unknown>) {
   return res.json();
 }
 
+export async function fetchJobEvents(id: number): Promise<
+  Array<{ id: number; jobId: number; status: string; message: string | null; createdAt: string }>
+> {
+  const res = await fetch(`/api/jobs/${id}/events`);
+  return res.json();
+}
+
 export async function deleteJob(id: number) {
   const res = await fetch(`/api/jobs/${id}`, { method: "DELETE" });
   return res.json();
@@ -68,7 +125,15 @@ export async function deleteJob(id: number) {
 
 export async function bulkAction(
   action: string,
-  opts: { jobIds?: number[]; groupIds?: number[] }
+  opts: {
+    jobIds?: number[];
+    groupIds?: number[];
+    // retitle only
+    title?: string;
+    year?: number | null;
+    // unskip only: the `previous` snapshot a skip returned
+    snapshot?: unknown;
+  }
 ) {
   const res = await fetch("/api/jobs/bulk", {
     method: "POST",
@@ -78,6 +143,11 @@ export async function bulkAction(
   return res.json();
 }
 
+export async function emptyTrash(): Promise<{ groups: number; jobs: number }> {
+  const res = await fetch("/api/trash", { method: "DELETE" });
+  return res.json();
+}
+
 // ── Scan ────────────────────────────────────────────────
 
 export async function triggerScan(path?: string) {
@@ -91,8 +161,67 @@ export async function triggerScan(path?: string) {
 
 // ── Match ───────────────────────────────────────────────
 
-export async function triggerMatch() {
-  const res = await fetch("/api/match", { method: "POST" });
+export async function triggerMatch(options: { failedOnly?: boolean } = {}) {
+  const res = await fetch("/api/match", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(options),
+  });
+  return res.json();
+}
+
+export async function cancelMatch(): Promise<{ cancelled: boolean }> {
+  const res = await fetch("/api/match/cancel", { method: "POST" });
+  return res.json();
+}
+
+// ── Maintenance ─────────────────────────────────────────
+
+export async function reparseLibrary(): Promise<{
+  groups?: number;
+  files?: number;
+  reset?: number;
+  lockedSkipped?: number;
+  renumbered?: number;
+  titlesRefreshed?: number;
+  error?: string;
+}> {
+  const res = await fetch("/api/groups/reparse", { method: "POST" });
+  return res.json();
+}
+
+export async function runDatabaseMaintenance(action: "vacuum" | "integrity"): Promise<{
+  bytesBefore?: number;
+  bytesAfter?: number;
+  ok?: boolean;
+  problems?: string[];
+  error?: string;
+}> {
+  const res = await fetch("/api/maintenance", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ action }),
+  });
+  return res.json();
+}
+
+export function databaseBackupUrl() {
+  return "/api/maintenance/backup";
+}
+
+export async function restoreDatabaseBackup(file: File): Promise<{ success?: boolean; error?: string }> {
+  const res = await fetch("/api/maintenance/restore", {
+    method: "POST",
+    headers: { "Content-Type": "application/octet-stream" },
+    body: file,
+  });
+  return res.json();
+}
+
+// ── Stats ───────────────────────────────────────────────
+
+export async function fetchLibraryStats() {
+  const res = await fetch("/api/stats");
   return res.json();
 }
 
@@ -112,8 +241,28 @@ export async function updateSettings(updates: Record<string, string>) {
   return res.json();
 }
 
+export async function checkScanPath(path: string): Promise<{ ok: boolean; error?: string }> {
+  const res = await fetch("/api/settings/check-path", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ path }),
+  });
+  return res.json();
+}
+
 // ── TMDB Search ─────────────────────────────────────────
 
+export async function validateTmdbKey(
+  apiKey: string
+): Promise<{ ok: boolean; kind?: string; error?: string }> {
+  const res = await fetch("/api/settings/validate-tmdb", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ apiKey }),
+  });
+  return res.json();
+}
+
 export async function searchTmdb(query: string, mediaType?: string, year?: number) {
   const params = new URLSearchParams({ query });
   if (mediaType) params.set("mediaType", mediaType);
@@ -124,13 +273,95 @@ export async function searchTmdb(query: string, mediaType?: string, year?: numbe
 
 // ── Seasons / Episodes ─────────────────────────────────
 
-export async function fetchSeasons(groupId: number) {
-  const res = await fetch(`/api/groups/${groupId}/seasons`);
+export async function fetchSeasons(groupId: number, provider?: string, order?: string) {
+  const params = new URLSearchParams();
+  if (provider) params.set("provider", provider);
+  if (order) params.set("order", order);
+  const res = await fetch(`/api/groups/${groupId}/seasons?${params}`);
+  return res.json();
+}
+
+export async function fetchSeasonEpisodes(
+  groupId: number,
+  season: number,
+  provider?: string,
+  order?: string
+) {
+  const params = new URLSearchParams({ season: String(season) });
+  if (provider) params.set("provider", provider);
+  if (order) params.set("order", order);
+  const res = await fetch(`/api/groups/${groupId}/seasons?${params}`);
   return res.json();
 }
 
-export async function fetchSeasonEpisodes(groupId: number, season: number) {
-  const res = await fetch(`/api/groups/${groupId}/seasons?season=${season}`);
+/** Re-search a group's candidates as a movie, a show, or either ("unknown"). */
+export async function researchGroupCandidates(groupId: number, mediaType: string) {
+  const res = await fetch(`/api/groups/${groupId}/candidates`, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ mediaType }),
+  });
+  return res.json();
+}
+
+/** Fill in season/episode for unnumbered files by fuzzy-matching episode titles. */
+export async function alignEpisodesByTitle(groupId: number) {
+  const res = await fetch(`/api/groups/${groupId}/align-episodes`, { method: "POST" });
+  return res.json();
+}
+
+/** Move some of a group's files into a new, unmatched group. */
+export async function splitGroup(groupId: number, jobIds: number[]) {
+  const res = await fetch(`/api/groups/${groupId}/split`, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ jobIds }),
+  });
+  return res.json();
+}
+
+/**
+ * Move every file of the source groups into the target, which keeps its match.
+ * Answers 409 with `conflicts` when a source is matched to another title and
+ * `force` isn't set.
+ */
+export async function mergeGroups(targetId: number, sourceIds: number[], force = false) {
+  const res = await fetch(`/api/groups/${targetId}/merge`, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ sourceIds, force }),
+  });
+  return res.json();
+}
+
+// ── Posters ─────────────────────────────────────────────
+
+/** URL for a TMDB poster served through the local disk cache. */
+export function posterUrl(posterPath: string, size = "w185") {
+  const params = new URLSearchParams({ path: posterPath, size });
+  return `/api/posters?${params}`;
+}
+
+export async function fetchPosterCacheStats(): Promise<{ files: number; bytes: number }> {
+  const res = await fetch("/api/posters");
+  return res.json();
+}
+
+export async function clearPosterCache() {
+  const res = await fetch("/api/posters", { method: "DELETE" });
+  return res.json();
+}
+
+// ── File manager ────────────────────────────────────────
+
+export async function revealInFileManager(
+  opts: { groupId?: number; jobId?: number }
+): Promise<{ success?: boolean; error?: string }> {
+  const res = await fetch("/api/reveal", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(opts),
+  });
   return res.json();
 }
 
@@ -150,6 +381,15 @@ export async function createDestination(data: Record<string, unknown>) {
   return res.json();
 }
 
+export async function updateDestination(id: number, data: Record<string, unknown>) {
+  const res = await fetch(`/api/destinations/${id}`, {
+    method: "PATCH",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(data),
+  });
+  return res.json();
+}
+
 export async function deleteDestination(id: number) {
   const res = await fetch(`/api/destinations/${id}`, { method: "DELETE" });
   return res.json();
@@ -162,19 +402,54 @@ export async function testSshConnection(data: {
   sshKeyPath: string;
   sshKeyPassphrase?: string;
   basePath: string;
-}): Promise<{ ok: boolean; error?: string }> {
-  const res = await fetch("/api/destinations/test-connection", {
+  destinationId?: number;
+}): Promise<{ ok: boolean; stage?: string; error?: string }> {
+  // The server gives up after ~12s; don't let a dropped request leave the UI waiting forever
+  const controller = new AbortController();
+  const timer = setTimeout(() => controller.abort(), 20000);
+  try {
+    const res = await fetch("/api/destinations/test-connection", {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify(data),
+      signal: controller.signal,
+    });
+    return await res.json();
+  } catch {
+    return {
+      ok: false,
+      stage: "connect",
+      error: controller.signal.aborted
+        ? "Connection test timed out"
+        : "Connection test request failed",
+    };
+  } finally {
+    clearTimeout(timer);
+  }
+}
+
+export async function testLocalPath(
+  basePath: string
+): Promise<{ ok: boolean; error?: string }> {
+  const res = await fetch("/api/destinations/test-local", {
     method: "POST",
     headers: { "Content-Type": "application/json" },
-    body: JSON.stringify(data),
+    body: JSON.stringify({ basePath }),
   });
   return res.json();
 }
 
 // ── Transfer ────────────────────────────────────────────
 
+// Which job statuses a batch picks up from its groups; the server defaults to confirmed
+interface TransferBatch {
+  jobIds?: number[];
+  groupIds?: number[];
+  statuses?: ("confirmed" | "matched" | "completed")[];
+}
+
 export async function startTransfer(
-  opts: { jobIds?: number[]; groupIds?: number[] },
+  opts: TransferBatch,
   destinationId: number
 ) {
   const res = await fetch("/api/transfer", {
@@ -185,8 +460,42 @@ export async function startTransfer(
   return res.json();
 }
 
+export async function previewTransfer(
+  opts: TransferBatch,
+  destinationId: number
+): Promise<{
+  jobCount: number;
+  required: number;
+  available: number | null;
+  fits: boolean;
+  summary: string;
+  error?: string;
+}> {
+  const res = await fetch("/api/transfer/preview", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ ...opts, destinationId }),
+  });
+  return res.json();
+}
+
+export async function exportTransferScript(
+  opts: TransferBatch,
+  destinationId: number,
+  format: "rsync" | "robocopy"
+): Promise<{ script: string; filename: string; jobCount: number; error?: string }> {
+  const res = await fetch("/api/transfer/script", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ ...opts, destinationId, format }),
+  });
+  return res.json();
+}
+
 export async function fetchTransferStatus(): Promise<{
   active: boolean;
+  paused: boolean;
+  postTransferHook: { running: boolean; error: string | null };
   jobs: Array<{
     id: number;
     status: string;
@@ -195,8 +504,19 @@ export async function fetchTransferStatus(): Promise<{
     transferProgress: number | null;
     transferError: string | null;
     destinationPath: string | null;
+    bytesPerSecond: number;
   }>;
 }> {
   const res = await fetch("/api/transfer/status");
   return res.json();
 }
+
+export async function pauseTransfers(): Promise<{ paused: boolean }> {
+  const res = await fetch("/api/transfer/pause", { method: "POST" });
+  return res.json();
+}
+
+export async function resumeTransfers(): Promise<{ paused: boolean }> {
+  const res = await fetch("/api/transfer/pause", { method: "DELETE" });
+  return res.json();
+}
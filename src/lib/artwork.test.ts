@@ -0,0 +1,28 @@
+import { describe, expect, it } from "vitest";
+import { detectMetadataFile, pickMetadataFiles } from "./artwork";
+
+const files = (...names: string[]) => names.map((fileName) => ({ fileName }));
+
+describe("detectMetadataFile", () => {
+  it("recognizes artwork and nfo names", () => {
+    expect(detectMetadataFile("Folder.JPG")).toBe("poster");
+    expect(detectMetadataFile("backdrop.png")).toBe("fanart");
+    expect(detectMetadataFile("tvshow.nfo")).toBe("nfo");
+    expect(detectMetadataFile("Show.S01E01.nfo")).toBeNull();
+    expect(detectMetadataFile("poster.txt")).toBeNull();
+  });
+});
+
+describe("pickMetadataFiles", () => {
+  it("keeps the canonical name over an alias of the same kind", () => {
+    const { kept, duplicates } = pickMetadataFiles(files("folder.jpg", "poster.jpg", "fanart.jpg"));
+    expect(kept.map((f) => f.fileName).sort()).toEqual(["fanart.jpg", "poster.jpg"]);
+    expect(duplicates.map((f) => f.fileName)).toEqual(["folder.jpg"]);
+  });
+
+  it("keeps one alias by name when the canonical file is missing", () => {
+    const { kept, duplicates } = pickMetadataFiles(files("folder.jpg", "cover.jpg"));
+    expect(kept.map((f) => f.fileName)).toEqual(["cover.jpg"]);
+    expect(duplicates.map((f) => f.fileName)).toEqual(["folder.jpg"]);
+  });
+});
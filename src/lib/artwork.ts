@@ -0,0 +1,94 @@
+// Artwork and metadata files that sit in a source folder next to the video
+// (poster.jpg, fanart.jpg, tvshow.nfo) have fixed names Jellyfin and Plex look
+// for at the show or movie root. The scanner picks them up as "metadata" jobs
+// and naming renames them to those canonical names instead of treating them
+// as extras.
+
+const IMAGE_EXTENSIONS = new Set([".jpg", ".jpeg", ".png", ".webp"]);
+
+export type MetadataKind =
+  | "poster"
+  | "fanart"
+  | "banner"
+  | "logo"
+  | "clearart"
+  | "landscape"
+  | "disc"
+  | "nfo";
+
+// Names the common tools write (lowercased, without extension) → kind.
+// Both servers read the canonical name each kind is saved under.
+const IMAGE_NAMES: Record<string, MetadataKind> = {
+  poster: "poster",
+  folder: "poster",
+  cover: "poster",
+  default: "poster",
+  movie: "poster",
+  show: "poster",
+  fanart: "fanart",
+  backdrop: "fanart",
+  background: "fanart",
+  art: "fanart",
+  banner: "banner",
+  logo: "logo",
+  clearlogo: "logo",
+  clearart: "clearart",
+  landscape: "landscape",
+  thumb: "landscape",
+  disc: "disc",
+  discart: "disc",
+  cdart: "disc",
+};
+
+const NFO_NAMES = new Set(["tvshow", "movie"]);
+
+/** The metadata kind a group-root file is, or null if it isn't one. */
+export function detectMetadataFile(fileName: string): MetadataKind | null {
+  const lower = fileName.toLowerCase();
+  const dot = lower.lastIndexOf(".");
+  if (dot <= 0) return null;
+  const stem = lower.slice(0, dot);
+  const ext = lower.slice(dot);
+  if (ext === ".nfo") return NFO_NAMES.has(stem) ? "nfo" : null;
+  if (!IMAGE_EXTENSIONS.has(ext)) return null;
+  return IMAGE_NAMES[stem] ?? null;
+}
+
+/**
+ * Keep one file per metadata kind, since they'd all be renamed to the same
+ * canonical name. A file already under that name wins (poster.jpg over
+ * folder.jpg), otherwise the first by name; the rest are returned as
+ * duplicates for the caller to treat as extras.
+ */
+export function pickMetadataFiles<T extends { fileName: string }>(
+  files: T[]
+): { kept: T[]; duplicates: T[] } {
+  const byKind = new Map<MetadataKind, T[]>();
+  for (const file of files) {
+    const kind = detectMetadataFile(file.fileName);
+    if (!kind) continue;
+    byKind.set(kind, [...(byKind.get(kind) || []), file]);
+  }
+
+  const kept: T[] = [];
+  const duplicates: T[] = [];
+  for (const [kind, candidates] of byKind) {
+    const stem = (f: T) => f.fileName.toLowerCase().replace(/\.[^.]+$/, "");
+    const sorted = [...candidates].sort((a, b) => a.fileName.localeCompare(b.fileName));
+    const canonical = kind === "nfo" ? NFO_NAMES : new Set([kind]);
+    const winner = sorted.find((f) => canonical.has(stem(f))) ?? sorted[0];
+    kept.push(winner);
+    duplicates.push(...sorted.filter((f) => f !== winner));
+  }
+  return { kept, duplicates };
+}
+
+/**
+ * File name (without extension) a metadata file takes at the destination:
+ * the kind itself for artwork, and the file Jellyfin reads for the group's
+ * media type for an nfo.
+ */
+export function canonicalMetadataName(kind: MetadataKind, mediaType: string): string {
+  if (kind === "nfo") return mediaType === "tv" ? "tvshow" : "movie";
+  return kind;
+}
@@ -1,17 +1,12 @@
 import Database from "better-sqlite3";
 import { drizzle } from "drizzle-orm/better-sqlite3";
 import * as schema from "./schema";
+import { DATA_DIR } from "./paths";
+import { encryptSecret, isEncryptedSecret } from "../secrets";
 import path from "path";
 import fs from "fs";
 
-function getDataDir(): string {
-  if (process.env.REELNAME_DATA_DIR) {
-    return process.env.REELNAME_DATA_DIR;
-  }
-  return path.join(process.cwd(), "data");
-}
-
-export const DATA_DIR = getDataDir();
+export { DATA_DIR };
 const DB_PATH = path.join(DATA_DIR, "reelname.db");
 
 // During the Next.js build phase, parallel workers race to open the same
@@ -35,48 +30,149 @@ sqlite.pragma("foreign_keys = ON");
 
 export const db = drizzle(sqlite, { schema });
 
-function tryExec(sql: string) {
-  try {
-    sqlite.exec(sql);
-  } catch {
-    // Column/table may already exist
-  }
+function tableExists(table: string): boolean {
+  return !!sqlite
+    .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?")
+    .get(table);
+}
+
+function columnInfo(table: string): Array<{ name: string; notnull: number }> {
+  return sqlite.prepare(`PRAGMA table_info(${table})`).all() as Array<{
+    name: string;
+    notnull: number;
+  }>;
+}
+
+/** Add a column unless the table already has it. Any other failure throws. */
+function addColumn(table: string, column: string, definition: string) {
+  if (columnInfo(table).some((c) => c.name === column)) return;
+  sqlite.exec(`ALTER TABLE ${table} ADD COLUMN ${column} ${definition}`);
 }
 
 function migrateMatchCandidatesNullableJobId() {
-  // Check if job_id column has a NOT NULL constraint by trying a null insert
-  try {
-    const info = sqlite.prepare("PRAGMA table_info(match_candidates)").all() as Array<{
-      name: string;
-      notnull: number;
-    }>;
-    const jobIdCol = info.find((c) => c.name === "job_id");
-    if (!jobIdCol || jobIdCol.notnull === 0) return; // Already nullable or doesn't exist
-
-    // Recreate table with nullable job_id
-    sqlite.exec(`
-      ALTER TABLE match_candidates RENAME TO _match_candidates_old;
-      CREATE TABLE match_candidates (
-        id INTEGER PRIMARY KEY AUTOINCREMENT,
-        job_id INTEGER REFERENCES jobs(id) ON DELETE CASCADE,
-        group_id INTEGER REFERENCES groups(id) ON DELETE CASCADE,
-        tmdb_id INTEGER NOT NULL,
-        media_type TEXT NOT NULL,
-        title TEXT NOT NULL,
-        year INTEGER,
-        poster_path TEXT,
-        overview TEXT,
-        confidence REAL NOT NULL
+  // Old databases declared job_id NOT NULL
+  const jobIdCol = columnInfo("match_candidates").find((c) => c.name === "job_id");
+  if (!jobIdCol || jobIdCol.notnull === 0) return; // Already nullable or doesn't exist
+
+  // Recreate table with nullable job_id. Columns are listed because group_id
+  // was appended to the old table and sits in a different position.
+  const columns = "id, job_id, group_id, tmdb_id, media_type, title, year, poster_path, overview, confidence";
+  sqlite.exec(`
+    ALTER TABLE match_candidates RENAME TO _match_candidates_old;
+    CREATE TABLE match_candidates (
+      id INTEGER PRIMARY KEY AUTOINCREMENT,
+      job_id INTEGER REFERENCES jobs(id) ON DELETE CASCADE,
+      group_id INTEGER REFERENCES groups(id) ON DELETE CASCADE,
+      tmdb_id INTEGER NOT NULL,
+      media_type TEXT NOT NULL,
+      title TEXT NOT NULL,
+      year INTEGER,
+      poster_path TEXT,
+      overview TEXT,
+      confidence REAL NOT NULL
+    );
+    INSERT INTO match_candidates (${columns}) SELECT ${columns} FROM _match_candidates_old;
+    DROP TABLE _match_candidates_old;
+  `);
+}
+
+// Versioned migrations, tracked in PRAGMA user_version. Each runs once, in
+// order, inside a transaction that also records its version, so a failure
+// rolls back cleanly and is reported instead of being swallowed. Fresh
+// databases get the full schema from initializeDatabase() and start at the
+// latest version. To change the schema: update schema.ts and the CREATE TABLE
+// in initializeDatabase(), then append a migration here.
+const MIGRATIONS: Array<{ version: number; description: string; up: () => void }> = [
+  {
+    // Everything added before versioning. Databases at version 0 may have any
+    // subset of these, so each step checks before altering.
+    version: 1,
+    description: "baseline columns from unversioned releases",
+    up: () => {
+      addColumn("destinations", "ssh_key_passphrase", "TEXT");
+      addColumn("jobs", "group_id", "INTEGER REFERENCES groups(id) ON DELETE CASCADE");
+      addColumn("jobs", "file_category", "TEXT NOT NULL DEFAULT 'episode'");
+      addColumn("jobs", "extra_type", "TEXT");
+      addColumn("jobs", "parsed_hdr", "TEXT");
+      addColumn("jobs", "media_resolution", "TEXT");
+      addColumn("jobs", "media_duration", "REAL");
+      addColumn("jobs", "media_container", "TEXT");
+      addColumn("jobs", "media_video_codec", "TEXT");
+      addColumn("jobs", "media_audio_codec", "TEXT");
+      addColumn("jobs", "media_probed_at", "TEXT");
+      addColumn("match_candidates", "group_id", "INTEGER REFERENCES groups(id) ON DELETE CASCADE");
+      addColumn("groups", "episode_order", "TEXT NOT NULL DEFAULT 'aired'");
+      addColumn("groups", "missing_at", "TEXT");
+      addColumn("groups", "deleted_at", "TEXT");
+      addColumn("groups", "tmdb_collection", "TEXT");
+      addColumn("jobs", "deleted_at", "TEXT");
+      addColumn("jobs", "destination_override", "TEXT");
+      addColumn("jobs", "parsed_part", "INTEGER");
+      migrateMatchCandidatesNullableJobId();
+    },
+  },
+  {
+    version: 2,
+    description: "encrypt stored SSH key passphrases",
+    up: () => {
+      const rows = sqlite
+        .prepare("SELECT id, ssh_key_passphrase FROM destinations WHERE ssh_key_passphrase IS NOT NULL AND ssh_key_passphrase != ''")
+        .all() as Array<{ id: number; ssh_key_passphrase: string }>;
+      const update = sqlite.prepare("UPDATE destinations SET ssh_key_passphrase = ? WHERE id = ?");
+      for (const row of rows) {
+        if (isEncryptedSecret(row.ssh_key_passphrase)) continue;
+        update.run(encryptSecret(row.ssh_key_passphrase), row.id);
+      }
+    },
+  },
+  {
+    version: 3,
+    description: "add groups.locked",
+    up: () => {
+      addColumn("groups", "locked", "INTEGER NOT NULL DEFAULT 0");
+    },
+  },
+  {
+    version: 4,
+    description: "add groups.match_error",
+    up: () => {
+      addColumn("groups", "match_error", "TEXT");
+    },
+  },
+  {
+    version: 5,
+    description: "add groups.title_edited and jobs.numbers_edited",
+    up: () => {
+      addColumn("groups", "title_edited", "INTEGER NOT NULL DEFAULT 0");
+      addColumn("jobs", "numbers_edited", "INTEGER NOT NULL DEFAULT 0");
+    },
+  },
+];
+
+export const SCHEMA_VERSION = MIGRATIONS[MIGRATIONS.length - 1].version;
+
+function runMigrations() {
+  const current = sqlite.pragma("user_version", { simple: true }) as number;
+  for (const migration of MIGRATIONS) {
+    if (migration.version <= current) continue;
+    try {
+      sqlite.transaction(() => {
+        migration.up();
+        sqlite.pragma(`user_version = ${migration.version}`);
+      })();
+    } catch (err) {
+      const message = err instanceof Error ? err.message : String(err);
+      throw new Error(
+        `Database migration ${migration.version} (${migration.description}) failed: ${message}`
       );
-      INSERT INTO match_candidates SELECT * FROM _match_candidates_old;
-      DROP TABLE _match_candidates_old;
-    `);
-  } catch {
-    // Table might not exist yet, that's fine
+    }
+    console.log(`[db] Migrated to schema version ${migration.version}: ${migration.description}`);
   }
 }
 
 function initializeDatabase() {
+  const fresh = !tableExists("groups");
+
   sqlite.exec(`
     CREATE TABLE IF NOT EXISTS groups (
       id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -93,7 +189,14 @@ function initializeDatabase() {
       tmdb_year INTEGER,
       tmdb_poster_path TEXT,
       match_confidence REAL,
+      tmdb_collection TEXT,
+      episode_order TEXT NOT NULL DEFAULT 'aired',
       destination_id INTEGER REFERENCES destinations(id),
+      missing_at TEXT,
+      locked INTEGER NOT NULL DEFAULT 0,
+      match_error TEXT,
+      title_edited INTEGER NOT NULL DEFAULT 0,
+      deleted_at TEXT,
       created_at TEXT NOT NULL,
       updated_at TEXT NOT NULL
     );
@@ -115,6 +218,15 @@ function initializeDatabase() {
       parsed_episode INTEGER,
       parsed_quality TEXT,
       parsed_codec TEXT,
+      parsed_hdr TEXT,
+      parsed_part INTEGER,
+      numbers_edited INTEGER NOT NULL DEFAULT 0,
+      media_resolution TEXT,
+      media_duration REAL,
+      media_container TEXT,
+      media_video_codec TEXT,
+      media_audio_codec TEXT,
+      media_probed_at TEXT,
       tmdb_id INTEGER,
       tmdb_title TEXT,
       tmdb_year INTEGER,
@@ -123,8 +235,10 @@ function initializeDatabase() {
       match_confidence REAL,
       destination_id INTEGER REFERENCES destinations(id),
       destination_path TEXT,
+      destination_override TEXT,
       transfer_progress REAL,
       transfer_error TEXT,
+      deleted_at TEXT,
       created_at TEXT NOT NULL,
       updated_at TEXT NOT NULL
     );
@@ -142,6 +256,27 @@ function initializeDatabase() {
       confidence REAL NOT NULL
     );
 
+    CREATE TABLE IF NOT EXISTS job_events (
+      id INTEGER PRIMARY KEY AUTOINCREMENT,
+      job_id INTEGER NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+      status TEXT NOT NULL,
+      message TEXT,
+      created_at TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS labels (
+      id INTEGER PRIMARY KEY AUTOINCREMENT,
+      name TEXT NOT NULL UNIQUE,
+      color TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS group_labels (
+      id INTEGER PRIMARY KEY AUTOINCREMENT,
+      group_id INTEGER NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+      label_id INTEGER NOT NULL REFERENCES labels(id) ON DELETE CASCADE,
+      UNIQUE (group_id, label_id)
+    );
+
     CREATE TABLE IF NOT EXISTS destinations (
       id INTEGER PRIMARY KEY AUTOINCREMENT,
       name TEXT NOT NULL,
@@ -161,18 +296,11 @@ function initializeDatabase() {
     );
   `);
 
-  // Migration: add ssh_key_passphrase to destinations
-  tryExec("ALTER TABLE destinations ADD COLUMN ssh_key_passphrase TEXT");
-
-  // Migration: add new columns to existing tables
-  tryExec("ALTER TABLE jobs ADD COLUMN group_id INTEGER REFERENCES groups(id) ON DELETE CASCADE");
-  tryExec("ALTER TABLE jobs ADD COLUMN file_category TEXT NOT NULL DEFAULT 'episode'");
-  tryExec("ALTER TABLE jobs ADD COLUMN extra_type TEXT");
-  tryExec("ALTER TABLE match_candidates ADD COLUMN group_id INTEGER REFERENCES groups(id) ON DELETE CASCADE");
-
-  // Migration: recreate match_candidates with nullable job_id
-  // (old table had implicit NOT NULL on job_id)
-  migrateMatchCandidatesNullableJobId();
+  if (fresh) {
+    sqlite.pragma(`user_version = ${SCHEMA_VERSION}`);
+  } else {
+    runMigrations();
+  }
 
   // Insert default settings if not present
   const insertSetting = sqlite.prepare(
@@ -181,9 +309,168 @@ function initializeDatabase() {
   insertSetting.run("scan_path", "");
   insertSetting.run("tmdb_api_key", "");
   insertSetting.run("auto_match_threshold", "0.85");
+  // Empty = never auto-confirm
+  insertSetting.run("auto_confirm_above", "");
   insertSetting.run("naming_preset", "jellyfin");
   insertSetting.run("specials_folder_name", "Specials");
   insertSetting.run("extras_folder_name", "Extras");
+  insertSetting.run("desktop_notifications", "false");
+  insertSetting.run("toast_max_visible", "5");
+  insertSetting.run("page_size", "50");
+  insertSetting.run("view_state", "");
+  insertSetting.run("metadata_provider", "tmdb");
+  insertSetting.run("tvdb_api_key", "");
+  insertSetting.run("tmdb_language", "en-US");
+  insertSetting.run("tmdb_region", "");
+  insertSetting.run("poster_cache_max_mb", "200");
+  insertSetting.run("poster_size", "w185");
+  insertSetting.run("probe_media", "false");
+  insertSetting.run("use_collections", "false");
+  insertSetting.run("season_pad_width", "");
+  insertSetting.run("episode_pad_width", "");
+  insertSetting.run("last_scan_at", "");
+  insertSetting.run("selected_destination_id", "");
+  insertSetting.run("resume_transfers", "true");
+  insertSetting.run("group_by_parsed_title", "false");
+  insertSetting.run("require_year_for_movies", "false");
+  insertSetting.run("scan_max_depth", "");
+  insertSetting.run("scan_follow_symlinks", "false");
+  insertSetting.run("scan_min_episode_files", "");
+  insertSetting.run("scan_min_unnumbered_episodes", "");
+  insertSetting.run("post_transfer_command", "");
+  insertSetting.run("post_transfer_webhook", "");
+  insertSetting.run("ui_scale", "1");
 }
 
 initializeDatabase();
+
+// Maintenance helpers, run from /api/maintenance
+
+function databaseBytes(): number {
+  return ["", "-wal"].reduce((sum, suffix) => {
+    try {
+      return sum + fs.statSync(DB_PATH + suffix).size;
+    } catch {
+      return sum;
+    }
+  }, 0);
+}
+
+/** Fold the WAL back into the main file and truncate it. */
+export function checkpointDatabase() {
+  sqlite.pragma("wal_checkpoint(TRUNCATE)");
+}
+
+/** Rebuild the database file to reclaim space left by deleted rows. */
+export function vacuumDatabase(): { bytesBefore: number; bytesAfter: number } {
+  const bytesBefore = databaseBytes();
+  sqlite.exec("VACUUM");
+  checkpointDatabase();
+  return { bytesBefore, bytesAfter: databaseBytes() };
+}
+
+/** Run SQLite's integrity check; `problems` is empty when the database is healthy. */
+export function checkDatabaseIntegrity(): { ok: boolean; problems: string[] } {
+  const rows = sqlite.pragma("integrity_check") as Array<{ integrity_check: string }>;
+  const problems = rows.map((r) => r.integrity_check).filter((r) => r !== "ok");
+  return { ok: problems.length === 0, problems };
+}
+
+/** Write a consistent copy of the live database to `destPath` (SQLite online backup). */
+export async function backupDatabase(destPath: string): Promise<void> {
+  await sqlite.backup(destPath);
+}
+
+/**
+ * Replace the live database with the backup at `backupPath`. The backup is
+ * validated first (a ReelName database no newer than this build), the current
+ * database is saved alongside as reelname.db.before-restore, and the restored
+ * data is then migrated forward like any existing database.
+ */
+export async function restoreDatabase(backupPath: string): Promise<void> {
+  let version: number;
+  let source: Database.Database;
+  try {
+    source = new Database(backupPath, { readonly: true, fileMustExist: true });
+  } catch {
+    throw new Error("Not a SQLite database");
+  }
+
+  try {
+    const quickCheck = source.pragma("quick_check", { simple: true });
+    if (quickCheck !== "ok") throw new Error(`Backup is damaged: ${quickCheck}`);
+    const tables = new Set(
+      (source.prepare("SELECT name FROM sqlite_master WHERE type = 'table'").all() as Array<{
+        name: string;
+      }>).map((t) => t.name)
+    );
+    for (const required of ["groups", "jobs", "settings", "destinations"]) {
+      if (!tables.has(required)) throw new Error(`Not a ReelName backup (no ${required} table)`);
+    }
+    version = source.pragma("user_version", { simple: true }) as number;
+    if (version > SCHEMA_VERSION) {
+      throw new Error(
+        `Backup is from a newer ReelName (schema ${version}, this build supports ${SCHEMA_VERSION})`
+      );
+    }
+
+  } finally {
+    source.close();
+  }
+
+  await sqlite.backup(DB_PATH + ".before-restore");
+  replaceContents(backupPath, version);
+  initializeDatabase();
+}
+
+/**
+ * Swap every table for the attached backup's, in one transaction on the open
+ * connection. Writing the file underneath it instead would leave the WAL and
+ * this connection's page cache describing the old database.
+ */
+function replaceContents(backupPath: string, version: number) {
+  const objectsIn = (schemaName: string) =>
+    sqlite
+      .prepare(
+        `SELECT type, name, sql FROM ${schemaName}.sqlite_master
+         WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%'
+         ORDER BY type = 'table' DESC`
+      )
+      .all() as Array<{ type: string; name: string; sql: string }>;
+
+  sqlite.prepare("ATTACH DATABASE ? AS restore_source").run(backupPath);
+  // Tables are dropped and refilled in whatever order, so references between
+  // them can't be checked until the swap is complete
+  sqlite.pragma("foreign_keys = OFF");
+  try {
+    sqlite.transaction(() => {
+      for (const obj of objectsIn("main")) {
+        if (obj.type === "table" || obj.type === "view") {
+          sqlite.exec(`DROP ${obj.type.toUpperCase()} IF EXISTS main."${obj.name}"`);
+        }
+      }
+      // Tables first, then their indexes, triggers and views
+      for (const obj of objectsIn("restore_source")) {
+        sqlite.exec(obj.sql);
+        if (obj.type === "table") {
+          sqlite.exec(`INSERT INTO main."${obj.name}" SELECT * FROM restore_source."${obj.name}"`);
+        }
+      }
+      sqlite.pragma(`user_version = ${version}`);
+    })();
+  } finally {
+    sqlite.pragma("foreign_keys = ON");
+    sqlite.exec("DETACH DATABASE restore_source");
+  }
+}
+
+// Leave a clean, WAL-free file behind on shutdown
+if (!isBuildPhase) {
+  process.once("exit", () => {
+    try {
+      checkpointDatabase();
+    } catch {
+      // Connection may already be closed
+    }
+  });
+}
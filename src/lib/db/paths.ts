@@ -0,0 +1,10 @@
+import path from "path";
+
+function getDataDir(): string {
+  if (process.env.REELNAME_DATA_DIR) {
+    return process.env.REELNAME_DATA_DIR;
+  }
+  return path.join(process.cwd(), "data");
+}
+
+export const DATA_DIR = getDataDir();
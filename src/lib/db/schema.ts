@@ -7,6 +7,7 @@ export const groups = sqliteTable("groups", {
       "scanned",
       "matched",
       "ambiguous",
+      "unmatched",
       "confirmed",
       "queued",
       "transferring",
@@ -37,10 +38,30 @@ export const groups = sqliteTable("groups", {
   tmdbYear: integer("tmdb_year"),
   tmdbPosterPath: text("tmdb_poster_path"),
   matchConfidence: real("match_confidence"),
+  // TMDB belongs_to_collection name, for movies in a franchise
+  tmdbCollection: text("tmdb_collection"),
+  episodeOrder: text("episode_order", { enum: ["aired", "dvd", "absolute"] })
+    .notNull()
+    .default("aired"),
 
   // Transfer info
   destinationId: integer("destination_id").references(() => destinations.id),
 
+  // Set when a rescan no longer finds the folder on disk
+  missingAt: text("missing_at"),
+
+  // Locked groups keep their match through bulk rematches and rescans
+  locked: integer("locked", { mode: "boolean" }).notNull().default(false),
+
+  // Why the last match attempt couldn't reach TMDB; cleared when one succeeds
+  matchError: text("match_error"),
+
+  // Set when the title was edited by hand, so a re-parse leaves it alone
+  titleEdited: integer("title_edited", { mode: "boolean" }).notNull().default(false),
+
+  // Soft delete: set when moved to the trash
+  deletedAt: text("deleted_at"),
+
   createdAt: text("created_at")
     .notNull()
     .$defaultFn(() => new Date().toISOString()),
@@ -59,6 +80,7 @@ export const jobs = sqliteTable("jobs", {
       "scanned",
       "matched",
       "ambiguous",
+      "unmatched",
       "confirmed",
       "queued",
       "transferring",
@@ -73,7 +95,7 @@ export const jobs = sqliteTable("jobs", {
     .notNull()
     .default("unknown"),
   fileCategory: text("file_category", {
-    enum: ["episode", "movie", "special", "extra"],
+    enum: ["episode", "movie", "special", "extra", "metadata"],
   })
     .notNull()
     .default("episode"),
@@ -103,6 +125,18 @@ export const jobs = sqliteTable("jobs", {
   parsedEpisode: integer("parsed_episode"),
   parsedQuality: text("parsed_quality"),
   parsedCodec: text("parsed_codec"),
+  parsedHdr: text("parsed_hdr"),
+  parsedPart: integer("parsed_part"),
+  // Set when season/episode were fixed by hand or resolved, so a re-parse keeps them
+  numbersEdited: integer("numbers_edited", { mode: "boolean" }).notNull().default(false),
+
+  // Probed media info (ffprobe, opt-in)
+  mediaResolution: text("media_resolution"),
+  mediaDuration: real("media_duration"),
+  mediaContainer: text("media_container"),
+  mediaVideoCodec: text("media_video_codec"),
+  mediaAudioCodec: text("media_audio_codec"),
+  mediaProbedAt: text("media_probed_at"),
 
   // TMDB info (episode-level)
   tmdbId: integer("tmdb_id"),
@@ -115,9 +149,14 @@ export const jobs = sqliteTable("jobs", {
   // Transfer info
   destinationId: integer("destination_id").references(() => destinations.id),
   destinationPath: text("destination_path"),
+  // Manual relative path that replaces the naming template for this job
+  destinationOverride: text("destination_override"),
   transferProgress: real("transfer_progress"),
   transferError: text("transfer_error"),
 
+  // Soft delete: set when moved to the trash
+  deletedAt: text("deleted_at"),
+
   createdAt: text("created_at")
     .notNull()
     .$defaultFn(() => new Date().toISOString()),
@@ -141,6 +180,34 @@ export const matchCandidates = sqliteTable("match_candidates", {
   confidence: real("confidence").notNull(),
 });
 
+// Timestamped transfer status changes and errors, kept across retries
+export const jobEvents = sqliteTable("job_events", {
+  id: integer("id").primaryKey({ autoIncrement: true }),
+  jobId: integer("job_id")
+    .notNull()
+    .references(() => jobs.id, { onDelete: "cascade" }),
+  status: text("status").notNull(),
+  message: text("message"),
+  createdAt: text("created_at").notNull(),
+});
+
+export const labels = sqliteTable("labels", {
+  id: integer("id").primaryKey({ autoIncrement: true }),
+  name: text("name").notNull().unique(),
+  color: text("color").notNull(),
+});
+
+// Many-to-many between groups and labels; rows go with either side
+export const groupLabels = sqliteTable("group_labels", {
+  id: integer("id").primaryKey({ autoIncrement: true }),
+  groupId: integer("group_id")
+    .notNull()
+    .references(() => groups.id, { onDelete: "cascade" }),
+  labelId: integer("label_id")
+    .notNull()
+    .references(() => labels.id, { onDelete: "cascade" }),
+});
+
 export const destinations = sqliteTable("destinations", {
   id: integer("id").primaryKey({ autoIncrement: true }),
   name: text("name").notNull(),
@@ -173,5 +240,7 @@ export type Job = typeof jobs.$inferSelect;
 export type NewJob = typeof jobs.$inferInsert;
 export type MatchCandidate = typeof matchCandidates.$inferSelect;
 export type NewMatchCandidate = typeof matchCandidates.$inferInsert;
+export type JobEvent = typeof jobEvents.$inferSelect;
+export type Label = typeof labels.$inferSelect;
 export type Destination = typeof destinations.$inferSelect;
 export type Setting = typeof settings.$inferSelect;
@@ -0,0 +1,34 @@
+// Destination field checks shared by the add-destination form and the API
+
+export interface DestinationFields {
+  name?: string | null;
+  type?: string | null;
+  basePath?: string | null;
+  sshHost?: string | null;
+  sshPort?: string | number | null;
+  sshUser?: string | null;
+  sshKeyPath?: string | null;
+}
+
+/** Parse an SSH port, or null unless it is a whole number from 1 to 65535. */
+export function parsePort(value: string | number | null | undefined): number | null {
+  const text = String(value ?? "").trim();
+  if (!/^\d+$/.test(text)) return null;
+  const port = Number(text);
+  return port >= 1 && port <= 65535 ? port : null;
+}
+
+/** Problems with a destination's fields, keyed by field name. Empty when valid. */
+export function validateDestinationFields(fields: DestinationFields): Record<string, string> {
+  const errors: Record<string, string> = {};
+  if (!fields.name?.trim()) errors.name = "Name is required";
+  if (!fields.basePath?.trim()) errors.basePath = "Base path is required";
+
+  if (fields.type === "ssh") {
+    if (!fields.sshHost?.trim()) errors.sshHost = "Host is required";
+    if (!fields.sshUser?.trim()) errors.sshUser = "Username is required";
+    if (!fields.sshKeyPath?.trim()) errors.sshKeyPath = "SSH key path is required";
+    if (parsePort(fields.sshPort) === null) errors.sshPort = "Port must be a number from 1 to 65535";
+  }
+  return errors;
+}
@@ -0,0 +1,169 @@
+import { db } from "@/lib/db";
+import { groups, jobs } from "@/lib/db/schema";
+import { and, eq, isNotNull, isNull } from "drizzle-orm";
+import { trashGroups } from "@/lib/trash";
+import { copyLabels } from "@/lib/regroup";
+import type { Group, Job } from "@/lib/db/schema";
+
+// Moving a source folder and rescanning leaves the old group flagged Missing
+// next to a fresh group for the same content. Merging copies the work done on
+// the old group (match, episode fixes, labels, lock) onto the live one and
+// moves the old group to the trash.
+//
+// This isn't mergeGroups() from regroup.ts: that moves the sources' files into
+// the target under the target's match. Here the work flows the other way: the
+// stale group's files no longer exist, so nothing moves; its match and per-file
+// fixes are copied onto the live group's files, paired by name.
+
+export interface DuplicatePair {
+  stale: Group;
+  live: Group;
+  reason: "folder" | "tmdb";
+}
+
+export interface MergeResult {
+  staleId: number;
+  liveId: number;
+  title: string;
+  filesCarried: number;
+}
+
+// Statuses worth keeping; anything else becomes Matched once the match is copied
+const CARRIED_STATUSES = ["matched", "confirmed", "completed", "skipped"] as const;
+type CarriedStatus = (typeof CARRIED_STATUSES)[number];
+
+function carriedStatus(status: string): CarriedStatus {
+  return (CARRIED_STATUSES as readonly string[]).includes(status)
+    ? (status as CarriedStatus)
+    : "matched";
+}
+
+/**
+ * Missing groups paired with the live group that replaced them: same folder
+ * name first (unless both are matched to different titles), otherwise the
+ * same TMDB match. Each live group takes at most one stale group.
+ */
+export function findDuplicateGroups(): DuplicatePair[] {
+  const stale = db
+    .select()
+    .from(groups)
+    .where(and(isNotNull(groups.missingAt), isNull(groups.deletedAt)))
+    .all();
+  if (stale.length === 0) return [];
+
+  const live = db
+    .select()
+    .from(groups)
+    .where(and(isNull(groups.missingAt), isNull(groups.deletedAt)))
+    .all();
+
+  const taken = new Set<number>();
+  const pairs: DuplicatePair[] = [];
+  for (const old of stale) {
+    // Newest first, so a folder moved twice pairs with where it is now
+    const candidates = live
+      .filter((g) => !taken.has(g.id) && g.createdAt >= old.createdAt)
+      .sort((a, b) => b.createdAt.localeCompare(a.createdAt));
+    // A same-named folder matched to something else is a different title
+    // ("Season 1" of another show), not this one moved
+    const byFolder = candidates.find(
+      (g) =>
+        g.folderName === old.folderName &&
+        (!old.tmdbId || !g.tmdbId || (g.tmdbId === old.tmdbId && g.mediaType === old.mediaType))
+    );
+    const byTmdb = old.tmdbId
+      ? candidates.find((g) => g.tmdbId === old.tmdbId && g.mediaType === old.mediaType)
+      : undefined;
+    const match = byFolder || byTmdb;
+    if (!match) continue;
+    taken.add(match.id);
+    pairs.push({ stale: old, live: match, reason: byFolder ? "folder" : "tmdb" });
+  }
+  return pairs;
+}
+
+function jobKey(job: Job): string {
+  return job.fileName.toLowerCase();
+}
+
+/** Copy a stale group's work onto its live twin and trash the stale group. */
+function mergePair(stale: Group, live: Group): MergeResult {
+  const now = new Date().toISOString();
+
+  const groupUpdates: Partial<Group> = {
+    parsedTitle: stale.parsedTitle,
+    parsedYear: stale.parsedYear,
+    titleEdited: stale.titleEdited,
+    episodeOrder: stale.episodeOrder,
+    destinationId: stale.destinationId ?? live.destinationId,
+    locked: stale.locked || live.locked,
+    updatedAt: now,
+  };
+  if (stale.tmdbId) {
+    Object.assign(groupUpdates, {
+      status: carriedStatus(stale.status),
+      mediaType: stale.mediaType,
+      tmdbId: stale.tmdbId,
+      tmdbTitle: stale.tmdbTitle,
+      tmdbYear: stale.tmdbYear,
+      tmdbPosterPath: stale.tmdbPosterPath,
+      tmdbCollection: stale.tmdbCollection,
+      matchConfidence: stale.matchConfidence,
+    });
+  }
+  db.update(groups).set(groupUpdates).where(eq(groups.id, live.id)).run();
+
+  // Files are paired by name; the folder moved, the file names didn't
+  const staleJobs = new Map(
+    db.select().from(jobs).where(and(eq(jobs.groupId, stale.id), isNull(jobs.deletedAt))).all()
+      .map((j) => [jobKey(j), j])
+  );
+  const liveJobs = db
+    .select()
+    .from(jobs)
+    .where(and(eq(jobs.groupId, live.id), isNull(jobs.deletedAt)))
+    .all();
+  let filesCarried = 0;
+  for (const job of liveJobs) {
+    const old = staleJobs.get(jobKey(job));
+    if (!old) continue;
+    db.update(jobs)
+      .set({
+        status: stale.tmdbId ? carriedStatus(old.status) : job.status,
+        fileCategory: old.fileCategory,
+        extraType: old.extraType,
+        parsedSeason: old.parsedSeason,
+        parsedEpisode: old.parsedEpisode,
+        numbersEdited: old.numbersEdited,
+        tmdbId: old.tmdbId,
+        tmdbTitle: old.tmdbTitle,
+        tmdbYear: old.tmdbYear,
+        tmdbPosterPath: old.tmdbPosterPath,
+        tmdbEpisodeTitle: old.tmdbEpisodeTitle,
+        matchConfidence: old.matchConfidence,
+        destinationOverride: old.destinationOverride,
+        updatedAt: now,
+      })
+      .where(eq(jobs.id, job.id))
+      .run();
+    filesCarried++;
+  }
+
+  copyLabels(stale.id, live.id);
+  trashGroups([stale.id]);
+  return {
+    staleId: stale.id,
+    liveId: live.id,
+    title: stale.tmdbTitle || stale.parsedTitle || stale.folderName,
+    filesCarried,
+  };
+}
+
+/**
+ * Merge the detected duplicates, or only those whose stale group id is given.
+ * Pairs are re-detected here so a stale request can't merge the wrong groups.
+ */
+export function mergeDuplicateGroups(staleIds?: number[]): MergeResult[] {
+  const pairs = findDuplicateGroups().filter((p) => !staleIds || staleIds.includes(p.stale.id));
+  return pairs.map(({ stale, live }) => mergePair(stale, live));
+}
@@ -0,0 +1,30 @@
+import { execSync } from "child_process";
+import { describe, expect, it } from "vitest";
+import { quoteShellArg, renderHookCommand } from "./hooks";
+import type { TransferBatchSummary } from "./hooks";
+
+function summary(...names: string[]): TransferBatchSummary {
+  return {
+    completed: 3,
+    failed: 0,
+    destinations: names.map((name, i) => ({ id: i + 1, name })),
+    finishedAt: "2026-01-01T00:00:00.000Z",
+  };
+}
+
+describe("renderHookCommand", () => {
+  it("quotes each placeholder as one argument", () => {
+    expect(renderHookCommand("notify {count} {destination}", summary("NAS", "Backup"), "linux")).toBe(
+      "notify '3' 'NAS, Backup'"
+    );
+  });
+
+  it("keeps a hostile destination name inside its argument", () => {
+    const command = renderHookCommand("printf %s {destination}", summary("x'; echo pwned; '$(id)"), "linux");
+    expect(execSync(command, { shell: "/bin/sh" }).toString()).toBe("x'; echo pwned; '$(id)");
+  });
+
+  it("drops cmd.exe metacharacters on Windows", () => {
+    expect(quoteShellArg('NAS" & del %TEMP%', "win32")).toBe('"NAS  del TEMP"');
+  });
+});
@@ -0,0 +1,129 @@
+import { exec } from "child_process";
+import { db } from "@/lib/db";
+import { settings } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+import { createLogger } from "@/lib/log";
+
+// Post-transfer hooks let ReelName kick off the next step of an automation
+// chain (e.g. a Jellyfin library scan) once a transfer batch finishes. A hook
+// failing is reported to the UI but never affects the transfers themselves.
+
+const HOOK_TIMEOUT_MS = 60_000;
+const log = createLogger("hooks");
+
+export interface TransferBatchSummary {
+  completed: number;
+  failed: number;
+  destinations: Array<{ id: number; name: string }>;
+  finishedAt: string;
+}
+
+export interface PostTransferHookState {
+  running: boolean;
+  /** Why the last hook run failed, cleared when the next batch starts */
+  error: string | null;
+}
+
+let hookState: PostTransferHookState = { running: false, error: null };
+
+export function getPostTransferHookState(): PostTransferHookState {
+  return hookState;
+}
+
+export function resetPostTransferHookState() {
+  if (!hookState.running) hookState = { running: false, error: null };
+}
+
+function getSetting(key: string): string {
+  return db.select().from(settings).where(eq(settings.key, key)).get()?.value?.trim() || "";
+}
+
+function hookValues(summary: TransferBatchSummary) {
+  return {
+    count: String(summary.completed),
+    destination: summary.destinations.map((d) => d.name).join(", "),
+  };
+}
+
+/**
+ * Quote a value as one shell word. Destination names are user input, so they
+ * must not be able to end the word and run something else. cmd.exe has no
+ * quoting that survives every character, so its metacharacters are dropped.
+ */
+export function quoteShellArg(value: string, platform: NodeJS.Platform = process.platform): string {
+  if (platform === "win32") return `"${value.replace(/["%^&|<>!]/g, "")}"`;
+  return `'${value.replace(/'/g, "'\\''")}'`;
+}
+
+/**
+ * Fill `{count}` and `{destination}` in a command template, each quoted as a
+ * single shell argument.
+ */
+export function renderHookCommand(
+  template: string,
+  summary: TransferBatchSummary,
+  platform: NodeJS.Platform = process.platform
+): string {
+  const values = hookValues(summary);
+  return template
+    .replace(/\{count\}/g, () => quoteShellArg(values.count, platform))
+    .replace(/\{destination\}/g, () => quoteShellArg(values.destination, platform));
+}
+
+function runCommand(command: string, summary: TransferBatchSummary): Promise<void> {
+  const values = hookValues(summary);
+  // The same values as env vars, for scripts that would rather not parse args
+  const env = { ...process.env, REELNAME_COUNT: values.count, REELNAME_DESTINATION: values.destination };
+  return new Promise((resolve, reject) => {
+    exec(command, { timeout: HOOK_TIMEOUT_MS, env }, (err, _stdout, stderr) => {
+      if (!err) return resolve();
+      const detail = stderr.trim().split("\n").pop() || err.message;
+      reject(new Error(`Post-transfer command failed: ${detail}`));
+    });
+  });
+}
+
+async function postWebhook(url: string, summary: TransferBatchSummary): Promise<void> {
+  let res: Response;
+  try {
+    res = await fetch(url, {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({ event: "transfer_complete", count: summary.completed, ...summary }),
+      signal: AbortSignal.timeout(HOOK_TIMEOUT_MS),
+    });
+  } catch (err) {
+    const message = err instanceof Error ? err.message : String(err);
+    throw new Error(`Post-transfer webhook failed: ${message}`);
+  }
+  if (!res.ok) {
+    throw new Error(`Post-transfer webhook failed: ${url} returned ${res.status}`);
+  }
+}
+
+/**
+ * Run the configured post_transfer_command and post_transfer_webhook for a
+ * finished batch. Batches where nothing completed don't fire the hooks.
+ */
+export async function runPostTransferHooks(summary: TransferBatchSummary): Promise<void> {
+  const command = getSetting("post_transfer_command");
+  const webhook = getSetting("post_transfer_webhook");
+  if ((!command && !webhook) || summary.completed === 0) return;
+
+  hookState = { running: true, error: null };
+  const errors: string[] = [];
+  if (command) {
+    await runCommand(renderHookCommand(command, summary), summary).catch((err: Error) => {
+      errors.push(err.message);
+    });
+  }
+  if (webhook) {
+    await postWebhook(webhook, summary).catch((err: Error) => {
+      errors.push(err.message);
+    });
+  }
+
+  for (const error of errors) log.warn(error);
+  if (errors.length === 0) log.info("Post-transfer hooks ran", { completed: summary.completed });
+  hookState = { running: false, error: errors.length > 0 ? errors.join("; ") : null };
+}
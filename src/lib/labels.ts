@@ -0,0 +1,58 @@
+import { db } from "@/lib/db";
+import { groupLabels, labels } from "@/lib/db/schema";
+import { asc, eq, inArray } from "drizzle-orm";
+import type { Label } from "@/lib/db/schema";
+
+// New labels take the next color in turn
+export const LABEL_COLORS = [
+  "#3b82f6",
+  "#22c55e",
+  "#f59e0b",
+  "#ef4444",
+  "#8b5cf6",
+  "#06b6d4",
+  "#ec4899",
+  "#6b7280",
+];
+
+export function listLabels(): Label[] {
+  return db.select().from(labels).orderBy(asc(labels.name)).all();
+}
+
+export function createLabel(name: string, color?: string): Label {
+  const count = db.select().from(labels).all().length;
+  return db
+    .insert(labels)
+    .values({ name, color: color || LABEL_COLORS[count % LABEL_COLORS.length] })
+    .returning()
+    .get();
+}
+
+/** Labels for each of the given groups, keyed by group id. */
+export function getLabelsForGroups(groupIds: number[]): Map<number, Label[]> {
+  const byGroup = new Map<number, Label[]>();
+  if (groupIds.length === 0) return byGroup;
+
+  const rows = db
+    .select({ groupId: groupLabels.groupId, label: labels })
+    .from(groupLabels)
+    .innerJoin(labels, eq(groupLabels.labelId, labels.id))
+    .where(inArray(groupLabels.groupId, groupIds))
+    .orderBy(asc(labels.name))
+    .all();
+
+  for (const { groupId, label } of rows) {
+    const list = byGroup.get(groupId) || [];
+    list.push(label);
+    byGroup.set(groupId, list);
+  }
+  return byGroup;
+}
+
+/** Replace a group's labels with exactly the given set. */
+export function setGroupLabels(groupId: number, labelIds: number[]) {
+  db.delete(groupLabels).where(eq(groupLabels.groupId, groupId)).run();
+  for (const labelId of new Set(labelIds)) {
+    db.insert(groupLabels).values({ groupId, labelId }).run();
+  }
+}
@@ -0,0 +1,71 @@
+import { AsyncLocalStorage } from "async_hooks";
+import { redactSecrets } from "./secrets";
+
+// Server-side logging. Lines are human-readable ("[transfer] ...") by default;
+// LOG_FORMAT=json writes one JSON object per line for log shippers such as
+// Loki. LOG_LEVEL (debug, info, warn, error) sets the minimum level, default
+// info. Spans tag every line logged inside a scan, match pass, or transfer
+// batch with its id so the lines of one run can be pulled out together.
+
+export type LogLevel = "debug" | "info" | "warn" | "error";
+type Fields = Record<string, unknown>;
+
+const LEVELS: Record<LogLevel, number> = { debug: 10, info: 20, warn: 30, error: 40 };
+
+const jsonFormat = process.env.LOG_FORMAT?.toLowerCase() === "json";
+const minLevel = LEVELS[process.env.LOG_LEVEL?.toLowerCase() as LogLevel] ?? LEVELS.info;
+
+const spans = new AsyncLocalStorage<Fields>();
+let nextSpanId = 1;
+
+/**
+ * Run `fn` inside a span. Lines logged from it, including from async work it
+ * starts, carry `<name>Id` plus the given fields; nested spans keep the
+ * parent's id, so a match pass started by a scan has both scanId and matchId.
+ */
+export function withSpan<T>(name: string, fields: Fields, fn: () => T): T {
+  const parent = spans.getStore();
+  return spans.run({ ...parent, [`${name}Id`]: nextSpanId++, ...fields }, fn);
+}
+
+function serializeError(err: unknown): Fields {
+  if (err instanceof Error) return { error: err.message, stack: err.stack };
+  return { error: String(err) };
+}
+
+function write(level: LogLevel, scope: string, message: string, fields: Fields = {}) {
+  if (LEVELS[level] < minLevel) return;
+  const { err, ...rest } = fields;
+  const context: Fields = { ...spans.getStore(), ...rest, ...(err !== undefined ? serializeError(err) : {}) };
+  const out = level === "error" ? console.error : level === "warn" ? console.warn : console.log;
+
+  // Every line is masked, so a secret inside an error or field never reaches the log
+  if (jsonFormat) {
+    out(redactSecrets(JSON.stringify({ time: new Date().toISOString(), level, scope, msg: message, ...context })));
+    return;
+  }
+
+  const extra = Object.entries(context)
+    .filter(([key, value]) => key !== "stack" && value !== undefined)
+    .map(([key, value]) => `${key}=${typeof value === "string" ? value : JSON.stringify(value)}`)
+    .join(" ");
+  out(redactSecrets(`[${scope}] ${message}${extra ? ` (${extra})` : ""}`));
+  if (err instanceof Error && err.stack && LEVELS[level] >= LEVELS.error) out(redactSecrets(err.stack));
+}
+
+export interface Logger {
+  debug(message: string, fields?: Fields): void;
+  info(message: string, fields?: Fields): void;
+  warn(message: string, fields?: Fields): void;
+  error(message: string, fields?: Fields): void;
+}
+
+/** Logger whose lines are prefixed with (or, in JSON, tagged by) `scope`. Pass errors as `err`. */
+export function createLogger(scope: string): Logger {
+  return {
+    debug: (message, fields) => write("debug", scope, message, fields),
+    info: (message, fields) => write("info", scope, message, fields),
+    warn: (message, fields) => write("warn", scope, message, fields),
+    error: (message, fields) => write("error", scope, message, fields),
+  };
+}
@@ -0,0 +1,31 @@
+import { vacuumDatabase, checkDatabaseIntegrity, checkpointDatabase } from "@/lib/db";
+import { isTransferActive } from "@/lib/transfer";
+import { getMatchProgress } from "@/lib/matcher";
+
+// Database maintenance from the settings modal. VACUUM rewrites the whole file
+// and blocks every other query while it runs, so it (and the checkpoint) is
+// refused while a transfer or match pass is in flight. Scans do their
+// filesystem walk and inserts synchronously, so they can't interleave; the
+// match pass a scan starts afterwards is covered by the match check.
+
+export type MaintenanceAction = "vacuum" | "integrity";
+
+export type MaintenanceResult =
+  | { action: "vacuum"; bytesBefore: number; bytesAfter: number }
+  | { action: "integrity"; ok: boolean; problems: string[] };
+
+/** Why maintenance can't run right now, or null if it can. */
+export function maintenanceBlocker(): string | null {
+  if (isTransferActive()) return "Transfers are running. Try again when they finish.";
+  if (getMatchProgress().running) return "Matching is running. Try again when it finishes.";
+  return null;
+}
+
+export function runMaintenance(action: MaintenanceAction): MaintenanceResult {
+  if (action === "vacuum") {
+    return { action, ...vacuumDatabase() };
+  }
+  // Integrity check is read-only; checkpoint first so it sees one file
+  checkpointDatabase();
+  return { action, ...checkDatabaseIntegrity() };
+}
@@ -0,0 +1,81 @@
+import { describe, expect, it } from "vitest";
+import { compareCandidates, missingRequiredYear, shouldAutoMatch } from "./matcher";
+import type { TmdbSearchResult } from "./tmdb";
+
+function result(overrides: Partial<TmdbSearchResult> = {}): TmdbSearchResult {
+  return {
+    id: 1,
+    title: "Movie",
+    poster_path: null,
+    overview: "",
+    popularity: 10,
+    media_type: "movie",
+    vote_average: 7,
+    ...overrides,
+  };
+}
+
+describe("missingRequiredYear", () => {
+  const yearless = { mediaType: "movie" as const, parsedYear: null };
+
+  it("holds a movie with no year anywhere when the year is required", () => {
+    expect(missingRequiredYear(yearless, result(), true)).toBe(true);
+  });
+
+  it("lets the match through when the year is optional", () => {
+    expect(missingRequiredYear(yearless, result(), false)).toBe(false);
+  });
+
+  it("accepts a year from TMDB or the folder name", () => {
+    expect(missingRequiredYear(yearless, result({ release_date: "2004-05-01" }), true)).toBe(false);
+    expect(missingRequiredYear({ mediaType: "movie", parsedYear: 2004 }, result(), true)).toBe(false);
+  });
+
+  it("never applies to TV", () => {
+    expect(missingRequiredYear({ mediaType: "tv", parsedYear: null }, result({ media_type: "tv" }), true)).toBe(false);
+  });
+});
+
+describe("shouldAutoMatch", () => {
+  it("needs the top candidate to reach the threshold", () => {
+    expect(shouldAutoMatch([{ confidence: 0.9 }], 0.85)).toBe(true);
+    expect(shouldAutoMatch([{ confidence: 0.85 }], 0.85)).toBe(true);
+    expect(shouldAutoMatch([{ confidence: 0.84 }], 0.85)).toBe(false);
+    expect(shouldAutoMatch([], 0.85)).toBe(false);
+  });
+
+  it("holds a top candidate too close to the runner-up", () => {
+    expect(shouldAutoMatch([{ confidence: 0.95 }, { confidence: 0.85 }], 0.85)).toBe(false);
+    expect(shouldAutoMatch([{ confidence: 0.95 }, { confidence: 0.7 }], 0.85)).toBe(true);
+  });
+});
+
+describe("compareCandidates", () => {
+  // Ids of the candidates in sorted order
+  function order(candidates: Array<{ result: TmdbSearchResult; confidence: number }>, parsedYear: number | null) {
+    return [...candidates].sort((a, b) => compareCandidates(a, b, parsedYear)).map((c) => c.result.id);
+  }
+
+  it("prefers the more popular of equal-confidence candidates", () => {
+    const candidates = [
+      { result: result({ id: 1, popularity: 5 }), confidence: 0.8 },
+      { result: result({ id: 2, popularity: 40 }), confidence: 0.8 },
+    ];
+    expect(order(candidates, null)).toEqual([2, 1]);
+  });
+
+  it("then the year closest to the parsed one", () => {
+    const candidates = [
+      { result: result({ id: 1, release_date: "1978-06-01" }), confidence: 0.8 },
+      { result: result({ id: 2, release_date: "2016-06-01" }), confidence: 0.8 },
+    ];
+    expect(order(candidates, 2016)).toEqual([2, 1]);
+  });
+
+  it("then the lower TMDB id, whatever the input order", () => {
+    const a = { result: result({ id: 7 }), confidence: 0.8 };
+    const b = { result: result({ id: 3 }), confidence: 0.8 };
+    expect(order([a, b], null)).toEqual([3, 7]);
+    expect(order([b, a], null)).toEqual([3, 7]);
+  });
+});
@@ -3,48 +3,23 @@ import {
   searchMovies,
   searchTV,
   getEpisode,
+  getShowSeasons,
+  getSeason,
+  getMovieCollection,
+  isTmdbReachable,
+  lookupTmdbReference,
+  TmdbError,
   type TmdbSearchResult,
 } from "./tmdb";
 import { db } from "./db";
 import { groups, jobs, matchCandidates, settings } from "./db/schema";
-import { eq } from "drizzle-orm";
+import { eq, and, inArray, isNull } from "drizzle-orm";
 import type { Group, NewMatchCandidate } from "./db/schema";
+import { parseFileName, titleSimilarity } from "./parser";
+import { readFolderNfoReference } from "./nfo";
+import { createLogger, withSpan } from "./log";
 
-/**
- * Levenshtein distance normalized to 0-1 similarity
- */
-function titleSimilarity(a: string, b: string): number {
-  const s1 = a.toLowerCase().trim();
-  const s2 = b.toLowerCase().trim();
-
-  if (s1 === s2) return 1;
-  if (s1.length === 0 || s2.length === 0) return 0;
-
-  const len1 = s1.length;
-  const len2 = s2.length;
-  const matrix: number[][] = [];
-
-  for (let i = 0; i <= len1; i++) {
-    matrix[i] = [i];
-  }
-  for (let j = 0; j <= len2; j++) {
-    matrix[0][j] = j;
-  }
-
-  for (let i = 1; i <= len1; i++) {
-    for (let j = 1; j <= len2; j++) {
-      const cost = s1[i - 1] === s2[j - 1] ? 0 : 1;
-      matrix[i][j] = Math.min(
-        matrix[i - 1][j] + 1,
-        matrix[i][j - 1] + 1,
-        matrix[i - 1][j - 1] + cost
-      );
-    }
-  }
-
-  const maxLen = Math.max(len1, len2);
-  return 1 - matrix[len1][len2] / maxLen;
-}
+const log = createLogger("match");
 
 /**
  * Calculate confidence score for a match
@@ -95,53 +70,90 @@ function calculateConfidence(
   return titleScore + yearScore + typeScore + popScore;
 }
 
+function releaseYear(result: TmdbSearchResult): number | null {
+  const year = parseInt((result.release_date || result.first_air_date || "").slice(0, 4), 10);
+  return isNaN(year) ? null : year;
+}
+
 /**
- * Match a group against TMDB using folder name
+ * Best-first candidate order. Equal confidence (common for same-name remakes)
+ * falls back to popularity, then the year closest to the parsed one, then the
+ * lower TMDB id, so the top candidate is the same on every run.
  */
-export async function matchGroup(group: Group): Promise<void> {
-  if (!group.parsedTitle) {
-    db.update(groups)
-      .set({ status: "ambiguous", updatedAt: new Date().toISOString() })
-      .where(eq(groups.id, group.id))
-      .run();
-    return;
+export function compareCandidates(
+  a: { result: TmdbSearchResult; confidence: number },
+  b: { result: TmdbSearchResult; confidence: number },
+  parsedYear: number | null
+): number {
+  if (b.confidence !== a.confidence) return b.confidence - a.confidence;
+  const popularity = (b.result.popularity || 0) - (a.result.popularity || 0);
+  if (popularity !== 0) return popularity;
+  if (parsedYear) {
+    const distance = (r: TmdbSearchResult) => {
+      const year = releaseYear(r);
+      return year === null ? Infinity : Math.abs(year - parsedYear);
+    };
+    const diff = distance(a.result) - distance(b.result);
+    if (diff !== 0 && !isNaN(diff)) return diff;
   }
+  return a.result.id - b.result.id;
+}
+
+/**
+ * Search TMDB for a group's parsed title and replace its stored candidates
+ * with the scored results, best first. `mediaType` constrains the search;
+ * "unknown" uses multi search.
+ */
+export async function searchGroupCandidates(
+  group: Group,
+  mediaType: "movie" | "tv" | "unknown" = group.mediaType
+): Promise<Array<{ result: TmdbSearchResult; confidence: number }>> {
+  if (!group.parsedTitle) return [];
 
   // Search TMDB based on media type
   let results: TmdbSearchResult[];
-  if (group.mediaType === "tv") {
+  if (mediaType === "tv") {
     results = await searchTV(group.parsedTitle, group.parsedYear ?? undefined);
-  } else if (group.mediaType === "movie") {
+  } else if (mediaType === "movie") {
     results = await searchMovies(group.parsedTitle, group.parsedYear ?? undefined);
   } else {
     results = await searchMulti(group.parsedTitle, group.parsedYear ?? undefined);
   }
 
-  if (results.length === 0) {
-    db.update(groups)
-      .set({ status: "ambiguous", updatedAt: new Date().toISOString() })
-      .where(eq(groups.id, group.id))
-      .run();
-    return;
-  }
-
   // Score all results
   const scored = results.slice(0, 10).map((r) => ({
     result: r,
     confidence: calculateConfidence(
       group.parsedTitle!,
       group.parsedYear,
-      group.mediaType,
+      mediaType,
       r
     ),
   }));
 
-  scored.sort((a, b) => b.confidence - a.confidence);
+  scored.sort((a, b) => compareCandidates(a, b, group.parsedYear));
+
+  // Multi search can list the same entry twice; keep the best-scored copy
+  const seen = new Set<string>();
+  const unique = scored.filter(({ result }) => {
+    const key = `${result.media_type || "movie"}:${result.id}`;
+    if (seen.has(key)) return false;
+    seen.add(key);
+    return true;
+  });
 
-  // Save candidates at group level
-  db.delete(matchCandidates).where(eq(matchCandidates.groupId, group.id)).run();
+  saveGroupCandidates(group.id, unique);
+  return unique;
+}
 
-  for (const { result, confidence } of scored) {
+/** Replace a group's stored candidates with the given ones, best first. */
+function saveGroupCandidates(
+  groupId: number,
+  candidates: Array<{ result: TmdbSearchResult; confidence: number }>
+) {
+  db.delete(matchCandidates).where(eq(matchCandidates.groupId, groupId)).run();
+
+  for (const { result, confidence } of candidates) {
     const tmdbTitle = result.title || result.name || "";
     const tmdbYear = parseInt(
       (result.release_date || result.first_air_date || "").slice(0, 4),
@@ -149,7 +161,7 @@ export async function matchGroup(group: Group): Promise<void> {
     );
 
     const candidate: NewMatchCandidate = {
-      groupId: group.id,
+      groupId,
       jobId: null,
       tmdbId: result.id,
       mediaType: (result.media_type as "movie" | "tv") || "movie",
@@ -162,24 +174,114 @@ export async function matchGroup(group: Group): Promise<void> {
 
     db.insert(matchCandidates).values(candidate).run();
   }
+}
+
+export interface MatchOptions {
+  /** Overrides the auto_match_threshold setting for this run */
+  threshold?: number;
+}
+
+const DEFAULT_AUTO_MATCH_THRESHOLD = 0.85;
+// A top candidate this close to the runner-up is a coin flip (remakes,
+// reboots of the same name) and goes to review even above the threshold
+const MIN_AUTO_MATCH_GAP = 0.15;
 
-  // Auto-match logic
-  const threshold = parseFloat(
-    db.select().from(settings).where(eq(settings.key, "auto_match_threshold")).get()
-      ?.value || "0.85"
+/** The auto_match_threshold setting, or the default when it's blank or not within 0-1. */
+export function getAutoMatchThreshold(): number {
+  const value = parseFloat(
+    db.select().from(settings).where(eq(settings.key, "auto_match_threshold")).get()?.value || ""
   );
-  const top = scored[0];
-  const second = scored[1];
-  const gap = second ? top.confidence - second.confidence : 1;
+  return Number.isFinite(value) && value >= 0 && value <= 1 ? value : DEFAULT_AUTO_MATCH_THRESHOLD;
+}
+
+/**
+ * Whether the best candidate becomes the group's match. It must reach the
+ * threshold and clearly beat the runner-up; anything else stays Ambiguous
+ * with its candidates saved for review.
+ */
+export function shouldAutoMatch(
+  candidates: Array<{ confidence: number }>,
+  threshold: number
+): boolean {
+  const [top, second] = candidates;
+  if (!top || top.confidence < threshold) return false;
+  return !second || top.confidence - second.confidence >= MIN_AUTO_MATCH_GAP;
+}
+
+/**
+ * Candidates from the TMDB/IMDB id in the group folder's .nfo, at full
+ * confidence, or null when there's no usable nfo and the fuzzy search is needed.
+ */
+async function nfoCandidates(
+  group: Group
+): Promise<Array<{ result: TmdbSearchResult; confidence: number }> | null> {
+  // Groups regrouped or split out of one folder share it, and its nfo can only
+  // describe one of them
+  const sharing = db
+    .select({ id: groups.id })
+    .from(groups)
+    .where(and(eq(groups.folderPath, group.folderPath), isNull(groups.deletedAt)))
+    .all();
+  if (sharing.length > 1) return null;
+
+  const ref = readFolderNfoReference(group.folderPath);
+  if (!ref) return null;
+  let results: TmdbSearchResult[];
+  try {
+    results = await lookupTmdbReference(ref, group.mediaType);
+  } catch (err) {
+    // A bad key or lost connection fails the group; a dead id just falls back
+    if (err instanceof TmdbError && (err.isFatal || err.kind === "network")) throw err;
+    log.warn("Couldn't look up .nfo id, searching by name", { groupId: group.id, ref, err });
+    return null;
+  }
+  // An id that resolves to both a movie and a show needs a human to pick
+  if (results.length !== 1) {
+    log.warn("Ignoring .nfo id that didn't resolve to one title", { groupId: group.id, ref, found: results.length });
+    return null;
+  }
+  log.info("Matched from .nfo", { groupId: group.id, ref });
+  saveGroupCandidates(group.id, [{ result: results[0], confidence: 1 }]);
+  return [{ result: results[0], confidence: 1 }];
+}
+
+/**
+ * Match a group against TMDB: by the id in its folder's .nfo when there is
+ * one, otherwise by searching for the parsed folder name
+ */
+export async function matchGroup(group: Group, options: MatchOptions = {}): Promise<void> {
+  const fromNfo = await nfoCandidates(group);
+  const unique = fromNfo ?? await searchGroupCandidates(group);
+
+  // Nothing to pick from usually means the title was parsed badly, which is
+  // a different fix from choosing between weak candidates
+  if (unique.length === 0) {
+    db.update(groups)
+      .set({ status: "unmatched", updatedAt: new Date().toISOString() })
+      .where(eq(groups.id, group.id))
+      .run();
+    return;
+  }
+
+  const threshold = options.threshold ?? getAutoMatchThreshold();
+  const top = unique[0];
 
   const now = new Date().toISOString();
 
-  if (top.confidence >= threshold && gap >= 0.15) {
+  // An id someone wrote into the nfo is trusted as-is
+  if (
+    fromNfo ||
+    (shouldAutoMatch(unique, threshold) &&
+      !missingRequiredYear(group, top.result, requireYearForMovies()))
+  ) {
     const tmdbTitle = top.result.title || top.result.name || "";
     const tmdbYear = parseInt(
       (top.result.release_date || top.result.first_air_date || "").slice(0, 4),
       10
     );
+    const mediaType = (top.result.media_type as "movie" | "tv") || group.mediaType;
+    const tmdbCollection =
+      mediaType === "movie" ? await getMovieCollection(top.result.id) : null;
 
     // Update group with match
     db.update(groups)
@@ -190,7 +292,8 @@ export async function matchGroup(group: Group): Promise<void> {
         tmdbYear: isNaN(tmdbYear) ? null : tmdbYear,
         tmdbPosterPath: top.result.poster_path,
         matchConfidence: top.confidence,
-        mediaType: (top.result.media_type as "movie" | "tv") || group.mediaType,
+        tmdbCollection,
+        mediaType,
         updatedAt: now,
       })
       .where(eq(groups.id, group.id))
@@ -214,22 +317,97 @@ export async function matchGroup(group: Group): Promise<void> {
     if (top.result.media_type === "tv") {
       await fetchEpisodeTitles(group.id, top.result.id);
     }
+
+    if (shouldAutoConfirm(group.id, mediaType, top.confidence)) {
+      db.update(groups)
+        .set({ status: "confirmed", updatedAt: now })
+        .where(eq(groups.id, group.id))
+        .run();
+      db.update(jobs)
+        .set({ status: "confirmed", updatedAt: now })
+        .where(eq(jobs.groupId, group.id))
+        .run();
+    }
   } else {
+    // Candidates only: a match left over from an earlier run must not look chosen
     db.update(groups)
       .set({
         status: "ambiguous",
+        tmdbId: null,
+        tmdbTitle: null,
+        tmdbYear: null,
+        tmdbPosterPath: null,
+        tmdbCollection: null,
         matchConfidence: top.confidence,
         updatedAt: now,
       })
       .where(eq(groups.id, group.id))
       .run();
+    db.update(jobs)
+      .set({
+        tmdbId: null,
+        tmdbTitle: null,
+        tmdbYear: null,
+        tmdbPosterPath: null,
+        tmdbEpisodeTitle: null,
+        matchConfidence: null,
+        updatedAt: now,
+      })
+      .where(eq(jobs.groupId, group.id))
+      .run();
+  }
+}
+
+function requireYearForMovies(): boolean {
+  return db.select().from(settings).where(eq(settings.key, "require_year_for_movies")).get()?.value === "true";
+}
+
+/**
+ * With require_year_for_movies on, a movie whose year is known neither from
+ * TMDB nor the folder name is left for review instead of producing a yearless
+ * "Title/Title.ext" path.
+ */
+export function missingRequiredYear(
+  group: Pick<Group, "mediaType" | "parsedYear">,
+  result: TmdbSearchResult,
+  requireYear: boolean
+): boolean {
+  if (!requireYear) return false;
+  const mediaType = result.media_type || group.mediaType;
+  if (mediaType !== "movie" || group.parsedYear) return false;
+  return !/^\d{4}/.test(result.release_date || "");
+}
+
+/**
+ * Whether a fresh match is confident enough to skip manual review, per the
+ * optional auto_confirm_above setting. TV groups also need every episode file
+ * numbered, since confirming would otherwise transfer unresolved episodes.
+ */
+function shouldAutoConfirm(groupId: number, mediaType: string, confidence: number): boolean {
+  const value = db.select().from(settings).where(eq(settings.key, "auto_confirm_above")).get()?.value;
+  const threshold = value ? parseFloat(value) : NaN;
+  if (isNaN(threshold) || confidence < threshold) return false;
+
+  if (mediaType === "tv") {
+    const groupJobs = db
+      .select()
+      .from(jobs)
+      .where(and(eq(jobs.groupId, groupId), isNull(jobs.deletedAt)))
+      .all();
+    return groupJobs.every(
+      (job) =>
+        job.fileCategory === "extra" ||
+        job.fileCategory === "metadata" ||
+        (job.parsedSeason != null && job.parsedEpisode != null)
+    );
   }
+  return true;
 }
 
 /**
  * Fetch episode titles from TMDB for all jobs in a TV group
  */
-async function fetchEpisodeTitles(
+export async function fetchEpisodeTitles(
   groupId: number,
   tmdbId: number
 ): Promise<void> {
@@ -242,6 +420,7 @@ async function fetchEpisodeTitles(
   for (const job of groupJobs) {
     if (
       job.fileCategory === "extra" ||
+      job.fileCategory === "metadata" ||
       job.parsedSeason == null ||
       job.parsedEpisode == null
     ) {
@@ -265,37 +444,227 @@ async function fetchEpisodeTitles(
   }
 }
 
+// Files whose leftover title is less similar than this to every episode are left alone
+const EPISODE_TITLE_MIN_SIMILARITY = 0.75;
+
 /**
- * Match all unmatched groups
+ * Assign season/episode to files in a matched TV group that have no episode
+ * number, by comparing the text left after the show title against TMDB
+ * episode names.
  */
-export async function matchAllGroups(): Promise<{
+export async function alignEpisodesByTitle(groupId: number): Promise<{
+  aligned: number;
+  skipped: number;
+}> {
+  const group = db.select().from(groups).where(eq(groups.id, groupId)).get();
+  if (!group?.tmdbId || group.mediaType !== "tv") {
+    throw new Error("Group is not matched to a TV show");
+  }
+
+  const candidates = db
+    .select()
+    .from(jobs)
+    .where(and(eq(jobs.groupId, groupId), isNull(jobs.parsedEpisode), isNull(jobs.deletedAt)))
+    .all()
+    .filter((job) => job.fileCategory !== "extra" && job.fileCategory !== "metadata");
+  if (candidates.length === 0) return { aligned: 0, skipped: 0 };
+
+  const episodes: Array<{ season: number; episode: number; name: string }> = [];
+  for (const season of await getShowSeasons(group.tmdbId)) {
+    const detail = await getSeason(group.tmdbId, season.season_number);
+    for (const ep of detail?.episodes || []) {
+      if (ep.name) {
+        episodes.push({ season: season.season_number, episode: ep.episode_number, name: ep.name });
+      }
+    }
+  }
+
+  const showTitles = [group.tmdbTitle, group.parsedTitle]
+    .filter((t): t is string => !!t)
+    .map((t) => t.toLowerCase());
+
+  let aligned = 0;
+  for (const job of candidates) {
+    // "Show - The Beginning.mkv" parses to "Show The Beginning"; drop the show part
+    let leftover = parseFileName(job.fileName).title;
+    for (const showTitle of showTitles) {
+      if (leftover.toLowerCase().startsWith(showTitle)) {
+        leftover = leftover.slice(showTitle.length).trim();
+        break;
+      }
+    }
+    if (!leftover) continue;
+
+    let best: (typeof episodes)[number] | null = null;
+    let bestScore = 0;
+    for (const ep of episodes) {
+      const score = titleSimilarity(leftover, ep.name);
+      if (score > bestScore) {
+        best = ep;
+        bestScore = score;
+      }
+    }
+    if (!best || bestScore < EPISODE_TITLE_MIN_SIMILARITY) continue;
+
+    db.update(jobs)
+      .set({
+        parsedSeason: best.season,
+        parsedEpisode: best.episode,
+        numbersEdited: true,
+        tmdbEpisodeTitle: best.name,
+        fileCategory: best.season === 0 ? "special" : "episode",
+        updatedAt: new Date().toISOString(),
+      })
+      .where(eq(jobs.id, job.id))
+      .run();
+    aligned++;
+  }
+
+  return { aligned, skipped: candidates.length - aligned };
+}
+
+// Matching is IO-bound; the TMDB rate limiter keeps this many workers in check
+const MATCH_CONCURRENCY = 4;
+
+export interface MatchProgress {
+  running: boolean;
+  completed: number;
+  total: number;
+  currentTitle: string | null;
+}
+
+let matchProgress: MatchProgress = { running: false, completed: 0, total: 0, currentTitle: null };
+let cancelRequested = false;
+
+/** Progress of the current (or last) matchAllGroups run, polled by /api/match/progress. */
+export function getMatchProgress(): MatchProgress {
+  return matchProgress;
+}
+
+/**
+ * Stop the running match pass. Groups already being matched finish normally so
+ * none is left with candidates but no status; the rest stay "scanned".
+ * Returns false if nothing was running.
+ */
+export function cancelMatch(): boolean {
+  if (!matchProgress.running) return false;
+  cancelRequested = true;
+  return true;
+}
+
+/**
+ * Note on the group that TMDB couldn't be reached for it, so the group can be
+ * found and retried later. Key problems aren't the group's fault and aren't noted.
+ */
+function recordMatchError(groupId: number, err: unknown) {
+  if (err instanceof TmdbError && err.isFatal) return;
+  db.update(groups)
+    .set({
+      matchError: err instanceof Error ? err.message : String(err),
+      updatedAt: new Date().toISOString(),
+    })
+    .where(eq(groups.id, groupId))
+    .run();
+}
+
+/** Match one group, trying again once if TMDB couldn't be reached. */
+async function matchGroupWithRetry(group: Group, options: MatchOptions) {
+  try {
+    await matchGroup(group, options);
+  } catch (err) {
+    if (!(err instanceof TmdbError) || err.kind !== "network") throw err;
+    log.warn(`Retrying group ${group.id} after a network error`, { groupId: group.id, err });
+    await matchGroup(group, options);
+  }
+}
+
+/**
+ * Match all unmatched groups, or only the given ones
+ */
+export async function matchAllGroups(
+  groupIds?: number[],
+  options: MatchOptions = {}
+): Promise<{
   matched: number;
   ambiguous: number;
+  unmatched: number;
+  failed: number;
+  cancelled: boolean;
+  offline: boolean;
 }> {
-  const unmatched = db
+  const pending = db
     .select()
     .from(groups)
-    .where(eq(groups.status, "scanned"))
+    .where(
+      and(
+        eq(groups.status, "scanned"),
+        eq(groups.locked, false),
+        isNull(groups.deletedAt),
+        groupIds ? inArray(groups.id, groupIds) : undefined
+      )
+    )
     .all();
 
+  // One check up front instead of a failure logged for every group; the groups
+  // stay Scanned, so the next match picks them up
+  if (pending.length > 0 && !(await isTmdbReachable())) {
+    log.warn(`TMDB unreachable, leaving ${pending.length} groups unmatched`);
+    return { matched: 0, ambiguous: 0, unmatched: 0, failed: 0, cancelled: false, offline: true };
+  }
+
   let matched = 0;
   let ambiguous = 0;
+  let unmatched = 0;
+  let failed = 0;
+  let fatal: TmdbError | null = null;
+  let next = 0;
+  matchProgress = { running: true, completed: 0, total: pending.length, currentTitle: null };
+  cancelRequested = false;
+
+  const worker = async () => {
+    while (!fatal && !cancelRequested && next < pending.length) {
+      const group = pending[next++];
+      matchProgress.currentTitle = group.parsedTitle || group.folderName;
+      try {
+        await matchGroupWithRetry(group, options);
+        if (group.matchError) {
+          db.update(groups).set({ matchError: null }).where(eq(groups.id, group.id)).run();
+        }
+        const updated = db
+          .select()
+          .from(groups)
+          .where(eq(groups.id, group.id))
+          .get();
+        if (updated?.status === "matched" || updated?.status === "confirmed") matched++;
+        else if (updated?.status === "unmatched") unmatched++;
+        else ambiguous++;
+      } catch (err) {
+        recordMatchError(group.id, err);
+        // A bad key fails every group the same way — stop and report it. A
+        // network blip that outlasted the retry only fails this group.
+        if (err instanceof TmdbError && err.isFatal) {
+          fatal = err;
+          return;
+        }
+        log.error(`Failed to match group ${group.id}`, { groupId: group.id, err });
+        failed++;
+      }
+      matchProgress.completed++;
+    }
+  };
 
-  for (const group of unmatched) {
+  await withSpan("match", {}, async () => {
+    log.info(`Matching ${pending.length} groups`);
     try {
-      await matchGroup(group);
-      const updated = db
-        .select()
-        .from(groups)
-        .where(eq(groups.id, group.id))
-        .get();
-      if (updated?.status === "matched") matched++;
-      else ambiguous++;
-    } catch (err) {
-      console.error(`Failed to match group ${group.id}:`, err);
-      ambiguous++;
+      await Promise.all(
+        Array.from({ length: Math.min(MATCH_CONCURRENCY, pending.length) }, worker)
+      );
+    } finally {
+      matchProgress = { ...matchProgress, running: false, currentTitle: null };
     }
-  }
+    log.info("Match pass finished", { matched, ambiguous, unmatched, failed, cancelled: cancelRequested });
+  });
+  if (fatal) throw fatal;
 
-  return { matched, ambiguous };
+  return { matched, ambiguous, unmatched, failed, cancelled: cancelRequested, offline: false };
 }
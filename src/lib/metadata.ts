@@ -0,0 +1,197 @@
+import { db } from "@/lib/db";
+import { settings } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+import * as tmdb from "@/lib/tmdb";
+import * as tvdb from "@/lib/tvdb";
+
+// Groups are always matched against TMDB; other providers are looked up
+// from the TMDB id so episode lists can come from an alternative source.
+
+export type MetadataProviderId = "tmdb" | "tvdb";
+
+export const METADATA_PROVIDERS: MetadataProviderId[] = ["tmdb", "tvdb"];
+
+export type EpisodeOrder = "aired" | "dvd" | "absolute";
+
+export const EPISODE_ORDERS: EpisodeOrder[] = ["aired", "dvd", "absolute"];
+
+export interface ProviderSeason {
+  season_number: number;
+  name: string;
+  episode_count: number;
+}
+
+export interface ProviderEpisode {
+  episode_number: number;
+  season_number: number;
+  name: string;
+  air_date: string | null;
+  overview: string;
+}
+
+export interface ProviderSeasonDetail {
+  season_number: number;
+  name: string;
+  episodes: ProviderEpisode[];
+}
+
+export interface MetadataProvider {
+  id: MetadataProviderId;
+  getSeasons(tmdbId: number, order?: EpisodeOrder): Promise<ProviderSeason[]>;
+  getSeasonDetail(
+    tmdbId: number,
+    season: number,
+    order?: EpisodeOrder
+  ): Promise<ProviderSeasonDetail | null>;
+}
+
+function seasonName(season: number): string {
+  return season === 0 ? "Specials" : `Season ${season}`;
+}
+
+/**
+ * Load the TMDB episode group for a non-aired ordering. Each sub-group acts as
+ * a season (numbered by its order) and episodes are numbered by position.
+ */
+async function getTmdbOrdering(
+  tmdbId: number,
+  order: Exclude<EpisodeOrder, "aired">
+): Promise<ProviderSeasonDetail[]> {
+  const summaries = await tmdb.getEpisodeGroups(tmdbId);
+  const summary = summaries.find((g) => g.type === tmdb.EPISODE_GROUP_TYPES[order]);
+  if (!summary) throw new Error(`No ${order.toUpperCase()} ordering on TMDB for this show`);
+
+  const detail = await tmdb.getEpisodeGroup(summary.id);
+  if (!detail) throw new Error(`Failed to load ${order.toUpperCase()} ordering from TMDB`);
+
+  return detail.groups
+    .sort((a, b) => a.order - b.order)
+    .map((g) => ({
+      season_number: g.order,
+      name: g.name,
+      episodes: g.episodes
+        .sort((a, b) => a.order - b.order)
+        .map((ep) => ({
+          episode_number: ep.order + 1,
+          season_number: g.order,
+          name: ep.name,
+          air_date: ep.air_date ?? null,
+          overview: ep.overview,
+        })),
+    }));
+}
+
+const tmdbProvider: MetadataProvider = {
+  id: "tmdb",
+
+  async getSeasons(tmdbId, order = "aired") {
+    if (order !== "aired") {
+      const ordering = await getTmdbOrdering(tmdbId, order);
+      return ordering.map((s) => ({
+        season_number: s.season_number,
+        name: s.name,
+        episode_count: s.episodes.length,
+      }));
+    }
+
+    const seasons = await tmdb.getShowSeasons(tmdbId);
+    return seasons.map((s) => ({
+      season_number: s.season_number,
+      name: s.name,
+      episode_count: s.episode_count,
+    }));
+  },
+
+  async getSeasonDetail(tmdbId, season, order = "aired") {
+    if (order !== "aired") {
+      const ordering = await getTmdbOrdering(tmdbId, order);
+      return ordering.find((s) => s.season_number === season) || null;
+    }
+
+    const detail = await tmdb.getSeason(tmdbId, season);
+    if (!detail) return null;
+    return {
+      season_number: detail.season_number,
+      name: detail.name,
+      episodes: detail.episodes.map((ep) => ({
+        episode_number: ep.episode_number,
+        season_number: ep.season_number,
+        name: ep.name,
+        air_date: ep.air_date ?? null,
+        overview: ep.overview,
+      })),
+    };
+  },
+};
+
+async function resolveTvdbId(tmdbId: number): Promise<number> {
+  const ids = await tmdb.getExternalIds(tmdbId);
+  if (!ids?.tvdb_id) throw new Error("No TVDB id linked to this TMDB show");
+  return ids.tvdb_id;
+}
+
+function toProviderEpisode(ep: tvdb.TvdbEpisode): ProviderEpisode {
+  return {
+    episode_number: ep.number,
+    season_number: ep.seasonNumber,
+    name: ep.name || `Episode ${ep.number}`,
+    air_date: ep.aired,
+    overview: ep.overview || "",
+  };
+}
+
+// TVDB calls the aired ordering "default"
+const TVDB_SEASON_TYPES: Record<EpisodeOrder, string> = {
+  aired: "default",
+  dvd: "dvd",
+  absolute: "absolute",
+};
+
+const tvdbProvider: MetadataProvider = {
+  id: "tvdb",
+
+  async getSeasons(tmdbId, order = "aired") {
+    const tvdbId = await resolveTvdbId(tmdbId);
+    const episodes = await tvdb.getSeriesEpisodes(tvdbId, TVDB_SEASON_TYPES[order]);
+
+    // TVDB's season list has no episode counts, so derive them from the episodes
+    const counts = new Map<number, number>();
+    for (const ep of episodes) {
+      counts.set(ep.seasonNumber, (counts.get(ep.seasonNumber) || 0) + 1);
+    }
+    return [...counts.entries()]
+      .sort(([a], [b]) => a - b)
+      .map(([season, count]) => ({
+        season_number: season,
+        name: seasonName(season),
+        episode_count: count,
+      }));
+  },
+
+  async getSeasonDetail(tmdbId, season, order = "aired") {
+    const tvdbId = await resolveTvdbId(tmdbId);
+    const episodes = await tvdb.getSeriesEpisodes(tvdbId, TVDB_SEASON_TYPES[order], season);
+    if (episodes.length === 0) return null;
+    return {
+      season_number: season,
+      name: seasonName(season),
+      episodes: episodes
+        .filter((ep) => ep.seasonNumber === season)
+        .sort((a, b) => a.number - b.number)
+        .map(toProviderEpisode),
+    };
+  },
+};
+
+const PROVIDERS: Record<MetadataProviderId, MetadataProvider> = {
+  tmdb: tmdbProvider,
+  tvdb: tvdbProvider,
+};
+
+/** Look up a provider by id, falling back to the metadata_provider setting. */
+export function getMetadataProvider(id?: string | null): MetadataProvider {
+  const requested =
+    id ||
+    db.select().from(settings).where(eq(settings.key, "metadata_provider")).get()?.value;
+  return PROVIDERS[requested as MetadataProviderId] || tmdbProvider;
+}
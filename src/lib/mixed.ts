@@ -0,0 +1,65 @@
+import { titleSimilarity } from "./parser";
+import type { Job } from "./db/schema";
+
+// Flags groups whose files don't look like one title: a movie sitting next to
+// episodes, or files whose parsed titles have little in common. One group gets
+// one match, so everything in such a group would be named after the wrong
+// title. Runs on the jobs the client already has, so no column is stored.
+
+// Parsed titles less similar than this to every other cluster count as different titles
+const SAME_TITLE_SIMILARITY = 0.6;
+
+type MixedJob = Pick<Job, "id" | "fileCategory" | "parsedTitle" | "deletedAt">;
+
+export interface MixedContent {
+  reason: string;
+  /** The files that look out of place, suggested for splitting into their own group */
+  suggestedJobIds: number[];
+}
+
+function hasTitle(title: string | null): title is string {
+  // Bare "01.mkv"-style names carry no title to compare
+  return !!title && /[^\d\s]/.test(title);
+}
+
+/** Clusters of similar parsed titles, largest first. */
+function titleClusters(jobs: MixedJob[]): { title: string; jobs: MixedJob[] }[] {
+  const clusters: { title: string; jobs: MixedJob[] }[] = [];
+  for (const job of jobs) {
+    if (!hasTitle(job.parsedTitle)) continue;
+    const cluster = clusters.find(
+      (c) => titleSimilarity(c.title, job.parsedTitle!) >= SAME_TITLE_SIMILARITY
+    );
+    if (cluster) cluster.jobs.push(job);
+    else clusters.push({ title: job.parsedTitle, jobs: [job] });
+  }
+  return clusters.sort((a, b) => b.jobs.length - a.jobs.length);
+}
+
+/** Why a group's files look like more than one title, or null if they don't. */
+export function detectMixedContent(jobs: MixedJob[]): MixedContent | null {
+  const main = jobs.filter(
+    (j) => !j.deletedAt && j.fileCategory !== "extra" && j.fileCategory !== "metadata"
+  );
+  if (main.length < 2) return null;
+
+  const movies = main.filter((j) => j.fileCategory === "movie");
+  const episodes = main.filter((j) => j.fileCategory === "episode" || j.fileCategory === "special");
+  if (movies.length > 0 && episodes.length > 0) {
+    const odd = movies.length <= episodes.length ? movies : episodes;
+    return {
+      reason: `Holds ${movies.length} movie file${movies.length !== 1 ? "s" : ""} and ${episodes.length} episode${episodes.length !== 1 ? "s" : ""}`,
+      suggestedJobIds: odd.map((j) => j.id),
+    };
+  }
+
+  const clusters = titleClusters(main);
+  if (clusters.length > 1) {
+    const names = clusters.slice(0, 3).map((c) => `"${c.title}"`).join(", ");
+    return {
+      reason: `Files look like ${clusters.length} different titles: ${names}${clusters.length > 3 ? ", ..." : ""}`,
+      suggestedJobIds: clusters.slice(1).flatMap((c) => c.jobs.map((j) => j.id)),
+    };
+  }
+  return null;
+}
@@ -0,0 +1,156 @@
+import { describe, expect, it } from "vitest";
+import {
+  confinePath,
+  formatGroupedPath,
+  renderOptionalSegments,
+  standaloneGroup,
+  validateDestinationOverride,
+} from "./naming";
+import type { Group, Job } from "./db/schema";
+
+function makeJob(overrides: Partial<Job> = {}): Job {
+  return {
+    id: 1,
+    groupId: 1,
+    status: "matched",
+    mediaType: "tv",
+    fileCategory: "episode",
+    extraType: null,
+    sourcePath: "/src/Show/Show.S01E02.mkv",
+    fileName: "Show.S01E02.mkv",
+    fileSize: 1000,
+    fileExtension: ".mkv",
+    parsedTitle: "Show",
+    parsedYear: null,
+    parsedSeason: 1,
+    parsedEpisode: 2,
+    parsedQuality: null,
+    parsedCodec: null,
+    parsedHdr: null,
+    parsedPart: null,
+    numbersEdited: false,
+    mediaResolution: null,
+    mediaDuration: null,
+    mediaContainer: null,
+    mediaVideoCodec: null,
+    mediaAudioCodec: null,
+    mediaProbedAt: null,
+    tmdbId: null,
+    tmdbTitle: null,
+    tmdbYear: null,
+    tmdbPosterPath: null,
+    tmdbEpisodeTitle: null,
+    matchConfidence: null,
+    destinationId: null,
+    destinationPath: null,
+    destinationOverride: null,
+    transferProgress: null,
+    transferError: null,
+    deletedAt: null,
+    createdAt: "2026-01-01T00:00:00.000Z",
+    updatedAt: "2026-01-01T00:00:00.000Z",
+    ...overrides,
+  };
+}
+
+function makeGroup(overrides: Partial<Group> = {}): Group {
+  return { ...standaloneGroup(makeJob()), id: 1, tmdbTitle: "Show", tmdbYear: 2020, ...overrides };
+}
+
+const jellyfin = { naming_preset: "jellyfin", specials_folder_name: "Specials", extras_folder_name: "Extras" };
+
+describe("formatGroupedPath metadata", () => {
+  it("renames folder.jpg to poster.jpg at the show root", () => {
+    const job = makeJob({ fileCategory: "metadata", fileName: "folder.jpg", fileExtension: ".jpg" });
+    expect(formatGroupedPath(job, makeGroup(), jellyfin)).toBe("Show (2020)/poster.jpg");
+  });
+
+  it("names the nfo after the media type", () => {
+    const job = makeJob({ fileCategory: "metadata", fileName: "movie.nfo", fileExtension: ".nfo" });
+    expect(formatGroupedPath(job, makeGroup(), jellyfin)).toBe("Show (2020)/tvshow.nfo");
+  });
+});
+
+describe("formatGroupedPath optional segments", () => {
+  it("drops a missing year with its parentheses", () => {
+    const group = makeGroup({ tmdbYear: null });
+    expect(formatGroupedPath(makeJob(), group, jellyfin)).toBe("Show/Season 01/Show S01E02.mkv");
+  });
+
+  it("keeps the separator only when there is an episode title", () => {
+    const titled = makeJob({ tmdbEpisodeTitle: "Pilot" });
+    expect(formatGroupedPath(titled, makeGroup(), jellyfin)).toBe("Show (2020)/Season 01/Show S01E02 - Pilot.mkv");
+    expect(formatGroupedPath(makeJob(), makeGroup(), jellyfin)).toBe("Show (2020)/Season 01/Show S01E02.mkv");
+  });
+
+  it("adds the part to split movies", () => {
+    const job = makeJob({ mediaType: "movie", fileCategory: "movie", parsedSeason: null, parsedEpisode: null, parsedPart: 2 });
+    const group = makeGroup({ mediaType: "movie", tmdbTitle: "Movie", tmdbYear: 2004 });
+    expect(formatGroupedPath(job, group, jellyfin)).toBe("Movie (2004)/Movie (2004) - part2.mkv");
+  });
+});
+
+describe("destination confinement", () => {
+  it("keeps a title with parent segments inside the base path", () => {
+    const relative = formatGroupedPath(makeJob(), makeGroup({ tmdbTitle: "../../etc", tmdbYear: null }), jellyfin);
+    expect(relative.split("/")).not.toContain("..");
+  });
+
+  it("drops parent, root, and drive segments", () => {
+    expect(confinePath("../../etc/passwd")).toBe("etc/passwd");
+    expect(confinePath("/media/../Show/a.mkv")).toBe("media/Show/a.mkv");
+    expect(confinePath("C:\\..\\a.mkv")).toBe("a.mkv");
+  });
+
+  it("rejects overrides that escape the destination", () => {
+    expect(validateDestinationOverride("../../etc/a.mkv")).toBe("Path may not contain '..' segments");
+    expect(validateDestinationOverride("/etc/a.mkv")).toMatch(/^Path must be relative/);
+    expect(validateDestinationOverride("C:\\a.mkv")).toMatch(/^Path must be relative/);
+    expect(validateDestinationOverride("Show/Season 01/a.mkv")).toBeNull();
+  });
+});
+
+describe("formatGroupedPath pad widths", () => {
+  it("pads seasons and episodes to the configured widths", () => {
+    const settings = { ...jellyfin, season_pad_width: "2", episode_pad_width: "3" };
+    expect(formatGroupedPath(makeJob(), makeGroup(), settings)).toBe("Show (2020)/Season 01/Show S01E002.mkv");
+  });
+
+  it("never truncates a longer number", () => {
+    const settings = { ...jellyfin, episode_pad_width: "2" };
+    expect(formatGroupedPath(makeJob({ parsedEpisode: 125 }), makeGroup(), settings)).toBe(
+      "Show (2020)/Season 01/Show S01E125.mkv"
+    );
+  });
+});
+
+describe("formatGroupedPath plex specials", () => {
+  const plex = { ...jellyfin, naming_preset: "plex" };
+  const special = makeJob({ fileCategory: "special", parsedSeason: 0, parsedEpisode: 1 });
+
+  it("leaves no dangling separator without an episode title", () => {
+    expect(formatGroupedPath(special, makeGroup(), plex)).toBe("Show (2020)/Specials/Show (2020) - s00e01.mkv");
+  });
+
+  it("appends the episode title when there is one", () => {
+    expect(formatGroupedPath({ ...special, tmdbEpisodeTitle: "Pilot" }, makeGroup(), plex)).toBe(
+      "Show (2020)/Specials/Show (2020) - s00e01 - Pilot.mkv"
+    );
+  });
+});
+
+describe("renderOptionalSegments", () => {
+  const width = (_name: string, templateWidth: string) => parseInt(templateWidth, 10);
+
+  it("drops a missing quality with its separator", () => {
+    expect(renderOptionalSegments("{title}{ - quality}.{ext}", { title: "Movie", quality: "" }, width)).toBe(
+      "{title}.{ext}"
+    );
+  });
+
+  it("keeps the separator when there is a quality", () => {
+    expect(renderOptionalSegments("{title}{ - quality}.{ext}", { title: "Movie", quality: "1080p" }, width)).toBe(
+      "{title} - 1080p.{ext}"
+    );
+  });
+});
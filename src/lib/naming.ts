@@ -1,4 +1,5 @@
 import type { Job, Group } from "./db/schema";
+import { canonicalMetadataName, detectMetadataFile } from "./artwork";
 
 export type NamingPreset = "jellyfin" | "plex";
 
@@ -16,6 +17,21 @@ export type NamingPreset = "jellyfin" | "plex";
  *   Movie folder:   "Movie Name (year)/Movie Name (year).ext"
  *   Specials:       "Series Name (year)/Specials/Series Name (year) - s00eXX - Episode Title.ext"
  *   Extras:         "Series Name (year)/Behind The Scenes/filename.ext" (title case folder names)
+ *
+ * Artwork and nfo files found at the source folder root go to the show or
+ * movie root under the names both servers read: "folder.jpg" becomes
+ * "Series Name (year)/poster.jpg", "tvshow.nfo" stays "tvshow.nfo".
+ *
+ * Multi-part movies (CD1/CD2, part1/part2) keep one folder and get a
+ * " - partN" suffix: "Movie Name (year)/Movie Name (year) - part1.ext".
+ *
+ * Templates may wrap a placeholder in literal text that only renders when the
+ * value is non-empty: "{ - episodeTitle}" drops the dash when there's no
+ * episode title and "{ (year)}" drops the parentheses without a year.
+ * "{year?}" is the same with no surrounding text.
+ *
+ * With use_collections on, movies that TMDB places in a collection are nested
+ * one level deeper: "Collection Name/Movie Name (year)/Movie Name (year).ext".
  */
 export const NAMING_PRESETS: Record<
   NamingPreset,
@@ -24,21 +40,24 @@ export const NAMING_PRESETS: Record<
     tv: string;
     special: string;
     extra: string;
+    metadata: string;
   }
 > = {
   jellyfin: {
-    movie: "{title} ({year})/{title} ({year}).{ext}",
-    tv: "{title} ({year})/Season {season:2}/{title} S{season:2}E{episode:2} - {episodeTitle}.{ext}",
+    movie: "{title}{ (year)}/{title}{ (year)}.{ext}",
+    tv: "{title}{ (year)}/Season {season:2}/{title} S{season:2}E{episode:2}{ - episodeTitle}.{ext}",
     special:
-      "{title} ({year})/Season 00/{title} S00E{episode:2} - {episodeTitle}.{ext}",
-    extra: "{title} ({year})/{extraType}/{fileName}.{ext}",
+      "{title}{ (year)}/Season 00/{title} S00E{episode:2}{ - episodeTitle}.{ext}",
+    extra: "{title}{ (year)}/{extraType}/{fileName}.{ext}",
+    metadata: "{title}{ (year)}/{metadataName}.{ext}",
   },
   plex: {
-    movie: "{title} ({year})/{title} ({year}).{ext}",
-    tv: "{title} ({year})/Season {season:2}/{title} ({year}) - s{season:2}e{episode:2} - {episodeTitle}.{ext}",
+    movie: "{title}{ (year)}/{title}{ (year)}.{ext}",
+    tv: "{title}{ (year)}/Season {season:2}/{title}{ (year)} - s{season:2}e{episode:2}{ - episodeTitle}.{ext}",
     special:
-      "{title} ({year})/Specials/{title} ({year}) - s00e{episode:2} - {episodeTitle}.{ext}",
-    extra: "{title} ({year})/{extraType}/{fileName}.{ext}",
+      "{title}{ (year)}/Specials/{title}{ (year)} - s00e{episode:2}{ - episodeTitle}.{ext}",
+    extra: "{title}{ (year)}/{extraType}/{fileName}.{ext}",
+    metadata: "{title}{ (year)}/{metadataName}.{ext}",
   },
 };
 
@@ -78,10 +97,128 @@ function padNum(val: number | null | undefined, width: number): string {
   return String(val).padStart(width, "0");
 }
 
+// "{ - episodeTitle}", "{ (year)}", "{year?}", "{ - part:2}": literal text
+// around a placeholder that is dropped along with an empty value. The text
+// can't hold letters or digits, which would read as part of the name.
+const OPTIONAL_SEGMENT_PATTERN = /\{([^{}A-Za-z0-9]*)([A-Za-z]+)(?::(\d+))?(\?)?([^{}A-Za-z0-9]*)\}/g;
+
+/**
+ * Render optional segments, leaving plain "{name}" placeholders for the
+ * regular replacements. Unknown names are left untouched.
+ */
+export function renderOptionalSegments(
+  template: string,
+  values: Record<string, string>,
+  widthFor: (name: string, templateWidth: string) => number
+): string {
+  return template.replace(
+    OPTIONAL_SEGMENT_PATTERN,
+    (whole, prefix: string, name: string, width: string | undefined, optional: string | undefined, suffix: string) => {
+      if (!prefix && !suffix && !optional) return whole;
+      if (!(name in values)) return whole;
+      let value = values[name];
+      if (value && width) value = value.padStart(widthFor(name, width), "0");
+      return value ? prefix + value + suffix : "";
+    }
+  );
+}
+
 interface NamingSettings {
   naming_preset: string;
   specials_folder_name: string;
   extras_folder_name: string;
+  use_collections?: string;
+  season_pad_width?: string;
+  episode_pad_width?: string;
+}
+
+/**
+ * Width from a pad-width setting, or the template's own width when the
+ * setting is blank or out of range. Padding never truncates, so E125 stays
+ * E125 at width 2.
+ */
+function padWidth(setting: string | undefined, templateWidth: string): number {
+  const width = parseInt(setting || "", 10);
+  return width >= 1 && width <= 4 ? width : parseInt(templateWidth, 10);
+}
+
+/**
+ * Drop anything that could take a relative path outside the destination base:
+ * empty, "." and ".." segments, leading slashes, and a Windows drive prefix.
+ * Separators are normalized to "/".
+ */
+export function confinePath(relativePath: string): string {
+  return relativePath
+    .replace(/^[A-Za-z]:/, "")
+    .split(/[\\/]+/)
+    .filter((segment) => segment !== "" && segment !== "." && segment !== "..")
+    .join("/");
+}
+
+/**
+ * Check a manual destination override. Returns an error message, or null if
+ * the path is a safe relative path that stays inside the destination base.
+ */
+export function validateDestinationOverride(relativePath: string): string | null {
+  const trimmed = relativePath.trim();
+  if (!trimmed) return "Path is empty";
+  if (/^[\\/]/.test(trimmed) || /^[A-Za-z]:/.test(trimmed)) {
+    return "Path must be relative to the destination (no leading slash or drive)";
+  }
+  if (trimmed.split(/[\\/]/).some((segment) => segment === "..")) {
+    return "Path may not contain '..' segments";
+  }
+  if (/[\\/]$/.test(trimmed)) return "Path must end in a file name";
+  return null;
+}
+
+/**
+ * Drop separators an empty plain placeholder left at the end of a folder or
+ * file name, e.g. "Show - s00e01 - .mkv" or "Movie - /", whatever the template.
+ */
+function trimDanglingSeparators(relativePath: string): string {
+  const segments = relativePath.split("/");
+  return segments
+    .map((segment, i) => {
+      // Only the file name has an extension to look past
+      const extMatch = i === segments.length - 1 ? segment.match(/^(.*?)(\.[A-Za-z0-9]+)$/) : null;
+      const [stem, ext] = extMatch ? [extMatch[1], extMatch[2]] : [segment, ""];
+      return stem.replace(/(?:\s*[-–—]\s*|\s+)+$/, "") + ext;
+    })
+    .join("/");
+}
+
+/**
+ * A stand-in group for a job that doesn't belong to one, built from the job's
+ * own fields so it can still be named. New group columns get their default here.
+ */
+export function standaloneGroup(job: Job): Group {
+  return {
+    id: 0,
+    status: "matched",
+    mediaType: job.mediaType,
+    folderPath: "",
+    folderName: "",
+    totalFileCount: 1,
+    totalFileSize: job.fileSize,
+    parsedTitle: job.parsedTitle,
+    parsedYear: job.parsedYear,
+    tmdbId: job.tmdbId,
+    tmdbTitle: job.tmdbTitle,
+    tmdbYear: job.tmdbYear,
+    tmdbPosterPath: job.tmdbPosterPath,
+    matchConfidence: job.matchConfidence,
+    tmdbCollection: null,
+    episodeOrder: "aired",
+    destinationId: job.destinationId,
+    missingAt: null,
+    locked: false,
+    matchError: null,
+    titleEdited: false,
+    deletedAt: null,
+    createdAt: job.createdAt,
+    updatedAt: job.updatedAt,
+  };
 }
 
 /**
@@ -92,6 +229,8 @@ export function formatGroupedPath(
   group: Group,
   namingSettings: NamingSettings
 ): string {
+  if (job.destinationOverride) return confinePath(job.destinationOverride);
+
   const preset = (namingSettings.naming_preset || "jellyfin") as NamingPreset;
   const presetTemplates = NAMING_PRESETS[preset] || NAMING_PRESETS.jellyfin;
 
@@ -100,6 +239,13 @@ export function formatGroupedPath(
   switch (job.fileCategory) {
     case "movie":
       template = presetTemplates.movie;
+      if (namingSettings.use_collections === "true" && group.tmdbCollection) {
+        template = "{collection}/" + template;
+      }
+      // Split movies get " - part1", " - part2" so Jellyfin/Plex stack them
+      if (job.parsedPart != null) {
+        template = template.replace(/\.\{ext\}$/, " - part{part}.{ext}");
+      }
       break;
     case "special":
       template = presetTemplates.special;
@@ -107,6 +253,13 @@ export function formatGroupedPath(
     case "extra":
       template = presetTemplates.extra;
       break;
+    case "metadata":
+      // Artwork sits next to the movie, so it follows the movie into its collection folder
+      template = presetTemplates.metadata;
+      if (group.mediaType === "movie" && namingSettings.use_collections === "true" && group.tmdbCollection) {
+        template = "{collection}/" + template;
+      }
+      break;
     default:
       template = presetTemplates.tv;
   }
@@ -117,7 +270,12 @@ export function formatGroupedPath(
   const ext = job.fileExtension.replace(/^\./, "");
   const episodeTitle = sanitize(job.tmdbEpisodeTitle || "");
   const quality = job.parsedQuality || "";
+  const resolution = job.mediaResolution || job.parsedQuality || "";
+  const hdr = job.parsedHdr || "";
+  const duration = job.mediaDuration ? String(Math.round(job.mediaDuration / 60)) : "";
   const fileName = sanitize(job.fileName.replace(/\.[^.]+$/, ""));
+  const metadataKind = job.fileCategory === "metadata" ? detectMetadataFile(job.fileName) : null;
+  const metadataName = metadataKind ? canonicalMetadataName(metadataKind, group.mediaType) : fileName;
 
   // Select extra type folder name based on preset
   const extraFolderMap =
@@ -126,35 +284,57 @@ export function formatGroupedPath(
     extraFolderMap[job.extraType || ""] ||
     (preset === "plex" ? "Other" : "extras");
 
-  let result = template;
+  const seasonWidth = (templateWidth: string) => padWidth(namingSettings.season_pad_width, templateWidth);
+  const episodeWidth = (templateWidth: string) => padWidth(namingSettings.episode_pad_width, templateWidth);
+
+  let result = renderOptionalSegments(
+    template,
+    {
+      title,
+      year: String(year),
+      collection: sanitize(group.tmdbCollection || ""),
+      episodeTitle,
+      quality,
+      resolution,
+      duration,
+      hdr,
+      part: job.parsedPart != null ? String(job.parsedPart) : "",
+      season: job.parsedSeason != null ? String(job.parsedSeason) : "",
+      episode: job.parsedEpisode != null ? String(job.parsedEpisode) : "",
+    },
+    (name, templateWidth) =>
+      name === "season" ? seasonWidth(templateWidth) : name === "episode" ? episodeWidth(templateWidth) : parseInt(templateWidth, 10)
+  );
 
   result = result.replace(/\{title\}/g, title);
   result = result.replace(/\{year\}/g, String(year));
+  result = result.replace(/\{collection\}/g, sanitize(group.tmdbCollection || ""));
   result = result.replace(/\{ext\}/g, ext);
   result = result.replace(/\{episodeTitle\}/g, episodeTitle || "Episode");
   result = result.replace(/\{quality\}/g, quality);
+  result = result.replace(/\{resolution\}/g, resolution);
+  result = result.replace(/\{duration\}/g, duration);
+  result = result.replace(/\{hdr\}/g, hdr);
   result = result.replace(/\{fileName\}/g, fileName);
   result = result.replace(/\{extraType\}/g, extraTypeName);
+  result = result.replace(/\{metadataName\}/g, metadataName);
+  result = result.replace(/\{part\}/g, String(job.parsedPart ?? ""));
 
   // Season/episode with padding
   result = result.replace(/\{season:(\d+)\}/g, (_, width) =>
-    padNum(job.parsedSeason, parseInt(width, 10))
+    padNum(job.parsedSeason, seasonWidth(width))
   );
   result = result.replace(/\{episode:(\d+)\}/g, (_, width) =>
-    padNum(job.parsedEpisode, parseInt(width, 10))
+    padNum(job.parsedEpisode, episodeWidth(width))
   );
 
   result = result.replace(/\{season\}/g, String(job.parsedSeason ?? 0));
   result = result.replace(/\{episode\}/g, String(job.parsedEpisode ?? 0));
 
-  // Clean up empty episode titles leaving trailing " - "
-  result = result.replace(/ - \./, ".");
-  result = result.replace(/ - Episode\./, ".");
-
-  // Clean up empty year leaving "()" in path
-  result = result.replace(/ \(\)/g, "");
+  result = trimDanglingSeparators(result);
 
-  return result;
+  // Titles come from TMDB and filenames, so never trust them to stay in bounds
+  return confinePath(result);
 }
 
 /**
@@ -183,5 +363,5 @@ export function formatPath(template: string, job: Job): string {
   result = result.replace(/\{episode\}/g, String(job.parsedEpisode ?? 0));
   result = result.replace(/ \(\)/g, "");
 
-  return result;
+  return confinePath(result);
 }
@@ -0,0 +1,74 @@
+import fs from "fs";
+import path from "path";
+import type { TmdbReference } from "./tmdb";
+
+// Libraries tagged by Kodi, Jellyfin, tinyMediaManager and friends carry an
+// .nfo next to the media with the TMDB or IMDB id already in it. When a group's
+// folder has one, the matcher uses that id instead of a fuzzy title search.
+
+// Folder-level files written by the common tools, in order of preference
+const FOLDER_NFO_NAMES = ["tvshow.nfo", "movie.nfo"];
+
+function tagValue(xml: string, pattern: RegExp): string | null {
+  const match = xml.match(pattern);
+  return match ? match[1].trim() : null;
+}
+
+/**
+ * Pull a TMDB (preferred) or IMDB id out of an .nfo: `<tmdbid>`,
+ * `<uniqueid type="tmdb|imdb">`, `<imdbid>`, `<id>tt…</id>`, or a bare
+ * themoviedb.org / imdb.com URL as written by URL-only nfo files. An
+ * `<episodedetails>` nfo carries the episode's ids, not the show's, so it
+ * yields nothing.
+ */
+export function parseNfo(content: string): TmdbReference | null {
+  const root = content.match(/<(tvshow|movie|episodedetails)\b/i)?.[1].toLowerCase();
+  if (root === "episodedetails") return null;
+  const mediaType = root === "movie" ? "movie" : root === "tvshow" ? "tv" : undefined;
+
+  const tmdb =
+    tagValue(content, /<uniqueid[^>]*type=["']tmdb["'][^>]*>\s*(\d+)\s*<\/uniqueid>/i) ??
+    tagValue(content, /<tmdbid>\s*(\d+)\s*<\/tmdbid>/i);
+  if (tmdb) return { kind: "tmdb", id: parseInt(tmdb, 10), mediaType };
+
+  const tmdbUrl = content.match(/themoviedb\.org\/(movie|tv)\/(\d+)/i);
+  if (tmdbUrl) {
+    return { kind: "tmdb", id: parseInt(tmdbUrl[2], 10), mediaType: tmdbUrl[1].toLowerCase() as "movie" | "tv" };
+  }
+
+  const imdb =
+    tagValue(content, /<uniqueid[^>]*type=["']imdb["'][^>]*>\s*(tt\d{7,})\s*<\/uniqueid>/i) ??
+    tagValue(content, /<imdbid>\s*(tt\d{7,})\s*<\/imdbid>/i) ??
+    tagValue(content, /<id>\s*(tt\d{7,})\s*<\/id>/i) ??
+    content.match(/imdb\.com\/title\/(tt\d{7,})/i)?.[1] ??
+    null;
+  if (imdb) return { kind: "imdb", id: imdb.toLowerCase() };
+
+  return null;
+}
+
+/**
+ * The id from a group folder's .nfo: tvshow.nfo or movie.nfo, otherwise the
+ * folder's only .nfo. A folder with several per-file nfos (a flat dump of
+ * movies) is ambiguous and yields nothing.
+ */
+export function readFolderNfoReference(folderPath: string): TmdbReference | null {
+  let names: string[];
+  try {
+    names = fs.readdirSync(folderPath).filter((n) => n.toLowerCase().endsWith(".nfo"));
+  } catch {
+    return null;
+  }
+
+  const preferred = FOLDER_NFO_NAMES
+    .map((wanted) => names.find((n) => n.toLowerCase() === wanted))
+    .find((n): n is string => !!n);
+  const nfoName = preferred ?? (names.length === 1 ? names[0] : null);
+  if (!nfoName) return null;
+
+  try {
+    return parseNfo(fs.readFileSync(path.join(folderPath, nfoName), "utf8"));
+  } catch {
+    return null;
+  }
+}
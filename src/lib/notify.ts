@@ -0,0 +1,29 @@
+// Desktop (OS-level) notifications via the browser Notification API
+
+import { useAppStore } from "@/lib/store";
+
+function notificationsEnabled(): boolean {
+  return useAppStore.getState().settings.desktop_notifications === "true";
+}
+
+/**
+ * Ask the browser for notification permission. Returns true if granted.
+ */
+export async function requestNotificationPermission(): Promise<boolean> {
+  if (typeof window === "undefined" || !("Notification" in window)) return false;
+  if (Notification.permission === "granted") return true;
+  if (Notification.permission === "denied") return false;
+  return (await Notification.requestPermission()) === "granted";
+}
+
+/**
+ * Show an OS notification when enabled in settings and the page isn't focused
+ * (in-app toasts already cover the focused case).
+ */
+export function notifyDesktop(title: string, body: string) {
+  if (!notificationsEnabled()) return;
+  if (typeof window === "undefined" || !("Notification" in window)) return;
+  if (Notification.permission !== "granted") return;
+  if (document.hasFocus()) return;
+  new Notification(title, { body, icon: "/icon.svg" });
+}
@@ -0,0 +1,106 @@
+import { describe, expect, it } from "vitest";
+import { detectExtraType, parseBareEpisodeNumber, parseFileName, parseFolderSeason, parsePart } from "./parser";
+
+describe("parseFileName title", () => {
+  it("drops streaming service tags after the episode number", () => {
+    const parsed = parseFileName("Show.Name.S01E02.1080p.AMZN.WEB-DL.DDP5.1.H.264-GRP.mkv");
+    expect(parsed.title).toBe("Show Name");
+    expect(parsed.source).toBe("AMZN");
+  });
+
+  it("drops service tags after the year", () => {
+    expect(parseFileName("Movie.Name.2019.NF.WEB-DL.1080p.mkv").title).toBe("Movie Name");
+  });
+
+  it("drops bracketed service tags before the episode number", () => {
+    expect(parseFileName("Show.Name.[NF].S01E02.mkv").title).toBe("Show Name");
+  });
+
+  it("keeps title words that look like release tags", () => {
+    expect(parseFileName("Mr.Hollands.Opus.1995.1080p.BluRay.x264.mkv").title).toBe("Mr Hollands Opus");
+    expect(parseFileName("The.Remux.Movie.2020.1080p.BluRay.REMUX.mkv").title).toBe("The Remux Movie");
+    expect(parseFileName("Cam.2018.1080p.NF.WEB-DL.DDP5.1.x264.mkv").title).toBe("Cam");
+    expect(parseFileName("STAN.2019.720p.mkv").title).toBe("STAN");
+  });
+});
+
+describe("parsePart", () => {
+  it("detects CD and part markers", () => {
+    expect(parsePart("Movie.2004.CD1.avi")).toEqual({ part: 1, stem: "Movie.2004" });
+    expect(parsePart("Movie.CD2.avi")).toEqual({ part: 2, stem: "Movie" });
+    expect(parsePart("Movie.2004.part 2.avi")).toEqual({ part: 2, stem: "Movie.2004" });
+    expect(parsePart("Movie.pt.2.avi")).toEqual({ part: 2, stem: "Movie" });
+  });
+
+  it("leaves Part N inside a title alone", () => {
+    expect(parsePart("Harry.Potter.and.the.Deathly.Hallows.Part.1.mkv")).toBeUndefined();
+    expect(parsePart("Harry.Potter.and.the.Deathly.Hallows.Part.2.2011.1080p.mkv")).toBeUndefined();
+    expect(parseFileName("Harry.Potter.and.the.Deathly.Hallows.Part.2.2011.1080p.mkv").title).toBe(
+      "Harry Potter and the Deathly Hallows Part 2"
+    );
+  });
+
+  it("still finds a CD marker after a title Part N", () => {
+    expect(parsePart("Harry.Potter.and.the.Deathly.Hallows.Part.1.2010.CD2.avi")?.part).toBe(2);
+  });
+});
+
+describe("parseFileName hdr", () => {
+  it("reads each HDR format", () => {
+    expect(parseFileName("Movie.2019.2160p.Dolby.Vision.mkv").hdr).toBe("DV");
+    expect(parseFileName("Movie.2019.2160p.HDR10+.mkv").hdr).toBe("HDR10+");
+    expect(parseFileName("Movie.2019.2160p.HDR10.x265.mkv").hdr).toBe("HDR10");
+    expect(parseFileName("Movie.2019.1080p.mkv").hdr).toBeUndefined();
+  });
+
+  it("lists Dolby Vision with its fallback layer", () => {
+    expect(parseFileName("Movie.2019.2160p.DV.HDR10.mkv").hdr).toBe("DV HDR10");
+    expect(parseFileName("Movie.2019.2160p.DoVi.HDR10Plus.mkv").hdr).toBe("DV HDR10+");
+  });
+
+  it("keeps HDR tags out of the title", () => {
+    expect(parseFileName("Movie.2019.2160p.Dolby.Vision.mkv").title).toBe("Movie");
+  });
+});
+
+describe("index prefixes", () => {
+  it("drops a list index before a numbered name", () => {
+    const parsed = parseFileName("001 - Show.Name.S01E05.mkv");
+    expect(parsed.title).toBe("Show Name");
+    expect(parsed.season).toBe(1);
+    expect(parsed.episode).toBe(5);
+    expect(parseFileName("12. Show.Name.S02E03.720p.mkv").episode).toBe(3);
+  });
+
+  it("drops a tracker id", () => {
+    expect(parseFileName("4829173 Show.Name.S01E02.mkv").title).toBe("Show Name");
+  });
+
+  it("keeps a leading number as the episode when nothing else numbers the file", () => {
+    expect(parseBareEpisodeNumber("012 - Title.mkv")).toBe(12);
+    expect(parseBareEpisodeNumber("001 - Show.Name.S01E05.mkv")).toBeUndefined();
+  });
+});
+
+describe("parseFolderSeason", () => {
+  it("reads S02 and Season 2 folder names", () => {
+    expect(parseFolderSeason("Show.Name.S02.1080p")).toBe(2);
+    expect(parseFolderSeason("Show Name Season 2")).toBe(2);
+  });
+
+  it("ignores episode tokens and plain titles", () => {
+    expect(parseFolderSeason("Show.Name.S02E01.1080p")).toBeUndefined();
+    expect(parseFolderSeason("Show Name")).toBeUndefined();
+  });
+});
+
+describe("detectExtraType", () => {
+  it("maps extra suffixes to their types", () => {
+    expect(detectExtraType("Movie-trailer.mkv")).toBe("trailers");
+    expect(detectExtraType("Movie-behindthescenes.mkv")).toBe("behind_the_scenes");
+  });
+
+  it("leaves the main file alone", () => {
+    expect(detectExtraType("Movie.mkv")).toBeNull();
+  });
+});
@@ -5,8 +5,10 @@ export interface ParsedFile {
   episode?: number;
   quality?: string;
   codec?: string;
+  hdr?: string;
   source?: string;
   audio?: string;
+  part?: number;
   mediaType: "movie" | "tv" | "unknown";
 }
 
@@ -24,8 +26,59 @@ const SEASON_EPISODE_PATTERNS = [
   /(?:^|[\s._-])[Ee][Pp]?(\d{1,3})(?:[\s._-]|$)/,
 ];
 
+// Leading list index ("001 - ", "12. ") or long tracker id ("4829173 ").
+// "[001]" needs no handling here since bracketed tags are stripped anyway.
+const INDEX_PREFIX_PATTERN = /^\s*(?:\d{1,4}\s*[-–.]\s+|\d{6,}[\s._-]+)/;
+
+/**
+ * Drop a leading index prefix. A short number is only treated as an index when
+ * the rest of the name carries its own season/episode, so "012 - Title.mkv"
+ * keeps 012 as an absolute episode number and "2001 - A Space Odyssey" keeps
+ * its title. Tracker ids are too long to be either and always go.
+ */
+function stripIndexPrefix(name: string): string {
+  const m = name.match(INDEX_PREFIX_PATTERN);
+  if (!m) return name;
+  const rest = name.slice(m[0].length);
+  const isTrackerId = /^\s*\d{6,}/.test(m[0]);
+  const restIsNumbered = SEASON_EPISODE_PATTERNS.slice(0, 4).some((p) => p.test(rest));
+  return isTrackerId || restIsNumbered ? rest : name;
+}
+
 const YEAR_PATTERN = /(?:^|[\s._(-])(\d{4})(?:[\s._)-]|$)/;
 
+// Multi-part movie markers: "CD1", "Disc 2", "part1", "pt.2"
+const PART_PATTERN = /(?:^|[\s._-])(cd|dis[ck]|part|pt)[\s._-]?(\d{1,2})(?=[\s._-]|$)/gi;
+
+/**
+ * Find a multi-part marker. "Part N" is also common inside titles ("Deathly
+ * Hallows Part 1"), so a bare "part" only counts once past the year; without
+ * a year it is left to the title. CD, disc and pt markers always count.
+ */
+function findPart(name: string): RegExpMatchArray | undefined {
+  const yearIndex = name.match(YEAR_PATTERN)?.index;
+  for (const m of name.matchAll(PART_PATTERN)) {
+    if (parseInt(m[2], 10) === 0) continue;
+    if (!/^part$/i.test(m[1])) return m;
+    if (yearIndex !== undefined && m.index! > yearIndex) return m;
+  }
+  return undefined;
+}
+
+/**
+ * The part number of a stacked movie file plus its name with the marker
+ * removed, so "Movie.2004.CD1.avi" and "Movie.2004.CD2.avi" share a stem.
+ */
+export function parsePart(fileName: string): { part: number; stem: string } | undefined {
+  const name = fileName.replace(/\.[^.]+$/, "");
+  const m = findPart(name);
+  if (!m) return undefined;
+  const stem = (name.slice(0, m.index) + name.slice(m.index! + m[0].length))
+    .replace(/[\s._-]+$/, "")
+    .trim();
+  return { part: parseInt(m[2], 10), stem };
+}
+
 const QUALITY_PATTERNS = [
   /\b(2160p|4[Kk]|UHD)\b/,
   /\b(1080p|1080i)\b/,
@@ -33,6 +86,15 @@ const QUALITY_PATTERNS = [
   /\b(480p|576p|SD)\b/,
 ];
 
+/** Canonical "1080p"-style label for a parsed quality tag, for comparing against probed resolution. */
+export function normalizeQuality(quality: string): string {
+  const q = quality.toLowerCase();
+  if (q === "4k" || q === "uhd") return "2160p";
+  if (q === "1080i") return "1080p";
+  if (q === "sd") return "480p";
+  return q;
+}
+
 const SOURCE_PATTERNS = [
   /\b(Blu-?[Rr]ay|BDRip|BRRip|BDREMUX)\b/i,
   /\b(WEB-?DL|WEBRip|WEBDL|AMZN|NF|DSNP|HMAX|ATVP|PCOK|PMTP)\b/i,
@@ -54,22 +116,58 @@ const CODEC_PATTERNS = [
 const AUDIO_PATTERNS = [
   /\b(DTS-?HD[\s._-]?MA|DTS-?HD|DTS-?X|DTS)\b/i,
   /\b(TrueHD[\s._-]?Atmos|TrueHD|Atmos)\b/i,
-  /\b(DD[P+]?\s*5\.1|DDP?7\.1|Dolby\s*Digital|AC-?3|EAC-?3|E-AC-3)\b/i,
+  // Dots are spaces by the time these run, so "DDP5.1" reads "DDP5 1"
+  /\b(DD[P+]?\s*5[\s.]1|DDP?7[\s.]1|Dolby\s*Digital|AC-?3|EAC-?3|E-AC-3)\b/i,
   /\b(FLAC|LPCM|PCM)\b/i,
-  /\b(AAC[\s._-]?2\.0|AAC[\s._-]?5\.1|AAC)\b/i,
+  /\b(AAC[\s._-]?2[\s.]0|AAC[\s._-]?5[\s.]1|AAC)\b/i,
   /\b(MP3|OGG|OPUS)\b/i,
 ];
 
+// HDR formats, in the order they're listed when a file carries several
+// (e.g. a DV release with an HDR10 fallback layer is "DV HDR10")
+const HDR_PATTERNS: Array<{ label: string; pattern: RegExp }> = [
+  { label: "DV", pattern: /\b(Dolby[\s._-]?Vision|DoVi|DV)\b/i },
+  { label: "HDR10+", pattern: /\b(HDR10\+|HDR10Plus)(?=[\s._\])-]|$)/i },
+  { label: "HDR10", pattern: /\bHDR10\b(?!\+|Plus)/i },
+  { label: "HLG", pattern: /\bHLG\b/i },
+  { label: "HDR", pattern: /\bHDR\b/i },
+];
+
+/** Collect every HDR format tag in a name, e.g. "DV HDR10". */
+function detectHdr(name: string): string | undefined {
+  const found: string[] = [];
+  for (const { label, pattern } of HDR_PATTERNS) {
+    if (!pattern.test(name)) continue;
+    // Plain "HDR" is implied by any specific format
+    if (label === "HDR" && found.some((f) => f !== "DV")) continue;
+    found.push(label);
+  }
+  return found.length > 0 ? found.join(" ") : undefined;
+}
+
 const RELEASE_GROUP_PATTERN = /-([A-Za-z0-9]+)$/;
 
+// Streaming services and release groups that show up as bare all-caps tokens.
+// Matched case-sensitively so ordinary title words ("Nf", "Yts") survive.
+const NOISE_TOKEN_PATTERN =
+  /(?:^|\s)(AMZN|NF|DSNP|HMAX|ATVP|PCOK|PMTP|HULU|CRKL|STAN|RARBG|YTS|YIFY|EZTV|ETTV|QxR)(?=\s|$)/g;
+
 const MISC_PATTERNS = [
   /\b(PROPER|REPACK|RERIP|REAL|INTERNAL|LIMITED|EXTENDED|UNRATED|DC|DIRECTORS[\s._-]?CUT)\b/i,
-  /\b(HDR10\+?|HDR|DV|DoVi|Dolby[\s._-]?Vision|SDR|HLG)\b/i,
+  /\b(HDR10Plus|HDR10\+?|HDR|DV|DoVi|Dolby[\s._-]?Vision|SDR|HLG)(?=[\s._\])-]|$)/gi,
   /\b(10bit|8bit|12bit)\b/i,
   /\b(MULTI|MULTi|DUAL|DUBBED|SUBBED)\b/i,
   /\b(COMPLETE|PROPER|REMASTERED)\b/i,
 ];
 
+function cleanTitle(text: string): string {
+  return text
+    .replace(/[-–—]/g, " ")
+    .replace(/[[\](){}]/g, " ")
+    .replace(/\s+/g, " ")
+    .trim();
+}
+
 function stripPattern(
   input: string,
   pattern: RegExp
@@ -94,9 +192,31 @@ function stripPatternList(
   return { cleaned: input, match: null };
 }
 
+/**
+ * Like stripPatternList, but keeps going until no pattern matches anywhere, so
+ * a second tag ("WEB-DL AMZN") can't leak into the title. The reported match
+ * is the first one found, in pattern order.
+ */
+function stripAllPatterns(
+  input: string,
+  patterns: RegExp[]
+): { cleaned: string; match: string | null } {
+  let cleaned = input;
+  let first: string | null = null;
+  for (const pattern of patterns) {
+    for (;;) {
+      const result = stripPattern(cleaned, pattern);
+      if (!result.match) break;
+      first ??= result.match;
+      cleaned = result.cleaned;
+    }
+  }
+  return { cleaned, match: first };
+}
+
 export function parseFileName(fileName: string): ParsedFile {
   // Remove file extension
-  let working = fileName.replace(/\.[^.]+$/, "");
+  let working = stripIndexPrefix(fileName.replace(/\.[^.]+$/, ""));
 
   // Strip bracketed tags like [DTA], [SubGroup], [1080p], [HEVC] etc.
   // These are common in anime/scene releases and should not pollute the title.
@@ -112,9 +232,19 @@ export function parseFileName(fileName: string): ParsedFile {
   const releaseResult = stripPattern(working, RELEASE_GROUP_PATTERN);
   working = releaseResult.cleaned;
 
+  // Extract multi-part marker before "pt 1" can be taken for anything else
+  let part: number | undefined;
+  const partMatch = findPart(working);
+  if (partMatch) {
+    part = parseInt(partMatch[2], 10);
+    working = working.slice(0, partMatch.index) + " " + working.slice(partMatch.index! + partMatch[0].length);
+  }
+
   // Extract season/episode
   let season: number | undefined;
   let episode: number | undefined;
+  // Where the title ends: the first of the SxxEyy and the year
+  let titleEnd: number | undefined;
 
   for (const pattern of SEASON_EPISODE_PATTERNS) {
     const m = working.match(pattern);
@@ -127,6 +257,7 @@ export function parseFileName(fileName: string): ParsedFile {
         season = parseInt(m[1], 10);
         episode = parseInt(m[2], 10);
       }
+      titleEnd = m.index;
       working = working.slice(0, m.index) + " " + working.slice(m.index! + m[0].length);
       break;
     }
@@ -134,45 +265,58 @@ export function parseFileName(fileName: string): ParsedFile {
 
   // Extract year
   let year: number | undefined;
+  const yearMatch = working.match(YEAR_PATTERN);
   const yearResult = stripPattern(working, YEAR_PATTERN);
   if (yearResult.groups) {
     const y = parseInt(yearResult.groups[0], 10);
     if (y >= 1900 && y <= new Date().getFullYear() + 1) {
       year = y;
+      // Text before the SxxEyy is untouched above, so both indexes still line up
+      titleEnd = Math.min(titleEnd ?? Infinity, yearMatch!.index!);
       working = yearResult.cleaned;
     }
   }
 
+  // Release tags are only looked for past the title, so title words that
+  // double as tags ("Mr. Holland's Opus", "Cam", "Stan") survive. Without a
+  // year or SxxEyy the whole name is searched.
+  const head = titleEnd !== undefined ? working.slice(0, titleEnd) : "";
+  let tags = titleEnd !== undefined ? working.slice(titleEnd) : working;
+
   // Extract quality
-  const qualityResult = stripPatternList(working, QUALITY_PATTERNS);
+  const qualityResult = stripAllPatterns(tags, QUALITY_PATTERNS);
   const quality = qualityResult.match || undefined;
-  working = qualityResult.cleaned;
+  tags = qualityResult.cleaned;
 
   // Extract source
-  const sourceResult = stripPatternList(working, SOURCE_PATTERNS);
-  working = sourceResult.cleaned;
+  const sourceResult = stripAllPatterns(tags, SOURCE_PATTERNS);
+  tags = sourceResult.cleaned;
 
   // Extract codec
-  const codecResult = stripPatternList(working, CODEC_PATTERNS);
+  const codecResult = stripPatternList(tags, CODEC_PATTERNS);
   const codec = codecResult.match || undefined;
-  working = codecResult.cleaned;
+  tags = codecResult.cleaned;
 
   // Extract audio
-  const audioResult = stripPatternList(working, AUDIO_PATTERNS);
+  const audioResult = stripAllPatterns(tags, AUDIO_PATTERNS);
   const audio = audioResult.match || undefined;
-  working = audioResult.cleaned;
+  tags = audioResult.cleaned;
+
+  // HDR tags are stripped along with the other misc tags below
+  const hdr = detectHdr(tags);
 
   // Strip misc tags
   for (const pattern of MISC_PATTERNS) {
-    working = working.replace(pattern, " ");
+    tags = tags.replace(pattern, " ");
   }
 
-  // Clean up remaining string to get title
-  let title = working
-    .replace(/[-–—]/g, " ")
-    .replace(/[[\](){}]/g, " ")
-    .replace(/\s+/g, " ")
-    .trim();
+  // Service/group names that dodged the patterns above
+  const withNoise = tags;
+  tags = tags.replace(/\s+/g, " ").replace(NOISE_TOKEN_PATTERN, " ");
+
+  // The title is what precedes the year/SxxEyy; a name that starts with one
+  // ("2001 - A Space Odyssey") falls back to what's left after the tags
+  let title = cleanTitle(head) || cleanTitle(tags) || cleanTitle(withNoise);
 
   // Media type heuristic
   let mediaType: "movie" | "tv" | "unknown" = "unknown";
@@ -189,17 +333,83 @@ export function parseFileName(fileName: string): ParsedFile {
     episode,
     quality,
     codec,
+    hdr,
     source: sourceResult.match || undefined,
     audio,
+    part,
     mediaType,
   };
 }
 
+// Plex/Jellyfin-style extra suffixes: "Movie-trailer.mkv", "Movie - behindthescenes.mkv"
+const EXTRA_SUFFIX_MAP: Record<string, string> = {
+  trailer: "trailers",
+  behindthescenes: "behind_the_scenes",
+  deleted: "deleted_scenes",
+  deletedscene: "deleted_scenes",
+  featurette: "featurettes",
+  interview: "interviews",
+  scene: "scenes",
+  short: "shorts",
+  other: "other",
+  extra: "other",
+};
+
+const EXTRA_SUFFIX_PATTERN = new RegExp(
+  `[\\s._]*-[\\s._]*(${Object.keys(EXTRA_SUFFIX_MAP).join("|")})$`,
+  "i"
+);
+
+/**
+ * Detect an extra from a filename suffix (e.g. "Movie-trailer.mkv" → "trailers").
+ * Returns the extra_type DB value, or null for regular files.
+ */
+export function detectExtraType(fileName: string): string | null {
+  const base = fileName.replace(/\.[^.]+$/, "").trim();
+  const m = base.match(EXTRA_SUFFIX_PATTERN);
+  return m ? EXTRA_SUFFIX_MAP[m[1].toLowerCase()] : null;
+}
+
 export interface ParsedFolder {
   title: string;
   year?: number;
 }
 
+// Season-only tokens in folder names: "Show.Name.S02.1080p", "Show Name Season 2"
+const FOLDER_SEASON_PATTERNS = [
+  /(?:^|[\s._-])[Ss](\d{1,2})(?![Ee]\d)(?=[\s._-]|$)/,
+  /(?:^|[\s._-])Season[\s._-]*(\d{1,2})(?=[\s._-]|$)/i,
+];
+
+function matchFolderSeason(folderName: string): RegExpMatchArray | null {
+  for (const pattern of FOLDER_SEASON_PATTERNS) {
+    const m = folderName.match(pattern);
+    if (m) return m;
+  }
+  return null;
+}
+
+/**
+ * Extract a season number from a season-pack folder name (e.g. "Show.Name.S02.1080p").
+ * Returns undefined when the folder carries no season-only token.
+ */
+export function parseFolderSeason(folderName: string): number | undefined {
+  const m = matchFolderSeason(folderName);
+  return m ? parseInt(m[1], 10) : undefined;
+}
+
+/**
+ * Read a bare episode number from season-pack file names like "01.mkv" or "01 - Pilot.mkv".
+ * Only meaningful when the season comes from elsewhere (folder hint).
+ */
+export function parseBareEpisodeNumber(fileName: string): number | undefined {
+  const base = fileName.replace(/\.[^.]+$/, "").trim();
+  // "001 - Show.S01E01" is an index, not episode 1
+  if (stripIndexPrefix(base) !== base) return undefined;
+  const m = base.match(/^(\d{1,3})(?:\s*[-–.]\s+.*)?$/);
+  return m ? parseInt(m[1], 10) : undefined;
+}
+
 /**
  * Parse a folder name to extract title and optional year.
  * Simpler than filename parsing — just handles year in parens and common separators.
@@ -210,6 +420,12 @@ export function parseFolderName(folderName: string): ParsedFolder {
   // Strip bracketed tags like [DTA], [SubGroup], [1080p] etc.
   working = working.replace(/\[[^\]]*\]/g, " ");
 
+  // Season packs: drop the season token and the release tags that follow it
+  const seasonMatch = matchFolderSeason(working);
+  if (seasonMatch && seasonMatch.index! > 0) {
+    working = working.slice(0, seasonMatch.index);
+  }
+
   // Extract year in parentheses: "Show Name (2020)" or "Show Name (2020) [extras]"
   let year: number | undefined;
   const parenYearMatch = working.match(/\((\d{4})\)/);
@@ -246,3 +462,39 @@ export function parseFolderName(folderName: string): ParsedFolder {
 
   return { title, year };
 }
+
+/**
+ * Levenshtein distance normalized to 0-1 similarity
+ */
+export function titleSimilarity(a: string, b: string): number {
+  const s1 = a.toLowerCase().trim();
+  const s2 = b.toLowerCase().trim();
+
+  if (s1 === s2) return 1;
+  if (s1.length === 0 || s2.length === 0) return 0;
+
+  const len1 = s1.length;
+  const len2 = s2.length;
+  const matrix: number[][] = [];
+
+  for (let i = 0; i <= len1; i++) {
+    matrix[i] = [i];
+  }
+  for (let j = 0; j <= len2; j++) {
+    matrix[0][j] = j;
+  }
+
+  for (let i = 1; i <= len1; i++) {
+    for (let j = 1; j <= len2; j++) {
+      const cost = s1[i - 1] === s2[j - 1] ? 0 : 1;
+      matrix[i][j] = Math.min(
+        matrix[i - 1][j] + 1,
+        matrix[i][j - 1] + 1,
+        matrix[i - 1][j - 1] + cost
+      );
+    }
+  }
+
+  const maxLen = Math.max(len1, len2);
+  return 1 - matrix[len1][len2] / maxLen;
+}
@@ -0,0 +1,119 @@
+import fs from "fs";
+import path from "path";
+import { db, DATA_DIR } from "@/lib/db";
+import { settings } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+import { createLogger } from "@/lib/log";
+
+const TMDB_IMG_ORIGIN = "https://image.tmdb.org/t/p";
+const POSTER_DIR = path.join(DATA_DIR, "posters");
+const log = createLogger("posters");
+
+export const POSTER_SIZES = new Set(["w92", "w154", "w185", "w342", "w500", "original"]);
+const DEFAULT_MAX_CACHE_MB = 200;
+
+// TMDB image paths look like "/kqjL17yufvn9OVLyXYpvtyrFfak.jpg"
+const POSTER_PATH_PATTERN = /^\/[A-Za-z0-9_-]+\.(jpg|jpeg|png|webp)$/;
+
+const CONTENT_TYPES: Record<string, string> = {
+  ".jpg": "image/jpeg",
+  ".jpeg": "image/jpeg",
+  ".png": "image/png",
+  ".webp": "image/webp",
+};
+
+export function isValidPosterPath(posterPath: string): boolean {
+  return POSTER_PATH_PATTERN.test(posterPath);
+}
+
+function getMaxCacheBytes(): number {
+  const setting = db
+    .select()
+    .from(settings)
+    .where(eq(settings.key, "poster_cache_max_mb"))
+    .get();
+  const mb = parseFloat(setting?.value || "");
+  return (Number.isFinite(mb) && mb >= 0 ? mb : DEFAULT_MAX_CACHE_MB) * 1024 * 1024;
+}
+
+function listCachedFiles(): { file: string; size: number; mtimeMs: number }[] {
+  if (!fs.existsSync(POSTER_DIR)) return [];
+  const results: { file: string; size: number; mtimeMs: number }[] = [];
+  for (const sizeDir of fs.readdirSync(POSTER_DIR)) {
+    const dir = path.join(POSTER_DIR, sizeDir);
+    if (!fs.statSync(dir).isDirectory()) continue;
+    for (const name of fs.readdirSync(dir)) {
+      const file = path.join(dir, name);
+      const stat = fs.statSync(file);
+      results.push({ file, size: stat.size, mtimeMs: stat.mtimeMs });
+    }
+  }
+  return results;
+}
+
+/** Delete least-recently-used posters until the cache fits the configured size. */
+function evictPosters() {
+  const maxBytes = getMaxCacheBytes();
+  const files = listCachedFiles();
+  let total = files.reduce((sum, f) => sum + f.size, 0);
+  if (total <= maxBytes) return;
+
+  files.sort((a, b) => a.mtimeMs - b.mtimeMs);
+  for (const f of files) {
+    if (total <= maxBytes) break;
+    try {
+      fs.unlinkSync(f.file);
+      total -= f.size;
+    } catch {
+      // Already gone or locked — skip
+    }
+  }
+}
+
+/**
+ * Return poster bytes from the disk cache, downloading from TMDB on a miss.
+ * Cache hits refresh the file's mtime so eviction is least-recently-used.
+ */
+export async function getPoster(
+  posterPath: string,
+  size: string
+): Promise<{ data: Buffer; contentType: string } | null> {
+  const ext = path.extname(posterPath).toLowerCase();
+  const contentType = CONTENT_TYPES[ext] || "application/octet-stream";
+  const cachedFile = path.join(POSTER_DIR, size, posterPath.slice(1));
+
+  try {
+    const data = await fs.promises.readFile(cachedFile);
+    const now = new Date();
+    fs.promises.utimes(cachedFile, now, now).catch(() => {});
+    return { data, contentType };
+  } catch {
+    // Cache miss — fall through to download
+  }
+
+  const res = await fetch(`${TMDB_IMG_ORIGIN}/${size}${posterPath}`);
+  if (!res.ok) return null;
+  const data = Buffer.from(await res.arrayBuffer());
+
+  try {
+    await fs.promises.mkdir(path.dirname(cachedFile), { recursive: true });
+    await fs.promises.writeFile(cachedFile, data);
+    evictPosters();
+  } catch (err) {
+    log.error("Failed to cache poster", { err });
+  }
+
+  return { data, contentType };
+}
+
+export function getPosterCacheStats(): { files: number; bytes: number } {
+  const files = listCachedFiles();
+  return {
+    files: files.length,
+    bytes: files.reduce((sum, f) => sum + f.size, 0),
+  };
+}
+
+export function clearPosterCache() {
+  fs.rmSync(POSTER_DIR, { recursive: true, force: true });
+}
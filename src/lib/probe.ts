@@ -0,0 +1,133 @@
+import { execFile } from "child_process";
+import { db } from "@/lib/db";
+import { jobs, settings } from "@/lib/db/schema";
+import { eq, isNull } from "drizzle-orm";
+import { createLogger } from "@/lib/log";
+
+// Optional ffprobe integration. Filename-derived quality/codec is often wrong,
+// so when enabled every scanned file is probed in the background and the real
+// values are stored alongside the parsed ones.
+
+const PROBE_TIMEOUT_MS = 30_000;
+const log = createLogger("probe");
+
+export interface MediaInfo {
+  resolution: string | null;
+  duration: number | null;
+  container: string | null;
+  videoCodec: string | null;
+  audioCodec: string | null;
+}
+
+let ffprobeAvailable: boolean | null = null;
+let probing = false;
+
+function runFfprobe(args: string[]): Promise<string> {
+  return new Promise((resolve, reject) => {
+    execFile(
+      "ffprobe",
+      args,
+      { timeout: PROBE_TIMEOUT_MS, maxBuffer: 4 * 1024 * 1024 },
+      (err, stdout) => (err ? reject(err) : resolve(stdout))
+    );
+  });
+}
+
+export async function isFfprobeAvailable(): Promise<boolean> {
+  if (ffprobeAvailable === null) {
+    ffprobeAvailable = await runFfprobe(["-version"]).then(
+      () => true,
+      () => false
+    );
+  }
+  return ffprobeAvailable;
+}
+
+export function isProbeEnabled(): boolean {
+  const setting = db.select().from(settings).where(eq(settings.key, "probe_media")).get();
+  return setting?.value === "true";
+}
+
+/** Map pixel dimensions to the usual "1080p"-style label. */
+export function resolutionLabel(width: number, height: number): string {
+  // Use width too so letterboxed 1920x800 still counts as 1080p
+  if (width >= 3200 || height >= 2000) return "2160p";
+  if (width >= 1800 || height >= 1000) return "1080p";
+  if (width >= 1200 || height >= 700) return "720p";
+  if (height >= 560) return "576p";
+  return `${height >= 470 ? 480 : height}p`;
+}
+
+export async function probeFile(filePath: string): Promise<MediaInfo> {
+  const stdout = await runFfprobe([
+    "-v", "error",
+    "-print_format", "json",
+    "-show_format",
+    "-show_streams",
+    filePath,
+  ]);
+  const data = JSON.parse(stdout) as {
+    format?: { format_name?: string; duration?: string };
+    streams?: Array<{
+      codec_type?: string;
+      codec_name?: string;
+      width?: number;
+      height?: number;
+      disposition?: { attached_pic?: number };
+    }>;
+  };
+
+  const streams = data.streams || [];
+  // Skip embedded cover art, which shows up as a video stream
+  const video = streams.find((s) => s.codec_type === "video" && !s.disposition?.attached_pic);
+  const audio = streams.find((s) => s.codec_type === "audio");
+  const duration = parseFloat(data.format?.duration || "");
+
+  return {
+    resolution: video?.width && video?.height ? resolutionLabel(video.width, video.height) : null,
+    duration: Number.isFinite(duration) ? duration : null,
+    container: data.format?.format_name?.split(",")[0] || null,
+    videoCodec: video?.codec_name || null,
+    audioCodec: audio?.codec_name || null,
+  };
+}
+
+/**
+ * Probe every job that hasn't been probed yet. Runs one file at a time in the
+ * background; calling it while a pass is already running is a no-op since the
+ * running pass picks up newly scanned jobs before it finishes.
+ */
+export async function probePendingJobs(): Promise<void> {
+  if (probing || !isProbeEnabled() || !(await isFfprobeAvailable())) return;
+  probing = true;
+
+  try {
+    for (;;) {
+      const job = db.select().from(jobs).where(isNull(jobs.mediaProbedAt)).get();
+      if (!job) break;
+
+      let info: MediaInfo | null = null;
+      try {
+        // Artwork and nfo files have nothing to probe
+        if (job.fileCategory !== "metadata") info = await probeFile(job.sourcePath);
+      } catch (err) {
+        log.warn(`Failed to probe ${job.sourcePath}`, { jobId: job.id, err });
+      }
+
+      // Mark failures as probed too so one bad file doesn't stall the pass
+      db.update(jobs)
+        .set({
+          mediaResolution: info?.resolution ?? null,
+          mediaDuration: info?.duration ?? null,
+          mediaContainer: info?.container ?? null,
+          mediaVideoCodec: info?.videoCodec ?? null,
+          mediaAudioCodec: info?.audioCodec ?? null,
+          mediaProbedAt: new Date().toISOString(),
+        })
+        .where(eq(jobs.id, job.id))
+        .run();
+    }
+  } finally {
+    probing = false;
+  }
+}
@@ -0,0 +1,236 @@
+import path from "path";
+import { db } from "@/lib/db";
+import { groupLabels, groups, jobs } from "@/lib/db/schema";
+import { and, eq, inArray, isNull, notInArray } from "drizzle-orm";
+import { determineMediaType } from "@/lib/scanner";
+import { parseFolderName } from "@/lib/parser";
+import type { Group, Job } from "@/lib/db/schema";
+
+// Structural edits to groups: moving files out into a group of their own, or
+// folding groups that should be one (seasons that landed separately) together.
+
+/** Recompute a group's file count and size from its live jobs. */
+export function refreshTotals(groupId: number, now: string) {
+  const live = db
+    .select()
+    .from(jobs)
+    .where(and(eq(jobs.groupId, groupId), isNull(jobs.deletedAt)))
+    .all();
+  db.update(groups)
+    .set({
+      totalFileCount: live.length,
+      totalFileSize: live.reduce((sum, j) => sum + j.fileSize, 0),
+      updatedAt: now,
+    })
+    .where(eq(groups.id, groupId))
+    .run();
+  return live;
+}
+
+/** Give `toId` every label `fromId` has. */
+export function copyLabels(fromId: number, toId: number) {
+  const existing = new Set(
+    db.select().from(groupLabels).where(eq(groupLabels.groupId, toId)).all().map((l) => l.labelId)
+  );
+  for (const { labelId } of db.select().from(groupLabels).where(eq(groupLabels.groupId, fromId)).all()) {
+    if (!existing.has(labelId)) db.insert(groupLabels).values({ groupId: toId, labelId }).run();
+  }
+}
+
+/** Deepest folder holding every file, so a rescan finds the new group's files under it. */
+function commonFolder(sourcePaths: string[]): string {
+  const dirs = sourcePaths.map((p) => path.dirname(p).split(path.sep));
+  const common: string[] = [];
+  for (let i = 0; i < dirs[0].length; i++) {
+    if (dirs.some((d) => d[i] !== dirs[0][i])) break;
+    common.push(dirs[0][i]);
+  }
+  return common.join(path.sep) || path.sep;
+}
+
+/**
+ * Folder name for files moved out of a group: the most common title parsed
+ * from their names, with its year, so matching searches for the right thing.
+ */
+function folderNameFor(moved: Job[], fallback: string): string {
+  const counts = new Map<string, { title: string; year: number | null; count: number }>();
+  for (const job of moved) {
+    if (!job.parsedTitle || !/[^\d\s]/.test(job.parsedTitle)) continue;
+    const key = job.parsedTitle.toLowerCase();
+    const entry = counts.get(key) ?? { title: job.parsedTitle, year: job.parsedYear, count: 0 };
+    entry.count++;
+    counts.set(key, entry);
+  }
+  const best = [...counts.values()].sort((a, b) => b.count - a.count)[0];
+  if (!best) return fallback;
+  return best.year ? `${best.title} (${best.year})` : best.title;
+}
+
+/**
+ * Move some of a group's files into a new, unmatched group. Candidates belong
+ * to the old group, so the new one starts from Scanned and is matched on its own.
+ */
+export function splitGroup(groupId: number, jobIds: number[]): Group {
+  const group = db.select().from(groups).where(eq(groups.id, groupId)).get();
+  if (!group || group.deletedAt) throw new Error("Group not found");
+  if (group.locked) throw new Error("Group is locked; unlock it to split it");
+  if (group.status === "queued" || group.status === "transferring") {
+    throw new Error("Group is transferring; wait for it to finish");
+  }
+
+  const live = db
+    .select()
+    .from(jobs)
+    .where(and(eq(jobs.groupId, groupId), isNull(jobs.deletedAt)))
+    .all();
+  const moving = new Set(jobIds);
+  const moved = live.filter((j) => moving.has(j.id));
+  if (moved.length === 0) throw new Error("None of those files are in this group");
+  if (moved.length === live.length) throw new Error("Leave at least one file in the group");
+
+  const now = new Date().toISOString();
+  const folderName = folderNameFor(moved, `${group.folderName} (split)`);
+  const mediaType = determineMediaType(moved);
+  const parsedFolder = parseFolderName(folderName);
+  const created = db
+    .insert(groups)
+    .values({
+      status: "scanned",
+      mediaType,
+      folderPath: commonFolder(moved.map((j) => j.sourcePath)),
+      folderName,
+      totalFileCount: moved.length,
+      totalFileSize: moved.reduce((sum, j) => sum + j.fileSize, 0),
+      parsedTitle: parsedFolder.title,
+      parsedYear: parsedFolder.year ?? null,
+      destinationId: group.destinationId,
+      createdAt: now,
+      updatedAt: now,
+    })
+    .returning()
+    .get();
+
+  db.update(jobs)
+    .set({
+      groupId: created.id,
+      status: "scanned",
+      mediaType,
+      tmdbId: null,
+      tmdbTitle: null,
+      tmdbYear: null,
+      tmdbPosterPath: null,
+      tmdbEpisodeTitle: null,
+      matchConfidence: null,
+      updatedAt: now,
+    })
+    .where(inArray(jobs.id, moved.map((j) => j.id)))
+    .run();
+
+  const remaining = refreshTotals(groupId, now);
+  // An unmatched group takes its type from what's left; a matched one keeps its match
+  if (!group.tmdbId) {
+    db.update(groups)
+      .set({ mediaType: determineMediaType(remaining) })
+      .where(eq(groups.id, groupId))
+      .run();
+  }
+
+  return created;
+}
+
+// Moved files that were set aside or already sent keep their status
+const KEPT_JOB_STATUSES = ["skipped", "completed", "failed"] as const;
+
+function isBusy(group: Group): boolean {
+  return group.status === "queued" || group.status === "transferring";
+}
+
+/** Source groups matched to a different title than the target, which merging would discard. */
+export function conflictingMatches(target: Group, sources: Group[]): Group[] {
+  return sources.filter(
+    (g) =>
+      g.tmdbId != null &&
+      target.tmdbId != null &&
+      (g.tmdbId !== target.tmdbId || g.mediaType !== target.mediaType)
+  );
+}
+
+export interface MergeGroupsResult {
+  target: Group;
+  merged: number;
+  filesMoved: number;
+  /** Set instead of merging when sources are matched to another title and `force` wasn't given */
+  conflicts?: Group[];
+}
+
+/**
+ * Move every file of the source groups into the target and delete the
+ * sources. The target keeps its match, and moved files take it on; a target
+ * that was confirmed or done goes back to Matched so the new files get reviewed.
+ * Skipped, completed and failed files keep their status. Sources matched to a
+ * different title are only merged with `force`.
+ */
+export function mergeGroups(targetId: number, sourceIds: number[], force = false): MergeGroupsResult {
+  const target = db.select().from(groups).where(eq(groups.id, targetId)).get();
+  if (!target || target.deletedAt) throw new Error("Group not found");
+  const sources = db
+    .select()
+    .from(groups)
+    .where(and(inArray(groups.id, sourceIds.filter((id) => id !== targetId)), isNull(groups.deletedAt)))
+    .all();
+  if (sources.length === 0) throw new Error("Pick at least one other group to merge");
+  if ([target, ...sources].some(isBusy)) {
+    throw new Error("A group is transferring; wait for it to finish");
+  }
+  const locked = sources.filter((g) => g.locked);
+  if (locked.length > 0) {
+    throw new Error(`Unlock ${locked.map((g) => g.folderName).join(", ")} to merge it away`);
+  }
+
+  const conflicts = conflictingMatches(target, sources);
+  if (conflicts.length > 0 && !force) {
+    return { target, merged: 0, filesMoved: 0, conflicts };
+  }
+
+  const now = new Date().toISOString();
+  const matched = target.tmdbId != null;
+  let filesMoved = 0;
+  const moved = {
+    groupId: target.id,
+    ...(matched ? { mediaType: target.mediaType } : {}),
+    tmdbId: target.tmdbId,
+    tmdbTitle: target.tmdbTitle,
+    tmdbYear: target.tmdbYear,
+    tmdbPosterPath: target.tmdbPosterPath,
+    // Episode titles belong to the old match; refetched for the target's
+    tmdbEpisodeTitle: null,
+    matchConfidence: target.matchConfidence,
+    updatedAt: now,
+  };
+  for (const source of sources) {
+    filesMoved += db
+      .update(jobs)
+      .set({ ...moved, status: matched ? "matched" : "scanned" })
+      .where(and(eq(jobs.groupId, source.id), notInArray(jobs.status, [...KEPT_JOB_STATUSES])))
+      .run().changes;
+    filesMoved += db
+      .update(jobs)
+      .set(moved)
+      .where(eq(jobs.groupId, source.id))
+      .run().changes;
+    copyLabels(source.id, target.id);
+    // Its candidates and label links go with it
+    db.delete(groups).where(eq(groups.id, source.id)).run();
+  }
+
+  const live = refreshTotals(target.id, now);
+  const updates: Partial<Group> = {};
+  if (target.status === "confirmed" || target.status === "completed") updates.status = "matched";
+  if (!matched) updates.mediaType = determineMediaType(live);
+  if (Object.keys(updates).length > 0) {
+    db.update(groups).set(updates).where(eq(groups.id, target.id)).run();
+  }
+
+  const updated = db.select().from(groups).where(eq(groups.id, target.id)).get()!;
+  return { target: updated, merged: sources.length, filesMoved };
+}
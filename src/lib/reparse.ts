@@ -0,0 +1,153 @@
+import { db } from "@/lib/db";
+import { groups, jobs, matchCandidates } from "@/lib/db/schema";
+import { and, eq, isNull } from "drizzle-orm";
+import { parseBareEpisodeNumber, parseFileName, parseFolderName } from "@/lib/parser";
+import type { Group } from "@/lib/db/schema";
+
+// Re-runs the parser over the folder and file names already in the database,
+// so parser or rename-rule improvements reach an existing library without
+// walking the disk again. Matches survive unless the parsed title or year
+// changed; renumbered episodes only lose their (now stale) episode title.
+// Titles and episode numbers fixed by hand are kept as they are.
+
+export interface ReparseResult {
+  groups: number;
+  files: number;
+  /** Groups whose title changed enough that they were reset for matching */
+  reset: number;
+  lockedSkipped: number;
+  /** Matched TV groups with renumbered episodes, whose titles need fetching again */
+  renumberedGroupIds: number[];
+}
+
+// Statuses whose match hasn't been signed off yet; past these a changed title
+// updates the parsed fields but leaves the match alone
+const RESETTABLE_STATUSES = new Set(["scanned", "matched", "ambiguous", "unmatched"]);
+
+// Groups with files in flight aren't touched
+const BUSY_STATUSES = new Set(["queued", "transferring"]);
+
+function normalizeTitle(title: string | null): string {
+  return (title ?? "").trim().toLowerCase();
+}
+
+function resetMatch(groupId: number, now: string) {
+  db.update(groups)
+    .set({
+      status: "scanned",
+      tmdbId: null,
+      tmdbTitle: null,
+      tmdbYear: null,
+      tmdbPosterPath: null,
+      tmdbCollection: null,
+      matchConfidence: null,
+      matchError: null,
+      updatedAt: now,
+    })
+    .where(eq(groups.id, groupId))
+    .run();
+  db.update(jobs)
+    .set({
+      status: "scanned",
+      tmdbId: null,
+      tmdbTitle: null,
+      tmdbYear: null,
+      tmdbPosterPath: null,
+      tmdbEpisodeTitle: null,
+      matchConfidence: null,
+      updatedAt: now,
+    })
+    .where(eq(jobs.groupId, groupId))
+    .run();
+  db.delete(matchCandidates).where(eq(matchCandidates.groupId, groupId)).run();
+}
+
+/** Re-parse one group's files; returns how many changed and whether any were renumbered. */
+function reparseFiles(group: Group, now: string): { files: number; renumbered: boolean } {
+  let files = 0;
+  let renumbered = false;
+  const groupJobs = db
+    .select()
+    .from(jobs)
+    .where(and(eq(jobs.groupId, group.id), isNull(jobs.deletedAt)))
+    .all();
+
+  for (const job of groupJobs) {
+    const parsed = parseFileName(job.fileName);
+    // Numbers fixed by hand win; otherwise a season or episode the name doesn't
+    // carry came from the folder, so it's kept
+    const season = job.numbersEdited ? job.parsedSeason : (parsed.season ?? job.parsedSeason);
+    const episode = job.numbersEdited
+      ? job.parsedEpisode
+      : (parsed.episode ??
+        (season != null ? parseBareEpisodeNumber(job.fileName) : undefined) ??
+        job.parsedEpisode);
+
+    const updates = {
+      parsedTitle: parsed.title,
+      parsedYear: parsed.year ?? null,
+      parsedSeason: season ?? null,
+      parsedEpisode: episode ?? null,
+      parsedQuality: parsed.quality ?? null,
+      parsedCodec: parsed.codec ?? null,
+      parsedHdr: parsed.hdr ?? null,
+      parsedPart: parsed.part ?? null,
+    };
+    const changed = (Object.keys(updates) as (keyof typeof updates)[]).some(
+      (key) => (job[key] ?? null) !== updates[key]
+    );
+    if (!changed) continue;
+
+    const numberChanged =
+      updates.parsedSeason !== job.parsedSeason || updates.parsedEpisode !== job.parsedEpisode;
+    if (numberChanged) renumbered = true;
+    db.update(jobs)
+      .set({
+        ...updates,
+        ...(numberChanged ? { tmdbEpisodeTitle: null } : {}),
+        updatedAt: now,
+      })
+      .where(eq(jobs.id, job.id))
+      .run();
+    files++;
+  }
+  return { files, renumbered };
+}
+
+export function reparseAll(): ReparseResult {
+  const now = new Date().toISOString();
+  const result: ReparseResult = { groups: 0, files: 0, reset: 0, lockedSkipped: 0, renumberedGroupIds: [] };
+
+  for (const group of db.select().from(groups).where(isNull(groups.deletedAt)).all()) {
+    if (group.locked) {
+      result.lockedSkipped++;
+      continue;
+    }
+    if (BUSY_STATUSES.has(group.status)) continue;
+
+    const parsedFolder = parseFolderName(group.folderName);
+    const titleChanged =
+      !group.titleEdited &&
+      (normalizeTitle(parsedFolder.title) !== normalizeTitle(group.parsedTitle) ||
+        (parsedFolder.year ?? null) !== group.parsedYear);
+    if (titleChanged) {
+      db.update(groups)
+        .set({ parsedTitle: parsedFolder.title, parsedYear: parsedFolder.year ?? null, updatedAt: now })
+        .where(eq(groups.id, group.id))
+        .run();
+    }
+
+    const { files, renumbered } = reparseFiles(group, now);
+    const reset = titleChanged && group.tmdbId != null && RESETTABLE_STATUSES.has(group.status);
+    if (reset) {
+      resetMatch(group.id, now);
+      result.reset++;
+    } else if (renumbered && group.tmdbId && group.mediaType === "tv") {
+      result.renumberedGroupIds.push(group.id);
+    }
+
+    if (titleChanged || files > 0) result.groups++;
+    result.files += files;
+  }
+  return result;
+}
@@ -0,0 +1,164 @@
+import fs from "fs";
+import os from "os";
+import path from "path";
+import { afterEach, describe, expect, it } from "vitest";
+import { determineMediaType, scanDirectoryGrouped } from "./scanner";
+import { parseBareEpisodeNumber } from "./parser";
+
+let root = "";
+
+// Lay out a source tree of placeholder files under a fresh temp dir
+function fixture(files: string[]): string {
+  root = fs.mkdtempSync(path.join(os.tmpdir(), "reelname-scan-"));
+  for (const file of files) {
+    const full = path.join(root, file);
+    fs.mkdirSync(path.dirname(full), { recursive: true });
+    fs.writeFileSync(full, Buffer.alloc(16));
+  }
+  return root;
+}
+
+afterEach(() => {
+  if (root) fs.rmSync(root, { recursive: true, force: true });
+  root = "";
+});
+
+// "folder | name | files" per group, sorted since readdir order isn't fixed
+function summarize(dir: string, groups: ReturnType<typeof scanDirectoryGrouped>["groups"]) {
+  return groups
+    .map((g) => {
+      const files = g.files.map((f) => f.fileName).sort();
+      return `${path.relative(dir, g.folderPath) || "."} | ${g.folderName} | ${files.join(", ")}`;
+    })
+    .sort();
+}
+
+describe("scanDirectoryGrouped with groupByParsedTitle", () => {
+  it("splits a flat dump into one group per title", () => {
+    const dir = fixture([
+      "Dump/Show.A.S01E01.mkv",
+      "Dump/Show.A.S01E02.mkv",
+      "Dump/Other.Show.S02E05.mkv",
+      "Dump/Some.Movie.2019.1080p.mkv",
+      "Dump/poster.jpg",
+    ]);
+    const { groups } = scanDirectoryGrouped(dir, { groupByParsedTitle: true });
+    expect(summarize(dir, groups)).toEqual([
+      "Dump | Other Show | Other.Show.S02E05.mkv",
+      "Dump | Show A | Show.A.S01E01.mkv, Show.A.S01E02.mkv, poster.jpg",
+      "Dump | Some Movie (2019) | Some.Movie.2019.1080p.mkv",
+    ]);
+    const movie = groups.find((g) => g.folderName === "Some Movie (2019)");
+    expect(movie?.files[0].fileCategory).toBe("movie");
+  });
+
+  it("pools loose files in the scan root by title", () => {
+    const dir = fixture(["Loose.Show.S01E01.mkv", "Loose.Show.S01E02.mkv", "Film.2010.mkv"]);
+    const { groups } = scanDirectoryGrouped(dir, { groupByParsedTitle: true });
+    expect(summarize(dir, groups)).toEqual([
+      ". | Film (2010) | Film.2010.mkv",
+      ". | Loose Show | Loose.Show.S01E01.mkv, Loose.Show.S01E02.mkv",
+    ]);
+  });
+
+  it("keeps the folder as one group when off", () => {
+    const dir = fixture(["Dump/Show.A.S01E01.mkv", "Dump/Other.Show.S02E05.mkv"]);
+    const { groups } = scanDirectoryGrouped(dir);
+    expect(summarize(dir, groups)).toEqual(["Dump | Dump | Other.Show.S02E05.mkv, Show.A.S01E01.mkv"]);
+  });
+});
+
+describe("scanDirectoryGrouped subfolders", () => {
+  // "path | category | season | extra type" per file
+  function categorize(dir: string, groups: ReturnType<typeof scanDirectoryGrouped>["groups"]) {
+    return groups
+      .flatMap((g) => g.files)
+      .map((f) => `${path.relative(dir, f.sourcePath)} | ${f.fileCategory} | ${f.detectedSeason} | ${f.extraType}`)
+      .sort();
+  }
+
+  it("reads specials folders however they're spelled", () => {
+    const dir = fixture([
+      "Show/Season 01/Show.S01E01.mkv",
+      "Show/Specials/Show.S00E01.mkv",
+      "Show/Season.00/Behind.mkv",
+      "Show/S00/Other.mkv",
+    ]);
+    const { groups } = scanDirectoryGrouped(dir);
+    expect(categorize(dir, groups)).toEqual([
+      "Show/S00/Other.mkv | special | 0 | null",
+      "Show/Season 01/Show.S01E01.mkv | episode | 1 | null",
+      "Show/Season.00/Behind.mkv | special | 0 | null",
+      "Show/Specials/Show.S00E01.mkv | special | 0 | null",
+    ]);
+  });
+
+  it("lets a nested extras folder override its season", () => {
+    const dir = fixture(["Show/Season 01/Show.S01E01.mkv", "Show/Season 01/Extras/Making Of.mkv"]);
+    const { groups } = scanDirectoryGrouped(dir);
+    expect(categorize(dir, groups)).toEqual([
+      "Show/Season 01/Extras/Making Of.mkv | extra | null | other",
+      "Show/Season 01/Show.S01E01.mkv | episode | 1 | null",
+    ]);
+  });
+});
+
+describe("scanDirectoryGrouped symlinks", () => {
+  it("terminates on a symlink loop when following links", () => {
+    const dir = fixture(["Show/Season 01/Show.S01E01.mkv"]);
+    fs.symlinkSync(path.join(dir, "Show"), path.join(dir, "Show/Season 01/loop"));
+    const { groups } = scanDirectoryGrouped(dir, { followSymlinks: true });
+    expect(summarize(dir, groups)).toEqual(["Show | Show | Show.S01E01.mkv"]);
+  });
+
+  it("skips symlinks by default", () => {
+    const dir = fixture(["Show/Show.S01E01.mkv"]);
+    fs.symlinkSync(path.join(dir, "Show"), path.join(dir, "Linked"));
+    const { groups } = scanDirectoryGrouped(dir);
+    expect(summarize(dir, groups)).toEqual(["Show | Show | Show.S01E01.mkv"]);
+  });
+});
+
+describe("determineMediaType", () => {
+  it("calls a group with any episode or special TV", () => {
+    expect(determineMediaType([{ fileCategory: "episode" }])).toBe("tv");
+    expect(determineMediaType([{ fileCategory: "special" }, { fileCategory: "extra" }])).toBe("tv");
+    expect(determineMediaType([{ fileCategory: "movie" }, { fileCategory: "episode" }])).toBe("tv");
+  });
+
+  it("calls a group of movie files a movie", () => {
+    expect(determineMediaType([{ fileCategory: "movie" }, { fileCategory: "extra" }])).toBe("movie");
+  });
+
+  it("ignores extras and artwork", () => {
+    expect(determineMediaType([{ fileCategory: "extra" }, { fileCategory: "metadata" }])).toBe("unknown");
+    expect(determineMediaType([])).toBe("unknown");
+  });
+});
+
+describe("scanDirectoryGrouped season packs", () => {
+  it("takes the season from the folder and episodes from bare numbers", () => {
+    const dir = fixture(["Show.Name.S02.1080p/01.mkv", "Show.Name.S02.1080p/02.mkv"]);
+    const { groups } = scanDirectoryGrouped(dir);
+    const files = groups[0].files
+      .map((f) => ({ season: f.detectedSeason, episode: parseBareEpisodeNumber(f.fileName) }))
+      .sort((a, b) => (a.episode ?? 0) - (b.episode ?? 0));
+    expect(files).toEqual([
+      { season: 2, episode: 1 },
+      { season: 2, episode: 2 },
+    ]);
+  });
+});
+
+describe("scanDirectoryGrouped extra suffixes", () => {
+  it("files suffixed extras next to the movie", () => {
+    const dir = fixture(["Movie (2010)/Movie.mkv", "Movie (2010)/Movie-trailer.mkv", "Movie (2010)/Movie-behindthescenes.mkv"]);
+    const { groups } = scanDirectoryGrouped(dir);
+    const files = groups[0].files.map((f) => `${f.fileName} | ${f.fileCategory} | ${f.extraType}`).sort();
+    expect(files).toEqual([
+      "Movie-behindthescenes.mkv | extra | behind_the_scenes",
+      "Movie-trailer.mkv | extra | trailers",
+      "Movie.mkv | movie | null",
+    ]);
+  });
+});
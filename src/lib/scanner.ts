@@ -1,5 +1,7 @@
 import fs from "fs";
 import path from "path";
+import { parseFolderSeason, detectExtraType, parsePart, parseFileName } from "./parser";
+import { detectMetadataFile, pickMetadataFiles } from "./artwork";
 
 const VIDEO_EXTENSIONS = new Set([
   ".mkv", ".mp4", ".avi", ".mov", ".wmv", ".flv", ".m4v",
@@ -7,7 +9,9 @@ const VIDEO_EXTENSIONS = new Set([
 ]);
 
 const SEASON_FOLDER_PATTERN = /^(?:Season\s*|S)(\d+)$/i;
-const SPECIALS_FOLDER_NAMES = new Set(["specials", "season 0", "season 00", "season0", "season00"]);
+const SPECIALS_FOLDER_NAMES = new Set([
+  "specials", "special", "season 0", "season 00", "season0", "season00", "s0", "s00",
+]);
 
 const EXTRA_FOLDER_MAP: Record<string, string> = {
   "extras": "other",
@@ -36,7 +40,7 @@ export interface ScannedFile {
   fileExtension: string;
 }
 
-export type FileCategory = "episode" | "movie" | "special" | "extra";
+export type FileCategory = "episode" | "movie" | "special" | "extra" | "metadata";
 
 export interface ScannedGroupFile {
   sourcePath: string;
@@ -54,6 +58,103 @@ export interface ScannedGroup {
   files: ScannedGroupFile[];
 }
 
+/** Directory levels below a scan root that are walked when no limit is set. */
+export const DEFAULT_SCAN_MAX_DEPTH = 8;
+
+/** Files named like episodes (S01E01, 1x01, E01) a folder needs to be read as TV. */
+export const DEFAULT_MIN_EPISODE_FILES = 1;
+
+/** Files without episode numbers a folder needs before they're read as a series. */
+export const DEFAULT_MIN_UNNUMBERED_EPISODES = 3;
+
+export interface ScanOptions {
+  groupByParsedTitle?: boolean;
+  /** Directory levels below the scan root to descend into; a group folder is level 1 */
+  maxDepth?: number;
+  /** Follow symlinked files and folders. Off by default */
+  followSymlinks?: boolean;
+  /** Episode-numbered files that make a folder without season structure TV */
+  minEpisodeFiles?: number;
+  /** Unnumbered files that make such a folder a series rather than a movie */
+  minUnnumberedEpisodes?: number;
+}
+
+/** A file or folder the scan couldn't read and left out. */
+export interface SkippedPath {
+  path: string;
+  reason: string;
+}
+
+export interface ScanResult {
+  groups: ScannedGroup[];
+  skipped: SkippedPath[];
+}
+
+interface WalkState {
+  maxDepth: number;
+  followSymlinks: boolean;
+  /** Real paths of folders already walked, so symlink loops terminate */
+  visited: Set<string>;
+  skipped: SkippedPath[];
+}
+
+function describeFsError(err: unknown): string {
+  const code = (err as NodeJS.ErrnoException)?.code;
+  if (code === "EACCES" || code === "EPERM") return "permission denied";
+  if (code === "ENOENT") return "no longer exists";
+  return err instanceof Error ? err.message : String(err);
+}
+
+// One unreadable entry shouldn't abort the scan: record it and move on
+
+function readDir(dir: string, walk: WalkState): fs.Dirent[] {
+  try {
+    return fs.readdirSync(dir, { withFileTypes: true });
+  } catch (err) {
+    walk.skipped.push({ path: dir, reason: describeFsError(err) });
+    return [];
+  }
+}
+
+function fileSize(filePath: string, walk: WalkState): number | null {
+  try {
+    return fs.statSync(filePath).size;
+  } catch (err) {
+    walk.skipped.push({ path: filePath, reason: describeFsError(err) });
+    return null;
+  }
+}
+
+/**
+ * Resolve what a directory entry should be walked as. Symlinks are skipped
+ * unless following is enabled; followed folders are only entered once by
+ * real path, which breaks loops and stops the same tree being scanned twice.
+ */
+function entryKind(fullPath: string, entry: fs.Dirent, walk: WalkState): "dir" | "file" | null {
+  let kind: "dir" | "file" | null = entry.isDirectory() ? "dir" : entry.isFile() ? "file" : null;
+  if (entry.isSymbolicLink()) {
+    if (!walk.followSymlinks) return null;
+    try {
+      const stat = fs.statSync(fullPath);
+      kind = stat.isDirectory() ? "dir" : stat.isFile() ? "file" : null;
+    } catch {
+      return null; // dangling link
+    }
+  }
+  if (kind === "dir" && walk.followSymlinks) {
+    let real: string;
+    try {
+      real = fs.realpathSync(fullPath);
+    } catch (err) {
+      walk.skipped.push({ path: fullPath, reason: describeFsError(err) });
+      return null;
+    }
+    if (walk.visited.has(real)) return null;
+    walk.visited.add(real);
+  }
+  return kind;
+}
+
 function collectVideoFiles(dir: string): ScannedFile[] {
   const results: ScannedFile[] = [];
   const entries = fs.readdirSync(dir, { withFileTypes: true });
@@ -84,7 +185,8 @@ function classifySubfolder(
   fileCategory: FileCategory;
   extraType: string | null;
 } {
-  const lower = folderName.toLowerCase().trim();
+  // "Season.00" and "Season_00" read the same as "Season 00"
+  const lower = folderName.toLowerCase().replace(/[._]+/g, " ").trim();
 
   // Check specials
   if (SPECIALS_FOLDER_NAMES.has(lower)) {
@@ -92,7 +194,7 @@ function classifySubfolder(
   }
 
   // Check season pattern
-  const seasonMatch = folderName.match(SEASON_FOLDER_PATTERN);
+  const seasonMatch = lower.match(SEASON_FOLDER_PATTERN);
   if (seasonMatch) {
     const seasonNum = parseInt(seasonMatch[1], 10);
     if (seasonNum === 0) {
@@ -107,17 +209,233 @@ function classifySubfolder(
     return { detectedSeason: null, fileCategory: "extra", extraType };
   }
 
+  // Release-named season folders: "Show.Name.S02.1080p"
+  const packSeason = parseFolderSeason(folderName);
+  if (packSeason !== undefined) {
+    return packSeason === 0
+      ? { detectedSeason: 0, fileCategory: "special", extraType: null }
+      : { detectedSeason: packSeason, fileCategory: "episode", extraType: null };
+  }
+
   return { detectedSeason: null, fileCategory: "episode", extraType: null };
 }
 
-export function scanDirectoryGrouped(dirPath: string): ScannedGroup[] {
+type FolderClassification = ReturnType<typeof classifySubfolder>;
+
+/**
+ * Collect the video files under a subfolder with its classification. Nested
+ * folders that are themselves specials, seasons, or extras ("Season 01/Extras",
+ * "Extras/Featurettes") override what they inherit; anything else inherits.
+ */
+function collectClassifiedFiles(
+  dir: string,
+  classification: FolderClassification,
+  walk: WalkState,
+  depth: number
+): ScannedGroupFile[] {
+  const results: ScannedGroupFile[] = [];
+  for (const entry of readDir(dir, walk)) {
+    const fullPath = path.join(dir, entry.name);
+    const kind = entryKind(fullPath, entry, walk);
+    if (kind === "dir") {
+      if (depth >= walk.maxDepth) continue;
+      const nested = classifySubfolder(entry.name);
+      const recognized = nested.fileCategory !== "episode" || nested.detectedSeason !== null;
+      results.push(
+        ...collectClassifiedFiles(fullPath, recognized ? nested : classification, walk, depth + 1)
+      );
+    } else if (kind === "file") {
+      const ext = path.extname(entry.name).toLowerCase();
+      const size = VIDEO_EXTENSIONS.has(ext) ? fileSize(fullPath, walk) : null;
+      if (size !== null) {
+        results.push({
+          sourcePath: fullPath,
+          fileName: entry.name,
+          fileSize: size,
+          fileExtension: ext,
+          ...classification,
+        });
+      }
+    }
+  }
+  return results;
+}
+
+/** Reclassify files whose names carry an extra suffix ("-trailer", "-featurette", ...). */
+function applyExtraSuffix(file: ScannedGroupFile): ScannedGroupFile {
+  if (file.fileCategory === "extra") return file;
+  const extraType = detectExtraType(file.fileName);
+  if (!extraType) return file;
+  return { ...file, detectedSeason: null, fileCategory: "extra", extraType };
+}
+
+/**
+ * Split the scan_path setting into its roots. Several roots are stored one per
+ * line; a single path (the old format) is just one line.
+ */
+export function parseScanPaths(value: string | null | undefined): string[] {
+  return [...new Set((value || "").split(/\r?\n/).map((p) => p.trim()).filter(Boolean))];
+}
+
+/** Why `dir` can't be used as a scan root, or null if it's a readable folder. */
+export function scanPathError(dir: string): string | null {
+  try {
+    if (!fs.statSync(dir).isDirectory()) return "Not a folder";
+    fs.accessSync(dir, fs.constants.R_OK);
+    return null;
+  } catch (err) {
+    const code = (err as NodeJS.ErrnoException)?.code;
+    if (code === "ENOENT" || code === "ENOTDIR") return "Folder not found";
+    return describeFsError(err);
+  }
+}
+
+/**
+ * Stem shared by every file when they are the parts of one movie
+ * ("Movie.CD1.avi", "Movie.CD2.avi"), or null if they aren't.
+ */
+function stackedMovieStem(fileNames: string[]): string | null {
+  if (fileNames.length < 2) return null;
+  const parts = fileNames.map(parsePart);
+  if (parts.some((p) => !p)) return null;
+  const stems = new Set(parts.map((p) => p!.stem.toLowerCase()));
+  const numbers = new Set(parts.map((p) => p!.part));
+  return stems.size === 1 && numbers.size === fileNames.length ? parts[0]!.stem : null;
+}
+
+/**
+ * Split a group by the title parsed from each file name instead of trusting
+ * the folder, for flat dumps where one folder holds several shows or movies.
+ * Extras ride along with the largest resulting group.
+ */
+function regroupByParsedTitle(group: ScannedGroup): ScannedGroup[] {
+  const byTitle = new Map<string, ScannedGroup>();
+  const extras: ScannedGroupFile[] = [];
+
+  for (const file of group.files) {
+    if (file.fileCategory === "extra" || file.fileCategory === "metadata") {
+      extras.push(file);
+      continue;
+    }
+    const parsed = parseFileName(file.fileName);
+    // Bare "01.mkv"-style names carry no title; keep those with their folder
+    // (loose files in the root have no folder, so they keep their own name)
+    const untitled = !/[^\d\s]/.test(parsed.title);
+    const fallbackName = group.folderName || file.fileName.replace(/\.[^.]+$/, "");
+    const key = untitled ? `\0${fallbackName}` : parsed.title.toLowerCase();
+    if (!byTitle.has(key)) {
+      byTitle.set(key, {
+        folderPath: group.folderPath,
+        // The year lets parseFolderName pick it up for matching
+        folderName: untitled
+          ? fallbackName
+          : parsed.year ? `${parsed.title} (${parsed.year})` : parsed.title,
+        files: [],
+      });
+    }
+    // Without a season folder, the file name alone decides episode vs movie
+    const numbered = parsed.season !== undefined || parsed.episode !== undefined;
+    byTitle.get(key)!.files.push(
+      file.detectedSeason === null ? { ...file, fileCategory: numbered ? "episode" : "movie" } : file
+    );
+  }
+
+  const regrouped = [...byTitle.values()];
+  if (regrouped.length === 0) return [group];
+
+  for (const g of regrouped) {
+    // Several unnumbered files under one title are episodes unless they're
+    // the parts of one movie
+    const unnumbered = g.files.filter((f) => f.fileCategory === "movie");
+    if (g.files.length > 1 && unnumbered.length > 0 &&
+        !stackedMovieStem(g.files.map((f) => f.fileName))) {
+      for (const f of unnumbered) f.fileCategory = "episode";
+    }
+  }
+
+  // Extras and artwork ride along with the largest group
+  regrouped.sort((a, b) => b.files.length - a.files.length)[0].files.push(...extras);
+  return regrouped;
+}
+
+/**
+ * Decide episode vs movie for the files of a folder with no season folders
+ * and no season in its name, from each file name rather than the file count.
+ * Numbered files make it TV, even a lone pilot. Otherwise a few unnumbered
+ * files are one movie (the largest) plus bonus features, and enough of them
+ * are a series whose episodes are numbered later.
+ */
+function classifyFlatFolder(
+  mainFiles: ScannedGroupFile[],
+  { minEpisodeFiles, minUnnumberedEpisodes }: { minEpisodeFiles: number; minUnnumberedEpisodes: number }
+) {
+  const numbered = mainFiles.filter((f) => {
+    const parsed = parseFileName(f.fileName);
+    return parsed.season !== undefined || parsed.episode !== undefined;
+  });
+  if (numbered.length > 0 && numbered.length >= minEpisodeFiles) return;
+
+  if (stackedMovieStem(mainFiles.map((f) => f.fileName))) {
+    // CD1/CD2-style parts of a single movie
+    for (const f of mainFiles) f.fileCategory = "movie";
+    return;
+  }
+  if (mainFiles.length >= minUnnumberedEpisodes) return;
+
+  const [movie, ...rest] = [...mainFiles].sort((a, b) => b.fileSize - a.fileSize);
+  movie.fileCategory = "movie";
+  for (const f of rest) {
+    f.fileCategory = "extra";
+    f.extraType = detectExtraType(f.fileName) ?? "other";
+  }
+}
+
+/**
+ * A group's media type from its classified files, the one rule every caller
+ * uses. Extras and artwork ride along with either type and don't count:
+ * - any episode or special → "tv", even alongside a movie-looking file
+ * - only movies → "movie"
+ * - nothing but extras → "unknown"
+ */
+export function determineMediaType(
+  files: Pick<ScannedGroupFile, "fileCategory">[]
+): "movie" | "tv" | "unknown" {
+  const mainFiles = files.filter((f) => f.fileCategory !== "extra" && f.fileCategory !== "metadata");
+  if (mainFiles.some((f) => f.fileCategory === "episode" || f.fileCategory === "special")) {
+    return "tv";
+  }
+  return mainFiles.length > 0 ? "movie" : "unknown";
+}
+
+export function scanDirectoryGrouped(
+  dirPath: string,
+  options: ScanOptions = {}
+): ScanResult {
+  const thresholds = {
+    minEpisodeFiles: options.minEpisodeFiles && options.minEpisodeFiles > 0
+      ? options.minEpisodeFiles : DEFAULT_MIN_EPISODE_FILES,
+    minUnnumberedEpisodes: options.minUnnumberedEpisodes && options.minUnnumberedEpisodes > 0
+      ? options.minUnnumberedEpisodes : DEFAULT_MIN_UNNUMBERED_EPISODES,
+  };
+  const walk: WalkState = {
+    maxDepth: options.maxDepth && options.maxDepth > 0 ? options.maxDepth : DEFAULT_SCAN_MAX_DEPTH,
+    followSymlinks: options.followSymlinks ?? false,
+    visited: new Set(),
+    skipped: [],
+  };
+  if (walk.followSymlinks && fs.existsSync(dirPath)) walk.visited.add(fs.realpathSync(dirPath));
+
   const groups: ScannedGroup[] = [];
-  const entries = fs.readdirSync(dirPath, { withFileTypes: true });
+  const entries = readDir(dirPath, walk);
+  // Loose files in the scan root, keyed by their part-less stem so the parts
+  // of a split movie land in one group
+  const looseFiles = new Map<string, ScannedGroupFile[]>();
 
   for (const entry of entries) {
     const fullPath = path.join(dirPath, entry.name);
+    const kind = entryKind(fullPath, entry, walk);
 
-    if (entry.isDirectory()) {
+    if (kind === "dir") {
       const group: ScannedGroup = {
         folderPath: fullPath,
         folderName: entry.name,
@@ -125,81 +443,128 @@ export function scanDirectoryGrouped(dirPath: string): ScannedGroup[] {
       };
 
       // Walk the group folder
-      const subEntries = fs.readdirSync(fullPath, { withFileTypes: true });
-      let hasSeasonFolders = false;
+      const subEntries = readDir(fullPath, walk);
+      // Artwork and nfo files at the group root, kept only if the folder has video
+      const metadataFiles: ScannedGroupFile[] = [];
+      // Season packs name the group folder itself: "Show.Name.S02.1080p"
+      const folderSeason = parseFolderSeason(entry.name) ?? null;
+      let hasSeasonFolders = folderSeason !== null;
 
       for (const sub of subEntries) {
         const subPath = path.join(fullPath, sub.name);
+        const subKind = entryKind(subPath, sub, walk);
 
-        if (sub.isDirectory()) {
+        if (subKind === "dir") {
+          if (walk.maxDepth < 2) continue;
           const classification = classifySubfolder(sub.name);
           if (classification.fileCategory === "episode" && classification.detectedSeason !== null) {
             hasSeasonFolders = true;
           }
 
           // Collect all video files in this subfolder
-          const files = collectVideoFiles(subPath);
-          for (const file of files) {
-            group.files.push({
-              ...file,
-              detectedSeason: classification.detectedSeason,
-              fileCategory: classification.fileCategory,
-              extraType: classification.extraType,
-            });
+          for (const file of collectClassifiedFiles(subPath, classification, walk, 2)) {
+            group.files.push(applyExtraSuffix(file));
           }
-        } else if (sub.isFile()) {
+        } else if (subKind === "file") {
           const ext = path.extname(sub.name).toLowerCase();
-          if (VIDEO_EXTENSIONS.has(ext)) {
-            const stat = fs.statSync(subPath);
-            group.files.push({
+          const size = VIDEO_EXTENSIONS.has(ext) ? fileSize(subPath, walk) : null;
+          if (size !== null) {
+            group.files.push(applyExtraSuffix({
               sourcePath: subPath,
               fileName: sub.name,
-              fileSize: stat.size,
+              fileSize: size,
               fileExtension: ext,
-              detectedSeason: null, // will be inferred from filename later
-              fileCategory: "episode", // default, may be reclassified
+              detectedSeason: folderSeason, // otherwise inferred from filename later
+              fileCategory: folderSeason === 0 ? "special" : "episode", // default, may be reclassified
               extraType: null,
-            });
+            }));
+          } else if (detectMetadataFile(sub.name)) {
+            const metadataSize = fileSize(subPath, walk);
+            if (metadataSize !== null) {
+              metadataFiles.push({
+                sourcePath: subPath,
+                fileName: sub.name,
+                fileSize: metadataSize,
+                fileExtension: ext,
+                detectedSeason: null,
+                fileCategory: "metadata",
+                extraType: null,
+              });
+            }
           }
         }
       }
 
       if (group.files.length > 0) {
-        // Media type heuristic: if season folders exist or multiple files → TV
-        // Single file with no season folders → movie
-        if (!hasSeasonFolders && group.files.length === 1 &&
-            group.files.every((f) => f.fileCategory === "episode")) {
-          // Single file, no season structure → likely a movie
-          group.files[0].fileCategory = "movie";
+        // Season folders (or a season in the folder name) settle it as TV;
+        // otherwise the file names decide
+        const mainFiles = group.files.filter((f) => f.fileCategory !== "extra");
+        if (!hasSeasonFolders && mainFiles.length > 0 &&
+            mainFiles.every((f) => f.fileCategory === "episode")) {
+          classifyFlatFolder(mainFiles, thresholds);
         }
+        // One file per kind becomes poster.jpg etc.; a second poster would
+        // overwrite the first, so it goes along as an extra instead
+        const { kept, duplicates } = pickMetadataFiles(metadataFiles);
+        group.files.push(...kept);
+        group.files.push(
+          ...duplicates.map((f) => ({ ...f, fileCategory: "extra" as const, extraType: "other" }))
+        );
 
         groups.push(group);
       }
-    } else if (entry.isFile()) {
+    } else if (kind === "file") {
       // Loose file in scan root → single-file group (movie)
       const ext = path.extname(entry.name).toLowerCase();
-      if (VIDEO_EXTENSIONS.has(ext)) {
-        const stat = fs.statSync(fullPath);
-        groups.push({
-          folderPath: dirPath,
-          folderName: entry.name.replace(/\.[^.]+$/, ""),
-          files: [
-            {
-              sourcePath: fullPath,
-              fileName: entry.name,
-              fileSize: stat.size,
-              fileExtension: ext,
-              detectedSeason: null,
-              fileCategory: "movie",
-              extraType: null,
-            },
-          ],
-        });
+      const size = VIDEO_EXTENSIONS.has(ext) ? fileSize(fullPath, walk) : null;
+      if (size !== null) {
+        const stem = parsePart(entry.name)?.stem ?? entry.name.replace(/\.[^.]+$/, "");
+        const key = stem.toLowerCase();
+        looseFiles.set(key, [
+          ...(looseFiles.get(key) || []),
+          applyExtraSuffix({
+            sourcePath: fullPath,
+            fileName: entry.name,
+            fileSize: size,
+            fileExtension: ext,
+            detectedSeason: null,
+            fileCategory: "movie",
+            extraType: null,
+          }),
+        ]);
       }
     }
   }
 
-  return groups;
+  for (const files of looseFiles.values()) {
+    const stem = stackedMovieStem(files.map((f) => f.fileName));
+    if (stem) {
+      groups.push({ folderPath: dirPath, folderName: stem, files });
+      continue;
+    }
+    // Same stem but not a clean set of parts: keep them apart as before
+    for (const file of files) {
+      groups.push({
+        folderPath: dirPath,
+        folderName: file.fileName.replace(/\.[^.]+$/, ""),
+        files: [file],
+      });
+    }
+  }
+
+  if (!options.groupByParsedTitle) return { groups, skipped: walk.skipped };
+
+  // Loose files in the root are pooled so a flat dump of episodes regroups
+  // into one group per show
+  const folderGroups = groups.filter((g) => g.folderPath !== dirPath);
+  const looseGroups = groups.filter((g) => g.folderPath === dirPath);
+  const pooled = looseGroups.length > 0
+    ? [{ folderPath: dirPath, folderName: "", files: looseGroups.flatMap((g) => g.files) }]
+    : [];
+  return {
+    groups: [...folderGroups, ...pooled].flatMap(regroupByParsedTitle),
+    skipped: walk.skipped,
+  };
 }
 
 // Keep legacy flat scanner for backward compatibility
@@ -0,0 +1,29 @@
+import { describe, expect, it } from "vitest";
+import { redactSecrets, registerSecret } from "./secrets";
+import { classifySshError } from "./ssh";
+
+describe("redactSecrets", () => {
+  it("masks every occurrence of a registered secret", () => {
+    registerSecret("hunter2-passphrase");
+    expect(redactSecrets("bad passphrase hunter2-passphrase (hunter2-passphrase)")).toBe(
+      "bad passphrase *** (***)"
+    );
+  });
+
+  it("masks secrets passed for one call only", () => {
+    expect(redactSecrets("key s3cret-once failed", ["s3cret-once"])).toBe("key *** failed");
+    expect(redactSecrets("key s3cret-once failed")).toBe("key s3cret-once failed");
+  });
+
+  it("leaves very short values alone", () => {
+    registerSecret("ab");
+    expect(redactSecrets("tab and grab")).toBe("tab and grab");
+  });
+
+  it("applies to SSH error messages", () => {
+    registerSecret("open-sesame-42");
+    expect(classifySshError(new Error("Cannot parse key with open-sesame-42")).message).toBe(
+      "Connection failed: Cannot parse key with ***"
+    );
+  });
+});
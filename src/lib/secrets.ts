@@ -0,0 +1,99 @@
+import crypto from "crypto";
+import fs from "fs";
+import path from "path";
+import { DATA_DIR } from "./db/paths";
+
+// Secrets stored in the database (SSH key passphrases) are encrypted with
+// AES-256-GCM so a copied or shared reelname.db doesn't leak them. The key
+// lives outside the database: REELNAME_SECRET_KEY if set, otherwise a random
+// key generated once into secret.key in the data directory (owner-only).
+// Losing that key means re-entering the passphrases.
+
+const PREFIX = "enc:v1:";
+const KEY_FILE = path.join(DATA_DIR, "secret.key");
+const IV_BYTES = 12;
+const TAG_BYTES = 16;
+
+let cachedKey: Buffer | null = null;
+
+// Plaintext secrets this process has handled. Anything shown to the user or
+// logged goes through redactSecrets() so they come out as "***". Very short
+// values are skipped; masking every "ab" in a message would make it unreadable.
+const MIN_REDACT_LENGTH = 4;
+const knownSecrets = new Set<string>();
+
+export function registerSecret(secret: string | null | undefined) {
+  if (secret && secret.length >= MIN_REDACT_LENGTH) knownSecrets.add(secret);
+}
+
+/** Replace every known secret (plus any given ones) in `text` with "***". */
+export function redactSecrets(text: string, extra: Array<string | null | undefined> = []): string {
+  let result = text;
+  for (const secret of [...knownSecrets, ...extra]) {
+    if (!secret || secret.length < MIN_REDACT_LENGTH) continue;
+    result = result.split(secret).join("***");
+  }
+  return result;
+}
+
+function secretKey(): Buffer {
+  if (cachedKey) return cachedKey;
+
+  if (process.env.REELNAME_SECRET_KEY) {
+    cachedKey = crypto.createHash("sha256").update(process.env.REELNAME_SECRET_KEY).digest();
+    return cachedKey;
+  }
+
+  if (fs.existsSync(KEY_FILE)) {
+    cachedKey = Buffer.from(fs.readFileSync(KEY_FILE, "utf8").trim(), "base64");
+  } else {
+    cachedKey = crypto.randomBytes(32);
+    fs.mkdirSync(DATA_DIR, { recursive: true });
+    fs.writeFileSync(KEY_FILE, cachedKey.toString("base64"), { mode: 0o600 });
+  }
+  return cachedKey;
+}
+
+export function isEncryptedSecret(value: string): boolean {
+  return value.startsWith(PREFIX);
+}
+
+export function encryptSecret(plain: string): string {
+  registerSecret(plain);
+  const iv = crypto.randomBytes(IV_BYTES);
+  const cipher = crypto.createCipheriv("aes-256-gcm", secretKey(), iv);
+  const encrypted = Buffer.concat([cipher.update(plain, "utf8"), cipher.final()]);
+  return PREFIX + Buffer.concat([iv, cipher.getAuthTag(), encrypted]).toString("base64");
+}
+
+/** Decrypt a stored secret. Values saved before encryption are returned as-is. */
+export function decryptSecret(stored: string): string {
+  if (!isEncryptedSecret(stored)) {
+    registerSecret(stored);
+    return stored;
+  }
+  const data = Buffer.from(stored.slice(PREFIX.length), "base64");
+  try {
+    const decipher = crypto.createDecipheriv(
+      "aes-256-gcm",
+      secretKey(),
+      data.subarray(0, IV_BYTES)
+    );
+    decipher.setAuthTag(data.subarray(IV_BYTES, IV_BYTES + TAG_BYTES));
+    const plain = Buffer.concat([
+      decipher.update(data.subarray(IV_BYTES + TAG_BYTES)),
+      decipher.final(),
+    ]).toString("utf8");
+    registerSecret(plain);
+    return plain;
+  } catch {
+    throw new Error(
+      "Stored SSH key passphrase can't be decrypted (secret key changed?). Re-enter it in the destination settings."
+    );
+  }
+}
+
+/** A destination as sent to the browser: the passphrase never leaves the server. */
+export function withoutSecrets<T extends { sshKeyPassphrase?: string | null }>(dest: T): T {
+  return { ...dest, sshKeyPassphrase: null };
+}
@@ -0,0 +1,75 @@
+import { CANDIDATE_POSTER_SIZES } from "./theme";
+
+// Settings validation shared by the settings modal (inline errors, Save
+// disabled until valid) and PUT /api/settings, which rejects the same values.
+// Only keys present in `values` are checked, so partial updates such as the
+// page size from the pagination bar validate just what they change.
+
+export type SettingsErrors = Record<string, string>;
+
+// Characters Windows, macOS or SMB shares refuse in a folder name
+const UNSAFE_NAME_CHARS = /[<>:"/\\|?*\u0000-\u001f]/;
+
+function numberError(
+  value: string,
+  { min, max, integer = false, optional = false }: { min: number; max?: number; integer?: boolean; optional?: boolean }
+): string | null {
+  const trimmed = value.trim();
+  if (!trimmed) return optional ? null : "Required";
+  const n = Number(trimmed);
+  if (!Number.isFinite(n)) return "Must be a number";
+  if (integer && !Number.isInteger(n)) return "Must be a whole number";
+  if (n < min || (max !== undefined && n > max)) {
+    return max !== undefined ? `Must be between ${min} and ${max}` : `Must be at least ${min}`;
+  }
+  return null;
+}
+
+/** Why `name` can't be used as a folder name, or null if it can. */
+export function folderNameError(name: string): string | null {
+  const trimmed = name.trim();
+  if (!trimmed) return "Required";
+  if (trimmed === "." || trimmed === "..") return "Not a valid folder name";
+  if (UNSAFE_NAME_CHARS.test(trimmed)) return 'Can\'t contain < > : " / \\ | ? *';
+  if (/[. ]$/.test(name)) return "Can't end with a dot or space";
+  return null;
+}
+
+function urlError(value: string): string | null {
+  if (!value.trim()) return null;
+  try {
+    const url = new URL(value.trim());
+    return url.protocol === "http:" || url.protocol === "https:" ? null : "Must be an http(s) URL";
+  } catch {
+    return "Must be an http(s) URL";
+  }
+}
+
+const CHECKS: Record<string, (value: string) => string | null> = {
+  auto_match_threshold: (v) => numberError(v, { min: 0, max: 1 }),
+  auto_confirm_above: (v) => numberError(v, { min: 0, max: 1, optional: true }),
+  scan_max_depth: (v) => numberError(v, { min: 1, integer: true, optional: true }),
+  scan_min_episode_files: (v) => numberError(v, { min: 1, integer: true, optional: true }),
+  scan_min_unnumbered_episodes: (v) => numberError(v, { min: 1, integer: true, optional: true }),
+  season_pad_width: (v) => numberError(v, { min: 1, max: 4, integer: true, optional: true }),
+  episode_pad_width: (v) => numberError(v, { min: 1, max: 4, integer: true, optional: true }),
+  poster_cache_max_mb: (v) => numberError(v, { min: 0, optional: true }),
+  toast_max_visible: (v) => numberError(v, { min: 1, max: 20, integer: true, optional: true }),
+  ui_scale: (v) => numberError(v, { min: 0.75, max: 2, optional: true }),
+  poster_size: (v) =>
+    CANDIDATE_POSTER_SIZES.includes(v) ? null : `Must be one of ${CANDIDATE_POSTER_SIZES.join(", ")}`,
+  specials_folder_name: folderNameError,
+  extras_folder_name: folderNameError,
+  post_transfer_webhook: urlError,
+};
+
+export function validateSettings(values: Record<string, string | undefined>): SettingsErrors {
+  const errors: SettingsErrors = {};
+  for (const [key, check] of Object.entries(CHECKS)) {
+    const value = values[key];
+    if (value === undefined) continue;
+    const error = check(String(value));
+    if (error) errors[key] = error;
+  }
+  return errors;
+}
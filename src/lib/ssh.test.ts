@@ -0,0 +1,67 @@
+import { describe, expect, it } from "vitest";
+import { classifySshError, describeSftpError, mkdirRemote } from "./ssh";
+import type { SftpDirOps } from "./ssh";
+
+// A fake SFTP session over a set of existing directories. `denied` paths fail
+// mkdir with SFTP's permission-denied status (3).
+function fakeSftp(existing: string[], denied: string[] = []) {
+  const dirs = new Set(existing);
+  const created: string[] = [];
+  const sftp = {
+    mkdir(dir: string, cb: (err?: Error & { code?: number }) => void) {
+      if (denied.includes(dir)) return cb(Object.assign(new Error("Permission denied"), { code: 3 }));
+      if (dirs.has(dir)) return cb(Object.assign(new Error("Failure"), { code: 4 }));
+      dirs.add(dir);
+      created.push(dir);
+      cb();
+    },
+    stat(dir: string, cb: (err: Error | undefined, stats: { isDirectory(): boolean }) => void) {
+      if (dirs.has(dir)) cb(undefined, { isDirectory: () => true });
+      else cb(Object.assign(new Error("No such file"), { code: 2 }), { isDirectory: () => false });
+    },
+  };
+  return { sftp: sftp as unknown as SftpDirOps, created };
+}
+
+describe("mkdirRemote", () => {
+  it("creates each missing directory in order and skips existing ones", async () => {
+    const { sftp, created } = fakeSftp(["/media"]);
+    await mkdirRemote(sftp, "/media/TV/Show (2020)/Season 01");
+    expect(created).toEqual(["/media/TV", "/media/TV/Show (2020)", "/media/TV/Show (2020)/Season 01"]);
+  });
+
+  it("reports permission denied with the directory that failed", async () => {
+    const { sftp } = fakeSftp(["/media"], ["/media/TV"]);
+    await expect(mkdirRemote(sftp, "/media/TV/Show")).rejects.toThrow(
+      "Permission denied while trying to create directory /media/TV"
+    );
+  });
+});
+
+describe("describeSftpError", () => {
+  it("names a missing parent", () => {
+    expect(describeSftpError({ code: 2 }, "rename into", "/media/a.mkv")).toBe(
+      "Parent directory does not exist while trying to rename into /media/a.mkv"
+    );
+  });
+
+  it("falls back to the raw message", () => {
+    expect(describeSftpError(new Error("Failure"), "write", "/media/a.mkv")).toBe(
+      "Failed to write /media/a.mkv: Failure"
+    );
+  });
+});
+
+describe("classifySshError", () => {
+  it("separates auth failures from connection failures", () => {
+    expect(classifySshError({ level: "client-authentication" }).stage).toBe("auth");
+    expect(classifySshError(new Error("Encrypted private key detected, but no passphrase given")).stage).toBe("auth");
+    expect(classifySshError({ level: "client-timeout" }).stage).toBe("connect");
+  });
+
+  it("explains socket errors", () => {
+    expect(classifySshError({ code: "ECONNREFUSED", message: "connect ECONNREFUSED" }).message).toBe(
+      "Connection failed: connection refused (is SSH running on that port?)"
+    );
+  });
+});
@@ -0,0 +1,104 @@
+import type { SFTPWrapper } from "ssh2";
+import { redactSecrets } from "./secrets";
+
+// Shared helpers for turning ssh2 failures into messages users can act on.
+// Messages are passed through redactSecrets() since ssh2 errors can echo input.
+
+export type SshFailureStage = "connect" | "auth" | "path";
+
+const STAGE_LABELS: Record<SshFailureStage, string> = {
+  connect: "Connection failed",
+  auth: "Authentication failed",
+  path: "Remote path check failed",
+};
+
+const NETWORK_ERROR_HINTS: Record<string, string> = {
+  ENOTFOUND: "host not found",
+  ECONNREFUSED: "connection refused (is SSH running on that port?)",
+  EHOSTUNREACH: "host unreachable",
+  ENETUNREACH: "network unreachable",
+  ETIMEDOUT: "connection timed out",
+  ECONNRESET: "connection reset by remote host",
+};
+
+export class SshStageError extends Error {
+  stage: SshFailureStage;
+
+  constructor(stage: SshFailureStage, detail: string) {
+    super(redactSecrets(`${STAGE_LABELS[stage]}: ${detail}`));
+    this.name = "SshStageError";
+    this.stage = stage;
+  }
+}
+
+/**
+ * Classify an error emitted by an ssh2 client. ssh2 tags failures with a
+ * `level` ("client-authentication", "client-timeout", "client-socket") and
+ * socket errors carry a Node `code`.
+ */
+export function classifySshError(err: unknown): SshStageError {
+  if (err instanceof SshStageError) return err;
+
+  const e = err as { level?: string; code?: string; message?: string };
+  const message = e?.message || String(err);
+
+  if (e?.level === "client-authentication") {
+    return new SshStageError("auth", "the server rejected the key or user");
+  }
+  if (/passphrase|private key|encrypted key/i.test(message)) {
+    return new SshStageError("auth", message);
+  }
+  if (e?.level === "client-timeout") {
+    return new SshStageError("connect", "timed out waiting for the SSH handshake");
+  }
+  if (e?.code && NETWORK_ERROR_HINTS[e.code]) {
+    return new SshStageError("connect", NETWORK_ERROR_HINTS[e.code]);
+  }
+  return new SshStageError("connect", message);
+}
+
+// SFTP status codes (draft-ietf-secsh-filexfer) that ssh2 puts on `err.code`
+const SFTP_NO_SUCH_FILE = 2;
+const SFTP_PERMISSION_DENIED = 3;
+
+/** Describe a failed SFTP operation on a remote path in user-facing terms. */
+export function describeSftpError(err: unknown, action: string, remotePath: string): string {
+  const e = err as { code?: number | string; message?: string };
+  if (e?.code === SFTP_PERMISSION_DENIED) {
+    return `Permission denied while trying to ${action} ${remotePath}`;
+  }
+  if (e?.code === SFTP_NO_SUCH_FILE) {
+    return `Parent directory does not exist while trying to ${action} ${remotePath}`;
+  }
+  return redactSecrets(`Failed to ${action} ${remotePath}: ${e?.message || String(err)}`);
+}
+
+/** The SFTP calls directory creation needs. */
+export type SftpDirOps = Pick<SFTPWrapper, "mkdir" | "stat">;
+
+/**
+ * Create a remote directory and any missing parents. A directory that already
+ * exists is fine; anything else (permission denied, a file in the way) rejects
+ * with a message naming the directory that couldn't be created.
+ */
+export async function mkdirRemote(sftp: SftpDirOps, dirPath: string): Promise<void> {
+  let current = "";
+  for (const part of dirPath.split("/").filter(Boolean)) {
+    current += "/" + part;
+    const dir = current;
+    await new Promise<void>((resolve, reject) => {
+      sftp.mkdir(dir, (mkErr) => {
+        if (!mkErr) {
+          resolve();
+          return;
+        }
+        // SFTP has no distinct "already exists" status, so check whether the
+        // directory is there before treating the failure as real
+        sftp.stat(dir, (statErr, stats) => {
+          if (!statErr && stats.isDirectory()) resolve();
+          else reject(new Error(describeSftpError(mkErr, "create directory", dir)));
+        });
+      });
+    });
+  }
+}
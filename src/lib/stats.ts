@@ -0,0 +1,48 @@
+import { db } from "@/lib/db";
+import { groups, jobs } from "@/lib/db/schema";
+import { and, isNull, notInArray, sql } from "drizzle-orm";
+
+export interface LibraryStats {
+  groupsByStatus: Record<string, number>;
+  sizeByMediaType: Record<string, number>;
+  pendingTransferSize: number;
+  pendingTransferCount: number;
+  unmatchedGroups: number;
+}
+
+/** Aggregate counts and sizes over everything that isn't in the trash. */
+export function getLibraryStats(): LibraryStats {
+  const statusRows = db
+    .select({ status: groups.status, count: sql<number>`count(*)` })
+    .from(groups)
+    .where(isNull(groups.deletedAt))
+    .groupBy(groups.status)
+    .all();
+
+  const typeRows = db
+    .select({ mediaType: groups.mediaType, size: sql<number>`coalesce(sum(${groups.totalFileSize}), 0)` })
+    .from(groups)
+    .where(isNull(groups.deletedAt))
+    .groupBy(groups.mediaType)
+    .all();
+
+  // Anything not yet transferred or deliberately skipped still needs moving
+  const pending = db
+    .select({
+      size: sql<number>`coalesce(sum(${jobs.fileSize}), 0)`,
+      count: sql<number>`count(*)`,
+    })
+    .from(jobs)
+    .where(and(isNull(jobs.deletedAt), notInArray(jobs.status, ["completed", "skipped"])))
+    .get();
+
+  const groupsByStatus = Object.fromEntries(statusRows.map((r) => [r.status, r.count]));
+
+  return {
+    groupsByStatus,
+    sizeByMediaType: Object.fromEntries(typeRows.map((r) => [r.mediaType, r.size])),
+    pendingTransferSize: pending?.size ?? 0,
+    pendingTransferCount: pending?.count ?? 0,
+    unmatchedGroups: groupsByStatus.unmatched ?? 0,
+  };
+}
@@ -1,5 +1,5 @@
 import { create } from "zustand";
-import type { Job, Group, MatchCandidate, Destination } from "@/lib/db/schema";
+import type { Job, Group, MatchCandidate, Destination, Label } from "@/lib/db/schema";
 
 export type JobWithPreview = Job & {
   previewName?: string | null;
@@ -8,6 +8,7 @@ export type JobWithPreview = Job & {
 export type GroupWithJobs = Group & {
   jobs: JobWithPreview[];
   candidates?: MatchCandidate[];
+  labels?: Label[];
 };
 
 interface AppState {
@@ -23,6 +24,10 @@ interface AppState {
   // Filters
   statusFilter: string | null;
   mediaTypeFilter: string | null;
+  changeFilter: string | null;
+  labelFilter: number | null;
+  issueFilter: string | null;
+  trashView: boolean;
   searchQuery: string;
   page: number;
   sortBy: string;
@@ -44,12 +49,19 @@ interface AppState {
   // Destinations
   destinations: Destination[];
 
+  // All defined labels
+  labels: Label[];
+
   // Actions
   setGroups: (groups: GroupWithJobs[], total: number) => void;
   setLoading: (loading: boolean) => void;
   setScanning: (scanning: boolean) => void;
   setStatusFilter: (status: string | null) => void;
   setMediaTypeFilter: (mediaType: string | null) => void;
+  setChangeFilter: (change: string | null) => void;
+  setLabelFilter: (labelId: number | null) => void;
+  setIssueFilter: (issue: string | null) => void;
+  setTrashView: (trashView: boolean) => void;
   setSearchQuery: (query: string) => void;
   setPage: (page: number) => void;
   setSorting: (sortBy: string, sortDir: "asc" | "desc") => void;
@@ -72,12 +84,67 @@ interface AppState {
   setTransferDrawerOpen: (open: boolean) => void;
   setSettings: (settings: Record<string, string>) => void;
   setDestinations: (destinations: Destination[]) => void;
+  setLabels: (labels: Label[]) => void;
 
   // Mutations
   updateGroup: (id: number, updates: Partial<Group>) => void;
   removeGroups: (ids: number[]) => void;
 }
 
+export interface ViewState {
+  sortBy: string;
+  sortDir: "asc" | "desc";
+  statusFilter: string | null;
+  mediaTypeFilter: string | null;
+  changeFilter: string | null;
+  labelFilter: number | null;
+  issueFilter: string | null;
+  searchQuery: string;
+  page: number;
+}
+
+/** Parse the persisted view_state setting, keeping only well-formed fields. */
+export function parseViewState(raw?: string): Partial<ViewState> {
+  if (!raw) return {};
+  let data: Record<string, unknown>;
+  try {
+    data = JSON.parse(raw);
+  } catch {
+    return {};
+  }
+  if (!data || typeof data !== "object") return {};
+
+  const view: Partial<ViewState> = {};
+  if (typeof data.sortBy === "string") view.sortBy = data.sortBy;
+  if (data.sortDir === "asc" || data.sortDir === "desc") view.sortDir = data.sortDir;
+  if (typeof data.statusFilter === "string" || data.statusFilter === null) {
+    view.statusFilter = data.statusFilter;
+  }
+  if (typeof data.mediaTypeFilter === "string" || data.mediaTypeFilter === null) {
+    view.mediaTypeFilter = data.mediaTypeFilter;
+  }
+  if (typeof data.changeFilter === "string" || data.changeFilter === null) {
+    view.changeFilter = data.changeFilter;
+  }
+  if (typeof data.labelFilter === "number" || data.labelFilter === null) {
+    view.labelFilter = data.labelFilter;
+  }
+  if (typeof data.issueFilter === "string" || data.issueFilter === null) {
+    view.issueFilter = data.issueFilter;
+  }
+  if (typeof data.searchQuery === "string") view.searchQuery = data.searchQuery;
+  if (typeof data.page === "number" && data.page >= 1) view.page = Math.floor(data.page);
+  return view;
+}
+
+export const DEFAULT_PAGE_SIZE = 50;
+
+/** Page count for the queue given the page_size setting (falls back to 50). */
+export function getTotalPages(totalGroups: number, pageSizeSetting?: string): number {
+  const pageSize = parseInt(pageSizeSetting || "", 10) || DEFAULT_PAGE_SIZE;
+  return Math.max(1, Math.ceil(totalGroups / pageSize));
+}
+
 export const useAppStore = create<AppState>((set, get) => ({
   groups: [],
   totalGroups: 0,
@@ -88,6 +155,10 @@ export const useAppStore = create<AppState>((set, get) => ({
 
   statusFilter: null,
   mediaTypeFilter: null,
+  changeFilter: null,
+  labelFilter: null,
+  issueFilter: null,
+  trashView: false,
   searchQuery: "",
   page: 1,
   sortBy: "createdAt",
@@ -103,12 +174,17 @@ export const useAppStore = create<AppState>((set, get) => ({
 
   settings: {},
   destinations: [],
+  labels: [],
 
   setGroups: (groups, total) => set({ groups, totalGroups: total }),
   setLoading: (loading) => set({ loading }),
   setScanning: (scanning) => set({ scanning }),
   setStatusFilter: (statusFilter) => set({ statusFilter, page: 1 }),
   setMediaTypeFilter: (mediaTypeFilter) => set({ mediaTypeFilter, page: 1 }),
+  setChangeFilter: (changeFilter) => set({ changeFilter, page: 1 }),
+  setLabelFilter: (labelFilter) => set({ labelFilter, page: 1 }),
+  setIssueFilter: (issueFilter) => set({ issueFilter, page: 1 }),
+  setTrashView: (trashView) => set({ trashView, page: 1, selectedGroupIds: {} }),
   setSearchQuery: (searchQuery) => set({ searchQuery, page: 1 }),
   setPage: (page) => set({ page }),
   setSorting: (sortBy, sortDir) => set({ sortBy, sortDir }),
@@ -175,6 +251,7 @@ export const useAppStore = create<AppState>((set, get) => ({
   setTransferDrawerOpen: (open) => set({ transferDrawerOpen: open }),
   setSettings: (settings) => set({ settings }),
   setDestinations: (destinations) => set({ destinations }),
+  setLabels: (labels) => set({ labels }),
 
   updateGroup: (id, updates) =>
     set((state) => ({
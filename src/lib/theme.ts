@@ -0,0 +1,51 @@
+// Shared presentation helpers for the UI components.
+
+const DEFAULT_AUTO_MATCH_THRESHOLD = 0.85;
+
+/**
+ * Red / amber / green text class for a match confidence (0-1). Green starts at
+ * the auto-match threshold so a green score is one that would auto-match;
+ * amber covers the lower band a reviewer should look at, red is a poor match.
+ */
+export function confidenceColor(confidence: number, autoMatchThreshold?: string | number): string {
+  const parsed = typeof autoMatchThreshold === "number"
+    ? autoMatchThreshold
+    : parseFloat(autoMatchThreshold ?? "");
+  const high = Number.isFinite(parsed) && parsed > 0 && parsed <= 1
+    ? parsed
+    : DEFAULT_AUTO_MATCH_THRESHOLD;
+  const low = high * 0.6;
+
+  if (confidence >= high) return "text-success";
+  if (confidence >= low) return "text-warning";
+  return "text-error";
+}
+
+// Poster widths offered for match candidates; smaller ones load faster on slow links
+export const CANDIDATE_POSTER_SIZES = ["w92", "w154", "w185"];
+export const DEFAULT_CANDIDATE_POSTER_SIZE = "w185";
+
+export function candidatePosterSize(value?: string): string {
+  return value && CANDIDATE_POSTER_SIZES.includes(value) ? value : DEFAULT_CANDIDATE_POSTER_SIZE;
+}
+
+// UI scale multiplies every rem-based size (text, padding, panel widths) via
+// the root font size, so the whole app grows together on high-DPI displays.
+export const UI_SCALES = [1, 1.1, 1.25, 1.5, 1.75, 2];
+const MIN_UI_SCALE = 0.75;
+const MAX_UI_SCALE = 2;
+
+export function parseUiScale(value?: string): number {
+  const scale = parseFloat(value ?? "");
+  if (!Number.isFinite(scale)) return 1;
+  return Math.min(MAX_UI_SCALE, Math.max(MIN_UI_SCALE, scale));
+}
+
+export function applyUiScale(scale: number) {
+  document.documentElement.style.setProperty("--ui-scale", String(scale));
+}
+
+/** A pixel size at the given UI scale, for layout math done in JS rather than CSS. */
+export function scaled(px: number, scale: number): number {
+  return Math.round(px * scale);
+}
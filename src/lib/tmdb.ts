@@ -9,32 +9,171 @@ let requestTimestamps: number[] = [];
 const RATE_LIMIT = 35; // stay slightly under
 const RATE_WINDOW = 10000;
 
-async function rateLimitedFetch(url: string, init?: RequestInit): Promise<Response> {
-  const now = Date.now();
-  requestTimestamps = requestTimestamps.filter((t) => now - t < RATE_WINDOW);
+// Slots are handed out one caller at a time so concurrent matches can't all
+// see a free window and burst past the limit together
+let slotQueue: Promise<void> = Promise.resolve();
 
-  if (requestTimestamps.length >= RATE_LIMIT) {
-    const waitTime = RATE_WINDOW - (now - requestTimestamps[0]);
-    await new Promise((resolve) => setTimeout(resolve, waitTime));
-  }
+function acquireSlot(): Promise<void> {
+  const slot = slotQueue.then(async () => {
+    const now = Date.now();
+    requestTimestamps = requestTimestamps.filter((t) => now - t < RATE_WINDOW);
 
-  requestTimestamps.push(Date.now());
+    if (requestTimestamps.length >= RATE_LIMIT) {
+      const waitTime = RATE_WINDOW - (now - requestTimestamps[0]);
+      await new Promise((resolve) => setTimeout(resolve, waitTime));
+    }
+
+    requestTimestamps.push(Date.now());
+  });
+  slotQueue = slot;
+  return slot;
+}
+
+async function rateLimitedFetch(url: string, init?: RequestInit): Promise<Response> {
+  await acquireSlot();
   return fetch(url, init);
 }
 
-function getApiKey(): string {
+function getSetting(key: string): string {
   const setting = db
     .select()
     .from(settings)
-    .where(eq(settings.key, "tmdb_api_key"))
+    .where(eq(settings.key, key))
     .get();
   return setting?.value || "";
 }
 
+function getApiKey(): string {
+  return getSetting("tmdb_api_key");
+}
+
+// v4 Read Access Tokens are JWTs ("eyJ...") and go in the Authorization header;
+// classic v3 keys are 32-char hex strings passed as ?api_key=
+function isBearerToken(key: string): boolean {
+  return key.startsWith("eyJ") && key.split(".").length === 3;
+}
+
+const FALLBACK_LANGUAGE = "en-US";
+
+function getLanguage(): string {
+  return getSetting("tmdb_language") || FALLBACK_LANGUAGE;
+}
+
+function isFallbackLanguage(language: string): boolean {
+  return language.toLowerCase().startsWith("en");
+}
+
+export type TmdbErrorKind = "missing_key" | "invalid_key" | "network" | "api";
+
+const TMDB_ERROR_MESSAGES: Record<TmdbErrorKind, string> = {
+  missing_key: "No TMDB API key configured. Set it in Settings.",
+  invalid_key: "Your TMDB API key is invalid. Check it in Settings.",
+  network: "Couldn't reach TMDB. Check your network connection.",
+  api: "TMDB API error",
+};
+
+/** Errors that mean no TMDB request can succeed until the user fixes something. */
+export class TmdbError extends Error {
+  kind: TmdbErrorKind;
+
+  constructor(kind: TmdbErrorKind, detail?: string) {
+    super(detail ? `${TMDB_ERROR_MESSAGES[kind]}: ${detail}` : TMDB_ERROR_MESSAGES[kind]);
+    this.name = "TmdbError";
+    this.kind = kind;
+  }
+
+  /** A missing or bad key fails every request; network and API errors may pass on retry. */
+  get isFatal(): boolean {
+    return this.kind === "missing_key" || this.kind === "invalid_key";
+  }
+}
+
+/**
+ * GET a TMDB v3 endpoint with the API key (or v4 token) and the configured language/region.
+ * `language` overrides the setting (used for English fallback lookups); `apiKey`
+ * overrides the stored key (used to validate a key before saving it).
+ * Throws TmdbError for a missing/invalid key or a network failure; other
+ * non-OK responses are returned for the caller to handle.
+ */
+async function tmdbGet(
+  apiPath: string,
+  query: Record<string, string> = {},
+  opts: { language?: string; apiKey?: string } = {}
+): Promise<Response> {
+  const apiKey = (opts.apiKey ?? getApiKey()).trim();
+  if (!apiKey) throw new TmdbError("missing_key");
+
+  const params = new URLSearchParams({ language: opts.language ?? getLanguage(), ...query });
+  const region = getSetting("tmdb_region");
+  if (region) params.set("region", region);
+
+  const init: RequestInit = {};
+  if (isBearerToken(apiKey)) {
+    init.headers = { Authorization: `Bearer ${apiKey}` };
+  } else {
+    params.set("api_key", apiKey);
+  }
+
+  let res: Response;
+  try {
+    res = await rateLimitedFetch(`${TMDB_BASE}${apiPath}?${params}`, init);
+  } catch (err) {
+    throw new TmdbError("network", err instanceof Error ? err.message : undefined);
+  }
+  if (res.status === 401) throw new TmdbError("invalid_key");
+  return res;
+}
+
+/** Check a key (or the stored one) against a cheap TMDB endpoint. */
+export async function validateApiKey(
+  apiKey?: string
+): Promise<{ ok: true } | { ok: false; kind: TmdbErrorKind; error: string }> {
+  try {
+    const res = await tmdbGet("/configuration", {}, { apiKey });
+    if (!res.ok) throw new TmdbError("api", String(res.status));
+    return { ok: true };
+  } catch (err) {
+    if (err instanceof TmdbError) return { ok: false, kind: err.kind, error: err.message };
+    throw err;
+  }
+}
+
+/**
+ * Whether TMDB can be reached right now: false when the request can't get
+ * through or TMDB answers with a server error. Key problems still throw.
+ */
+export async function isTmdbReachable(): Promise<boolean> {
+  try {
+    const res = await tmdbGet("/configuration");
+    return res.status < 500;
+  } catch (err) {
+    if (err instanceof TmdbError && err.kind === "network") return false;
+    throw err;
+  }
+}
+
+// TMDB fills untranslated episode names with "Episode N" (or the localized
+// equivalent). Only those literal placeholders count, so real titles built
+// around a number ("Chapter 3", "1984") are kept.
+const EPISODE_PLACEHOLDER_WORDS = [
+  "Episode", "Épisode", "Episodio", "Episódio", "Episodi", "Folge", "Aflevering", "Afsnit",
+  "Avsnitt", "Jakso", "Odcinek", "Epizoda", "Epizód", "Bölüm", "Серия", "Эпизод", "Επεισόδιο",
+];
+const EPISODE_PLACEHOLDER_PATTERN = new RegExp(
+  `^(?:(?:${EPISODE_PLACEHOLDER_WORDS.join("|")})\\s+\\d+|第\\s*\\d+\\s*[話话集]|\\d+\\s*화|에피소드\\s*\\d+)$`,
+  "iu"
+);
+
+function needsEpisodeFallback(ep: { name: string }): boolean {
+  return !ep.name || EPISODE_PLACEHOLDER_PATTERN.test(ep.name.trim());
+}
+
 export interface TmdbSearchResult {
   id: number;
   title?: string;
   name?: string;
+  original_title?: string;
+  original_name?: string;
   release_date?: string;
   first_air_date?: string;
   poster_path: string | null;
@@ -49,70 +188,122 @@ export interface TmdbSearchResponse {
   total_results: number;
 }
 
+// Untranslated titles come back empty in some languages; use the original instead
+function withTitleFallback(r: TmdbSearchResult): TmdbSearchResult {
+  return {
+    ...r,
+    title: r.title || r.original_title,
+    name: r.name || r.original_name,
+  };
+}
+
 export async function searchMulti(
   query: string,
   year?: number
 ): Promise<TmdbSearchResult[]> {
-  const apiKey = getApiKey();
-  if (!apiKey) throw new Error("TMDB API key not configured");
+  const params: Record<string, string> = { query, include_adult: "false" };
+  if (year) params.year = String(year);
 
-  const params = new URLSearchParams({
-    api_key: apiKey,
-    query,
-    include_adult: "false",
-  });
-  if (year) params.set("year", String(year));
-
-  const res = await rateLimitedFetch(`${TMDB_BASE}/search/multi?${params}`);
-  if (!res.ok) throw new Error(`TMDB API error: ${res.status}`);
+  const res = await tmdbGet("/search/multi", params);
+  if (!res.ok) throw new TmdbError("api", String(res.status));
 
   const data: TmdbSearchResponse = await res.json();
   // Filter to only movies and TV shows
-  return data.results.filter(
-    (r) => r.media_type === "movie" || r.media_type === "tv"
-  );
+  return data.results
+    .filter((r) => r.media_type === "movie" || r.media_type === "tv")
+    .map(withTitleFallback);
 }
 
 export async function searchMovies(
   query: string,
   year?: number
 ): Promise<TmdbSearchResult[]> {
-  const apiKey = getApiKey();
-  if (!apiKey) throw new Error("TMDB API key not configured");
+  const params: Record<string, string> = { query, include_adult: "false" };
+  if (year) params.year = String(year);
 
-  const params = new URLSearchParams({
-    api_key: apiKey,
-    query,
-    include_adult: "false",
-  });
-  if (year) params.set("year", String(year));
-
-  const res = await rateLimitedFetch(`${TMDB_BASE}/search/movie?${params}`);
-  if (!res.ok) throw new Error(`TMDB API error: ${res.status}`);
+  const res = await tmdbGet("/search/movie", params);
+  if (!res.ok) throw new TmdbError("api", String(res.status));
 
   const data: TmdbSearchResponse = await res.json();
-  return data.results.map((r) => ({ ...r, media_type: "movie" }));
+  return data.results.map((r) => ({ ...withTitleFallback(r), media_type: "movie" }));
 }
 
 export async function searchTV(
   query: string,
   year?: number
 ): Promise<TmdbSearchResult[]> {
-  const apiKey = getApiKey();
-  if (!apiKey) throw new Error("TMDB API key not configured");
-
-  const params = new URLSearchParams({
-    api_key: apiKey,
-    query,
-    include_adult: "false",
-  });
-  if (year) params.set("first_air_date_year", String(year));
+  const params: Record<string, string> = { query, include_adult: "false" };
+  if (year) params.first_air_date_year = String(year);
 
-  const res = await rateLimitedFetch(`${TMDB_BASE}/search/tv?${params}`);
-  if (!res.ok) throw new Error(`TMDB API error: ${res.status}`);
+  const res = await tmdbGet("/search/tv", params);
+  if (!res.ok) throw new TmdbError("api", String(res.status));
 
   const data: TmdbSearchResponse = await res.json();
-  return data.results.map((r) => ({ ...r, media_type: "tv" }));
+  return data.results.map((r) => ({ ...withTitleFallback(r), media_type: "tv" }));
+}
+
+export type TmdbReference =
+  | { kind: "tmdb"; id: number; mediaType?: "movie" | "tv" }
+  | { kind: "imdb"; id: string };
+
+/**
+ * Recognize direct references pasted into search: TMDB URLs
+ * ("themoviedb.org/movie/603"), "tmdb:603" / "tmdb:tv/1399", and IMDB ids ("tt0133093").
+ */
+export function parseTmdbReference(input: string): TmdbReference | null {
+  const trimmed = input.trim();
+
+  const url = trimmed.match(/themoviedb\.org\/(movie|tv)\/(\d+)/i);
+  if (url) {
+    return { kind: "tmdb", id: parseInt(url[2], 10), mediaType: url[1].toLowerCase() as "movie" | "tv" };
+  }
+
+  const prefixed = trimmed.match(/^tmdb:(?:(movie|tv)[:/])?(\d+)$/i);
+  if (prefixed) {
+    return {
+      kind: "tmdb",
+      id: parseInt(prefixed[2], 10),
+      mediaType: prefixed[1]?.toLowerCase() as "movie" | "tv" | undefined,
+    };
+  }
+
+  const imdb = trimmed.match(/^(?:https?:\/\/)?(?:(?:www|m)\.)?(?:imdb\.com\/title\/)?(tt\d{7,})\/?/i);
+  if (imdb) return { kind: "imdb", id: imdb[1].toLowerCase() };
+
+  return null;
+}
+
+async function getDetails(
+  mediaType: "movie" | "tv",
+  id: number
+): Promise<TmdbSearchResult | null> {
+  const res = await tmdbGet(`/${mediaType}/${id}`);
+  if (!res.ok) return null;
+  const data: TmdbSearchResult = await res.json();
+  return { ...withTitleFallback(data), media_type: mediaType };
+}
+
+/** Resolve a pasted TMDB/IMDB reference to its title(s) without a text search. */
+export async function lookupTmdbReference(
+  ref: TmdbReference,
+  mediaTypeHint?: string | null
+): Promise<TmdbSearchResult[]> {
+  if (ref.kind === "imdb") {
+    const res = await tmdbGet(`/find/${ref.id}`, { external_source: "imdb_id" });
+    if (!res.ok) throw new TmdbError("api", String(res.status));
+    const data: { movie_results: TmdbSearchResult[]; tv_results: TmdbSearchResult[] } =
+      await res.json();
+    return [
+      ...data.movie_results.map((r) => ({ ...withTitleFallback(r), media_type: "movie" })),
+      ...data.tv_results.map((r) => ({ ...withTitleFallback(r), media_type: "tv" })),
+    ];
+  }
+
+  // A bare id is ambiguous between movies and shows; try the hint first, then both
+  const hint = ref.mediaType ?? (mediaTypeHint === "movie" || mediaTypeHint === "tv" ? mediaTypeHint : undefined);
+  const types: ("movie" | "tv")[] = hint ? [hint] : ["movie", "tv"];
+  const results = await Promise.all(types.map((t) => getDetails(t, ref.id)));
+  return results.filter((r): r is TmdbSearchResult => r !== null);
 }
 
 export interface TmdbSeason {
@@ -133,13 +324,8 @@ export interface TmdbSeasonDetail {
 }
 
 export async function getShowSeasons(tvId: number): Promise<TmdbSeason[]> {
-  const apiKey = getApiKey();
-  if (!apiKey) throw new Error("TMDB API key not configured");
-
-  const res = await rateLimitedFetch(
-    `${TMDB_BASE}/tv/${tvId}?api_key=${apiKey}`
-  );
-  if (!res.ok) throw new Error(`TMDB API error: ${res.status}`);
+  const res = await tmdbGet(`/tv/${tvId}`);
+  if (!res.ok) throw new TmdbError("api", String(res.status));
 
   const data = await res.json();
   return data.seasons || [];
@@ -149,23 +335,108 @@ export async function getSeason(
   tvId: number,
   seasonNumber: number
 ): Promise<TmdbSeasonDetail | null> {
-  const apiKey = getApiKey();
-  if (!apiKey) throw new Error("TMDB API key not configured");
+  const language = getLanguage();
+  const res = await tmdbGet(`/tv/${tvId}/season/${seasonNumber}`, {}, { language });
+  if (!res.ok) return null;
+
+  const season: TmdbSeasonDetail = await res.json();
+  if (isFallbackLanguage(language) || !season.episodes.some(needsEpisodeFallback)) {
+    return season;
+  }
 
-  const res = await rateLimitedFetch(
-    `${TMDB_BASE}/tv/${tvId}/season/${seasonNumber}?api_key=${apiKey}`
+  // Fill untranslated episode names/overviews from English
+  const fallbackRes = await tmdbGet(
+    `/tv/${tvId}/season/${seasonNumber}`,
+    {},
+    { language: FALLBACK_LANGUAGE }
   );
+  if (!fallbackRes.ok) return season;
+  const fallback: TmdbSeasonDetail = await fallbackRes.json();
+  const byNumber = new Map(fallback.episodes.map((ep) => [ep.episode_number, ep]));
+
+  return {
+    ...season,
+    episodes: season.episodes.map((ep) => {
+      const en = byNumber.get(ep.episode_number);
+      if (!en) return ep;
+      return {
+        ...ep,
+        name: needsEpisodeFallback(ep) ? en.name : ep.name,
+        overview: ep.overview || en.overview,
+      };
+    }),
+  };
+}
+
+// TMDB episode group types we can use as alternate season orderings
+export const EPISODE_GROUP_TYPES = {
+  absolute: 2,
+  dvd: 3,
+} as const;
+
+export interface TmdbEpisodeGroupSummary {
+  id: string;
+  name: string;
+  type: number;
+  episode_count: number;
+  group_count: number;
+}
+
+export interface TmdbEpisodeGroupDetail {
+  id: string;
+  name: string;
+  type: number;
+  groups: {
+    id: string;
+    name: string;
+    order: number;
+    episodes: (TmdbEpisode & { order: number })[];
+  }[];
+}
+
+export async function getEpisodeGroups(tvId: number): Promise<TmdbEpisodeGroupSummary[]> {
+  const res = await tmdbGet(`/tv/${tvId}/episode_groups`);
+  if (!res.ok) throw new TmdbError("api", String(res.status));
+
+  const data = await res.json();
+  return data.results || [];
+}
+
+export async function getEpisodeGroup(groupId: string): Promise<TmdbEpisodeGroupDetail | null> {
+  const res = await tmdbGet(`/tv/episode_group/${groupId}`);
+  if (!res.ok) return null;
+
+  return res.json();
+}
+
+export interface TmdbExternalIds {
+  imdb_id: string | null;
+  tvdb_id: number | null;
+}
+
+export async function getExternalIds(tvId: number): Promise<TmdbExternalIds | null> {
+  const res = await tmdbGet(`/tv/${tvId}/external_ids`);
   if (!res.ok) return null;
 
   return res.json();
 }
 
+/** Name of the collection (franchise) a movie belongs to, if any. */
+export async function getMovieCollection(movieId: number): Promise<string | null> {
+  const res = await tmdbGet(`/movie/${movieId}`);
+  if (!res.ok) return null;
+
+  const data: { belongs_to_collection?: { name: string } | null } = await res.json();
+  return data.belongs_to_collection?.name || null;
+}
+
 export interface TmdbEpisode {
   id: number;
   name: string;
   episode_number: number;
   season_number: number;
   overview: string;
+  air_date: string | null;
   still_path: string | null;
 }
 
@@ -174,13 +445,18 @@ export async function getEpisode(
   season: number,
   episode: number
 ): Promise<TmdbEpisode | null> {
-  const apiKey = getApiKey();
-  if (!apiKey) return null;
+  if (!getApiKey()) return null;
 
-  const res = await rateLimitedFetch(
-    `${TMDB_BASE}/tv/${tvId}/season/${season}/episode/${episode}?api_key=${apiKey}`
-  );
+  const language = getLanguage();
+  const episodePath = `/tv/${tvId}/season/${season}/episode/${episode}`;
+  const res = await tmdbGet(episodePath, {}, { language });
   if (!res.ok) return null;
 
-  return res.json();
+  const data: TmdbEpisode = await res.json();
+  if (isFallbackLanguage(language) || !needsEpisodeFallback(data)) return data;
+
+  const fallbackRes = await tmdbGet(episodePath, {}, { language: FALLBACK_LANGUAGE });
+  if (!fallbackRes.ok) return data;
+  const en: TmdbEpisode = await fallbackRes.json();
+  return { ...data, name: en.name, overview: data.overview || en.overview };
 }
@@ -0,0 +1,84 @@
+import fs from "fs";
+import os from "os";
+import path from "path";
+import { afterEach, describe, expect, it } from "vitest";
+import { CHUNK_SIZE, copyInChunks, progressOf, statSource } from "./transfer";
+
+let root = "";
+
+function tempDir(): string {
+  root = fs.mkdtempSync(path.join(os.tmpdir(), "reelname-transfer-"));
+  return root;
+}
+
+afterEach(() => {
+  if (root) fs.rmSync(root, { recursive: true, force: true });
+  root = "";
+});
+
+describe("statSource", () => {
+  it("names a source that was removed since the scan", () => {
+    const missing = path.join(tempDir(), "gone.mkv");
+    expect(() => statSource(missing)).toThrow(`Source missing: ${missing}`);
+  });
+
+  it("rejects a directory where the file used to be", () => {
+    const dir = path.join(tempDir(), "Movie.mkv");
+    fs.mkdirSync(dir);
+    expect(() => statSource(dir)).toThrow(`Source is not a file: ${dir}`);
+  });
+
+  it("accepts a zero-byte file", () => {
+    const empty = path.join(tempDir(), "empty.nfo");
+    fs.writeFileSync(empty, "");
+    expect(statSource(empty).size).toBe(0);
+  });
+});
+
+describe("progressOf", () => {
+  it("treats an empty file as done", () => {
+    expect(progressOf(0, 0)).toBe(1);
+  });
+
+  it("caps progress at 1", () => {
+    expect(progressOf(50, 200)).toBe(0.25);
+    expect(progressOf(300, 200)).toBe(1);
+  });
+});
+
+describe("copyInChunks", () => {
+  it("streams a large sparse file one block at a time", async () => {
+    const dir = tempDir();
+    const source = path.join(dir, "big.mkv");
+    const size = 64 * CHUNK_SIZE + 123;
+    fs.closeSync(fs.openSync(source, "w"));
+    fs.truncateSync(source, size);
+
+    const steps: number[] = [];
+    let last = 0;
+    await copyInChunks(source, path.join(dir, "big.part"), 0, (transferred) => {
+      steps.push(transferred - last);
+      last = transferred;
+    });
+
+    expect(fs.statSync(path.join(dir, "big.part")).size).toBe(size);
+    expect(last).toBe(size);
+    expect(steps.every((step) => step <= CHUNK_SIZE)).toBe(true);
+  });
+
+  it("appends from the resume offset", async () => {
+    const dir = tempDir();
+    const source = path.join(dir, "a.mkv");
+    const dest = path.join(dir, "a.part");
+    fs.writeFileSync(source, "0123456789");
+    fs.writeFileSync(dest, "0123");
+
+    let last = 0;
+    await copyInChunks(source, dest, 4, (transferred) => {
+      last = transferred;
+    });
+
+    expect(fs.readFileSync(dest, "utf8")).toBe("0123456789");
+    expect(last).toBe(10);
+  });
+});
@@ -1,15 +1,161 @@
 import fs from "fs";
 import path from "path";
+import { Transform } from "stream";
 import { Client as SSHClient } from "ssh2";
 import { db } from "./db";
-import { jobs, groups, destinations, settings } from "./db/schema";
-import { eq } from "drizzle-orm";
-import { formatGroupedPath } from "./naming";
+import { jobs, groups, destinations, settings, jobEvents } from "./db/schema";
+import { eq, and, inArray, notInArray, isNull, desc, asc } from "drizzle-orm";
+import { formatGroupedPath, standaloneGroup } from "./naming";
+import { classifySshError, describeSftpError, mkdirRemote } from "./ssh";
+import { runPostTransferHooks, resetPostTransferHookState } from "./hooks";
+import { createLogger, withSpan } from "./log";
+import { decryptSecret, redactSecrets } from "./secrets";
 import type { Job, Destination } from "./db/schema";
 
 const MAX_CONCURRENT = 2;
+const log = createLogger("transfer");
+
+// Sources are streamed in blocks of this size for both local and SFTP copies;
+// with pipe() backpressure only a block or two per job is ever held in memory,
+// however large the file. Progress is recorded once per block.
+export const CHUNK_SIZE = 1024 * 1024;
+
+// Transfers are written under this suffix and renamed into place on success,
+// so an interrupted copy never looks like a finished file
+const PART_SUFFIX = ".reelname.part";
+
+// Job statuses a batch may pick up from the selected groups. Only confirmed
+// jobs by default; matched ones skip the review step and completed ones are
+// sent again (e.g. to a second destination), so both are opt-in.
+export const TRANSFER_ELIGIBLE_STATUSES = ["confirmed", "matched", "completed"] as const;
+export type TransferEligibleStatus = (typeof TRANSFER_ELIGIBLE_STATUSES)[number];
+export const DEFAULT_TRANSFER_STATUSES: TransferEligibleStatus[] = ["confirmed"];
+
+// Job statuses whose partial files may still be resumed
+const RESUMABLE_STATUSES = ["confirmed", "queued", "transferring", "failed"] as const;
 let activeTransfers = 0;
-const transferQueue: Array<{ jobId: number; destinationId: number }> = [];
+const transferQueue: Array<{ jobId: number; destinationId: number; batchId: number }> = [];
+
+// Outcome of the running batch, which ends once the queue drains and the
+// last active transfer finishes; the post-transfer hooks fire at that point
+let batch: { id: number; completed: number; failed: number; destinationIds: Set<number> } | null = null;
+let nextBatchId = 1;
+
+function finishBatch() {
+  if (!batch || activeTransfers > 0 || transferQueue.length > 0) return;
+  const { completed, failed, destinationIds } = batch;
+  batch = null;
+  log.info("Batch finished", { completed, failed });
+
+  const dests = db
+    .select({ id: destinations.id, name: destinations.name })
+    .from(destinations)
+    .where(inArray(destinations.id, [...destinationIds]))
+    .all();
+  runPostTransferHooks({
+    completed,
+    failed,
+    destinations: dests,
+    finishedAt: new Date().toISOString(),
+  }).catch((err) => {
+    log.error("Post-transfer hooks failed", { err });
+  });
+}
+
+// Batch-wide pause: every copy runs through a gate that holds its next chunk
+// while paused, so backpressure stops the source read and the temp files stay
+// open until resumed. The queue stops starting new jobs too.
+let paused = false;
+const heldChunks = new Set<() => void>();
+
+/** Pass-through between source and destination that holds chunks while paused. */
+function pauseGate(): Transform {
+  return new Transform({
+    transform(chunk, _encoding, callback) {
+      if (!paused) {
+        callback(null, chunk);
+        return;
+      }
+      heldChunks.add(() => callback(null, chunk));
+    },
+  });
+}
+
+/** Whether any transfer is running or waiting in the queue. */
+export function isTransferActive(): boolean {
+  return activeTransfers > 0 || transferQueue.length > 0;
+}
+
+export function isTransfersPaused(): boolean {
+  return paused;
+}
+
+export function pauseTransfers() {
+  paused = true;
+}
+
+export function resumeTransfers() {
+  paused = false;
+  // Drop rate samples so the pause doesn't drag the rolling average down
+  transferRates.clear();
+  const held = [...heldChunks];
+  heldChunks.clear();
+  for (const release of held) release();
+  processQueue();
+}
+
+// Rolling (exponential moving average) throughput per job, sampled from the
+// bytes seen in each transfer's data loop
+const RATE_SAMPLE_MS = 500;
+const RATE_SMOOTHING = 0.3;
+const transferRates = new Map<number, { bytes: number; time: number; rate: number }>();
+
+function recordTransferBytes(jobId: number, bytes: number) {
+  const now = Date.now();
+  const prev = transferRates.get(jobId);
+  if (!prev) {
+    transferRates.set(jobId, { bytes, time: now, rate: 0 });
+    return;
+  }
+  const elapsed = now - prev.time;
+  if (elapsed < RATE_SAMPLE_MS) return;
+  const instant = ((bytes - prev.bytes) / elapsed) * 1000;
+  const rate = prev.rate === 0 ? instant : prev.rate + RATE_SMOOTHING * (instant - prev.rate);
+  transferRates.set(jobId, { bytes, time: now, rate });
+}
+
+/** Smoothed bytes-per-second for an in-flight transfer (0 when idle). */
+export function getTransferRate(jobId: number): number {
+  if (paused) return 0;
+  return transferRates.get(jobId)?.rate ?? 0;
+}
+
+/**
+ * Append to a job's transfer history. A failure can be reported by both the
+ * stream handler and the queue, so an exact repeat of the last event is dropped.
+ */
+function recordJobEvent(jobId: number, status: string, message: string | null = null) {
+  const last = db
+    .select()
+    .from(jobEvents)
+    .where(eq(jobEvents.jobId, jobId))
+    .orderBy(desc(jobEvents.id))
+    .get();
+  if (last && last.status === status && last.message === message) return;
+  db.insert(jobEvents)
+    .values({ jobId, status, message, createdAt: new Date().toISOString() })
+    .run();
+}
+
+/** A job's transfer history, oldest first. */
+export function getJobEvents(jobId: number) {
+  return db
+    .select()
+    .from(jobEvents)
+    .where(eq(jobEvents.jobId, jobId))
+    .orderBy(asc(jobEvents.id))
+    .all();
+}
 
 function updateJobProgress(
   jobId: number,
@@ -18,6 +164,7 @@ function updateJobProgress(
 ) {
   const status =
     error ? "failed" : progress >= 1 ? "completed" : "transferring";
+  if (error) error = redactSecrets(error);
   db.update(jobs)
     .set({
       transferProgress: progress,
@@ -27,6 +174,30 @@ function updateJobProgress(
     })
     .where(eq(jobs.id, jobId))
     .run();
+  // Per-block progress isn't history; only the outcome is
+  if (status !== "transferring") recordJobEvent(jobId, status, error ?? null);
+}
+
+/**
+ * Stat a job's source before copying it. The source may have been moved,
+ * deleted, or replaced by a directory since the scan.
+ */
+export function statSource(sourcePath: string): fs.Stats {
+  let stat: fs.Stats;
+  try {
+    stat = fs.statSync(sourcePath);
+  } catch {
+    throw new Error(`Source missing: ${sourcePath}`);
+  }
+  if (!stat.isFile()) {
+    throw new Error(`Source is not a file: ${sourcePath}`);
+  }
+  return stat;
+}
+
+/** Fraction complete; empty files count as done as soon as they're created. */
+export function progressOf(transferred: number, totalSize: number): number {
+  return totalSize > 0 ? Math.min(transferred / totalSize, 1) : 1;
 }
 
 /**
@@ -45,50 +216,262 @@ function buildRelativePath(job: Job): string {
     db.select().from(settings).where(eq(settings.key, "specials_folder_name")).get()?.value || "Specials";
   const extrasFolderName =
     db.select().from(settings).where(eq(settings.key, "extras_folder_name")).get()?.value || "Extras";
+  const useCollections =
+    db.select().from(settings).where(eq(settings.key, "use_collections")).get()?.value;
+  const seasonPadWidth =
+    db.select().from(settings).where(eq(settings.key, "season_pad_width")).get()?.value;
+  const episodePadWidth =
+    db.select().from(settings).where(eq(settings.key, "episode_pad_width")).get()?.value;
 
   if (group) {
     return formatGroupedPath(job, group, {
       naming_preset: namingPreset,
       specials_folder_name: specialsFolderName,
       extras_folder_name: extrasFolderName,
+      use_collections: useCollections,
+      season_pad_width: seasonPadWidth,
+      episode_pad_width: episodePadWidth,
     });
   }
 
-  // Fallback for ungrouped jobs: construct a minimal group-like object
-  return formatGroupedPath(job, {
-    id: 0,
-    status: "matched",
-    mediaType: job.mediaType,
-    folderPath: "",
-    folderName: "",
-    totalFileCount: 1,
-    totalFileSize: job.fileSize,
-    parsedTitle: job.parsedTitle,
-    parsedYear: job.parsedYear,
-    tmdbId: job.tmdbId,
-    tmdbTitle: job.tmdbTitle,
-    tmdbYear: job.tmdbYear,
-    tmdbPosterPath: job.tmdbPosterPath,
-    matchConfidence: job.matchConfidence,
-    destinationId: job.destinationId,
-    createdAt: job.createdAt,
-    updatedAt: job.updatedAt,
-  }, {
+  // Fallback for ungrouped jobs
+  return formatGroupedPath(job, standaloneGroup(job), {
     naming_preset: namingPreset,
     specials_folder_name: specialsFolderName,
     extras_folder_name: extrasFolderName,
+    season_pad_width: seasonPadWidth,
+    episode_pad_width: episodePadWidth,
   });
 }
 
+function formatBytes(bytes: number): string {
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+  return `${(bytes / (1024 * 1024 * 1024)).toFixed(2)} GB`;
+}
+
+/** Throw if a joined destination path would land outside its base. */
+function assertWithinBase(base: string, fullDest: string, pathApi: typeof path.posix) {
+  const relative = pathApi.relative(base, fullDest);
+  if (!relative || relative.startsWith("..") || pathApi.isAbsolute(relative)) {
+    throw new Error(`Destination path escapes ${base}: ${fullDest}`);
+  }
+}
+
+function localDestPath(job: Job, dest: Destination): string {
+  const fullDest = path.join(dest.basePath, buildRelativePath(job));
+  assertWithinBase(path.resolve(dest.basePath), path.resolve(fullDest), path);
+  return fullDest;
+}
+
+function remoteDestPath(job: Job, dest: Destination): string {
+  // Use forward slashes for remote path
+  const base = dest.basePath.replace(/\\/g, "/");
+  const fullDest = base + "/" + buildRelativePath(job).replace(/\\/g, "/");
+  assertWithinBase(path.posix.normalize(base), path.posix.normalize(fullDest), path.posix);
+  return fullDest;
+}
+
+function partPath(fullDest: string): string {
+  return fullDest + PART_SUFFIX;
+}
+
+/** Bytes of a job already present at a local destination (finished or partial). */
+function localExistingBytes(job: Job, dest: Destination): number {
+  const fullDest = localDestPath(job, dest);
+  try {
+    if (fs.statSync(fullDest).size === job.fileSize) return job.fileSize;
+  } catch {
+    // Not finished yet
+  }
+  try {
+    const size = fs.statSync(partPath(fullDest)).size;
+    return size <= job.fileSize ? size : 0;
+  } catch {
+    return 0;
+  }
+}
+
+function sshConnectConfig(dest: Destination): Record<string, unknown> {
+  const connectConfig: Record<string, unknown> = {
+    host: dest.sshHost!,
+    port: dest.sshPort || 22,
+    username: dest.sshUser!,
+  };
+
+  if (dest.sshKeyPath) {
+    connectConfig.privateKey = fs.readFileSync(dest.sshKeyPath);
+    if (dest.sshKeyPassphrase) {
+      connectConfig.passphrase = decryptSecret(dest.sshKeyPassphrase);
+    }
+  }
+  return connectConfig;
+}
+
+/**
+ * Validate a request's `statuses` field, falling back to the default when
+ * it's missing. Returns null if it names anything outside the allowlist.
+ */
+export function parseEligibleStatuses(value: unknown): TransferEligibleStatus[] | null {
+  if (value == null) return DEFAULT_TRANSFER_STATUSES;
+  if (!Array.isArray(value) || value.length === 0) return null;
+  if (!value.every((s) => (TRANSFER_ELIGIBLE_STATUSES as readonly unknown[]).includes(s))) return null;
+  return value as TransferEligibleStatus[];
+}
+
+/**
+ * Expand explicit job ids plus the jobs of the given groups whose status is
+ * eligible (confirmed only, unless the caller opts into more).
+ */
+export function collectTransferJobIds(
+  jobIds?: number[],
+  groupIds?: number[],
+  statuses: readonly TransferEligibleStatus[] = DEFAULT_TRANSFER_STATUSES
+): number[] {
+  const allJobIds = new Set<number>(jobIds || []);
+  if (groupIds?.length) {
+    const groupJobs = db
+      .select()
+      .from(jobs)
+      .where(
+        and(inArray(jobs.groupId, groupIds), inArray(jobs.status, [...statuses]), isNull(jobs.deletedAt))
+      )
+      .all();
+    for (const j of groupJobs) allJobIds.add(j.id);
+  }
+  return [...allJobIds];
+}
+
+/** "confirmed", "confirmed or matched", ... for "no eligible jobs" errors. */
+export function describeStatuses(statuses: readonly TransferEligibleStatus[]): string {
+  return statuses.join(" or ");
+}
+
+export interface SpaceCheck {
+  required: number;
+  available: number | null;
+  fits: boolean;
+  summary: string;
+}
+
+/**
+ * Compare the bytes still to be written (file sizes minus partial files that
+ * will be resumed) against free space on the destination. `available` is null
+ * when the destination can't report free space (e.g. SFTP servers without the
+ * statvfs extension); such batches are allowed to start.
+ */
+export async function checkFreeSpace(
+  jobIds: number[],
+  destinationId: number
+): Promise<SpaceCheck> {
+  const dest = db.select().from(destinations).where(eq(destinations.id, destinationId)).get();
+  if (!dest) throw new Error("Destination not found");
+  const batch = jobIds.length
+    ? db.select().from(jobs).where(inArray(jobs.id, jobIds)).all()
+    : [];
+
+  let required = 0;
+  let available: number | null = null;
+
+  if (dest.type === "ssh") {
+    ({ required, available } = await checkRemoteSpace(batch, dest));
+  } else {
+    for (const job of batch) {
+      required += job.fileSize - localExistingBytes(job, dest);
+    }
+    try {
+      const stats = await fs.promises.statfs(dest.basePath);
+      available = stats.bavail * stats.bsize;
+    } catch {
+      available = null;
+    }
+  }
+
+  const fits = available === null || required <= available;
+  const summary =
+    available === null
+      ? `Needs ${formatBytes(required)}, free space unknown`
+      : `Needs ${formatBytes(required)}, has ${formatBytes(available)}`;
+  return { required, available, fits, summary };
+}
+
+function checkRemoteSpace(
+  batch: Job[],
+  dest: Destination
+): Promise<{ required: number; available: number | null }> {
+  return new Promise((resolve, reject) => {
+    const conn = new SSHClient();
+
+    conn.on("ready", () => {
+      conn.sftp(async (err, sftp) => {
+        if (err) {
+          conn.end();
+          reject(err);
+          return;
+        }
+
+        const statSize = (remotePath: string) =>
+          new Promise<number>((res) => {
+            sftp.stat(remotePath, (statErr, stats) => res(statErr ? 0 : stats.size));
+          });
+
+        // This callback's promise is never awaited, so a throw (e.g. a path
+        // escaping the base) has to reject here or the check would hang
+        try {
+          let required = 0;
+          for (const job of batch) {
+            const fullDest = remoteDestPath(job, dest);
+            if ((await statSize(fullDest)) === job.fileSize) continue;
+            const partial = await statSize(partPath(fullDest));
+            required += job.fileSize - (partial <= job.fileSize ? partial : 0);
+          }
+
+          // statvfs@openssh.com — not every server supports it
+          // eslint-disable-next-line @typescript-eslint/no-explicit-any
+          const ext = (sftp as any).ext_openssh_statvfs;
+          if (typeof ext !== "function") {
+            conn.end();
+            resolve({ required, available: null });
+            return;
+          }
+          ext.call(sftp, dest.basePath, (statErr: Error | null, stats: { bavail: number; frsize: number }) => {
+            conn.end();
+            resolve({
+              required,
+              available: statErr ? null : stats.bavail * stats.frsize,
+            });
+          });
+        } catch (checkErr) {
+          conn.end();
+          reject(checkErr);
+        }
+      });
+    });
+
+    conn.on("error", (connErr) => reject(classifySshError(connErr)));
+    try {
+      conn.connect(sshConnectConfig(dest));
+    } catch (configErr) {
+      reject(classifySshError(configErr));
+    }
+  });
+}
+
+/** Whether interrupted copies pick up from their partial file (on by default). */
+function isResumeEnabled(): boolean {
+  return db.select().from(settings).where(eq(settings.key, "resume_transfers")).get()?.value !== "false";
+}
+
 /**
  * Local file copy with progress tracking
  */
 async function transferLocal(
   job: Job,
-  dest: Destination
+  dest: Destination,
+  restart = false
 ): Promise<void> {
-  const relativePath = buildRelativePath(job);
-  const fullDest = path.join(dest.basePath, relativePath);
+  const fullDest = localDestPath(job, dest);
+  const tempDest = partPath(fullDest);
 
   // Create directory structure
   fs.mkdirSync(path.dirname(fullDest), { recursive: true });
@@ -96,61 +479,161 @@ async function transferLocal(
   const totalSize = job.fileSize;
   let transferred = 0;
 
+  if (fs.existsSync(fullDest) && fs.statSync(fullDest).size === totalSize) {
+    // Already complete
+    updateJobProgress(job.id, 1);
+    return;
+  }
+
+  // A partial written from a different version of the source can't be resumed
+  if ((restart || !isResumeEnabled()) && fs.existsSync(tempDest)) {
+    fs.unlinkSync(tempDest);
+  }
+
   // Check for partial file (resume support)
-  if (fs.existsSync(fullDest)) {
-    const existingStat = fs.statSync(fullDest);
-    if (existingStat.size === totalSize) {
-      // Already complete
-      updateJobProgress(job.id, 1);
-      return;
-    }
-    if (existingStat.size < totalSize) {
+  if (fs.existsSync(tempDest)) {
+    const existingStat = fs.statSync(tempDest);
+    if (existingStat.size <= totalSize) {
       transferred = existingStat.size;
     }
   }
 
-  return new Promise((resolve, reject) => {
-    const readStream = fs.createReadStream(job.sourcePath, {
-      start: transferred,
-    });
-    const writeStream = fs.createWriteStream(fullDest, {
-      flags: transferred > 0 ? "a" : "w",
+  try {
+    await copyInChunks(job.sourcePath, tempDest, transferred, (bytes) => {
+      transferred = bytes;
+      recordTransferBytes(job.id, transferred);
+      updateJobProgress(job.id, progressOf(transferred, totalSize));
     });
+    fs.renameSync(tempDest, fullDest);
+  } catch (err) {
+    const message = err instanceof Error ? err.message : "Transfer failed";
+    updateJobProgress(job.id, progressOf(transferred, totalSize), message);
+    throw err;
+  }
+  updateJobProgress(job.id, 1);
+
+  // Save destination path on the job
+  db.update(jobs)
+    .set({
+      destinationId: dest.id,
+      destinationPath: fullDest,
+      updatedAt: new Date().toISOString(),
+    })
+    .where(eq(jobs.id, job.id))
+    .run();
+}
+
+/**
+ * Copy a file from byte `start` onward, appending to `destPath` when resuming.
+ * Reads CHUNK_SIZE blocks through the pause gate and reports the running byte
+ * count after each one.
+ */
+export function copyInChunks(
+  sourcePath: string,
+  destPath: string,
+  start: number,
+  onProgress: (transferred: number) => void
+): Promise<void> {
+  return new Promise((resolve, reject) => {
+    let transferred = start;
+    const readStream = fs.createReadStream(sourcePath, { start, highWaterMark: CHUNK_SIZE });
+    const writeStream = fs.createWriteStream(destPath, { flags: start > 0 ? "a" : "w" });
 
     readStream.on("data", (chunk) => {
       transferred += chunk.length;
-      const progress = Math.min(transferred / totalSize, 1);
-      updateJobProgress(job.id, progress);
+      onProgress(transferred);
     });
+    readStream.on("error", reject);
+    writeStream.on("error", reject);
+    writeStream.on("finish", resolve);
 
-    readStream.on("error", (err) => {
-      updateJobProgress(job.id, transferred / totalSize, err.message);
-      reject(err);
-    });
+    readStream.pipe(pauseGate()).pipe(writeStream);
+  });
+}
+
+// One authenticated SSH connection per destination, shared by every SFTP job
+// in flight to it (each job opens its own SFTP channel). The connection is
+// closed once no running or queued transfer for that destination is left, or
+// once it has sat unused for SSH_IDLE_CLOSE_MS.
+interface SshSession {
+  conn: SSHClient;
+  users: number;
+  // Set once the connection is ending or dropped; never hand it out again
+  closing: boolean;
+  // Pending close of an unused session that queued jobs may still pick up
+  idleTimer?: NodeJS.Timeout;
+}
+const sshSessions = new Map<number, Promise<SshSession>>();
+// How long an unused session waits for a queued job (e.g. while paused) before closing
+const SSH_IDLE_CLOSE_MS = 30_000;
 
-    writeStream.on("error", (err) => {
-      updateJobProgress(job.id, transferred / totalSize, err.message);
-      reject(err);
+function connectSession(dest: Destination, onClose: () => void): Promise<SshSession> {
+  return new Promise((resolve, reject) => {
+    const session: SshSession = { conn: new SSHClient(), users: 0, closing: false };
+    let ready = false;
+    session.conn.on("ready", () => {
+      ready = true;
+      resolve(session);
+    });
+    session.conn.on("error", (connErr) => {
+      if (!ready) reject(classifySshError(connErr));
+    });
+    session.conn.on("close", () => {
+      session.closing = true;
+      onClose();
     });
+    try {
+      session.conn.connect(sshConnectConfig(dest));
+    } catch (configErr) {
+      // Unreadable key file or bad passphrase surfaces synchronously
+      reject(classifySshError(configErr));
+    }
+  });
+}
+
+async function acquireSshSession(dest: Destination): Promise<SshSession> {
+  const pending = sshSessions.get(dest.id);
+  const existing = pending && (await pending.catch(() => null));
+  if (existing && !existing.closing) {
+    clearTimeout(existing.idleTimer);
+    existing.users++;
+    return existing;
+  }
 
-    writeStream.on("finish", () => {
-      updateJobProgress(job.id, 1);
+  const forget = () => {
+    if (sshSessions.get(dest.id) === created) sshSessions.delete(dest.id);
+  };
+  const created = connectSession(dest, forget);
+  sshSessions.set(dest.id, created);
+  created.catch(forget);
 
-      // Save destination path on the job
-      db.update(jobs)
-        .set({
-          destinationId: dest.id,
-          destinationPath: fullDest,
-          updatedAt: new Date().toISOString(),
-        })
-        .where(eq(jobs.id, job.id))
-        .run();
+  const session = await created;
+  session.users++;
+  return session;
+}
 
-      resolve();
-    });
+function closeSshSession(session: SshSession) {
+  session.closing = true;
+  session.conn.end();
+}
 
-    readStream.pipe(writeStream);
-  });
+/**
+ * Drop a job's hold on a session. The last user closes it, unless jobs for
+ * the destination are still queued; then it stays open for them, but only
+ * for so long, since a paused or cleared queue may never get to them.
+ */
+function releaseSshSession(destinationId: number, session: SshSession) {
+  session.users--;
+  if (session.users > 0) return;
+  if (!transferQueue.some((t) => t.destinationId === destinationId)) {
+    closeSshSession(session);
+    return;
+  }
+  clearTimeout(session.idleTimer);
+  session.idleTimer = setTimeout(() => {
+    if (session.users === 0 && !session.closing) closeSshSession(session);
+  }, SSH_IDLE_CLOSE_MS);
+  session.idleTimer.unref();
 }
 
 /**
@@ -158,110 +641,135 @@ async function transferLocal(
  */
 async function transferSFTP(
   job: Job,
-  dest: Destination
+  dest: Destination,
+  restart = false
 ): Promise<void> {
-  const relativePath = buildRelativePath(job);
-  // Use forward slashes for remote path
-  const fullDest = dest.basePath.replace(/\\/g, "/") + "/" + relativePath.replace(/\\/g, "/");
+  const fullDest = remoteDestPath(job, dest);
+  const tempDest = partPath(fullDest);
 
-  return new Promise((resolve, reject) => {
-    const conn = new SSHClient();
+  let session: SshSession;
+  try {
+    session = await acquireSshSession(dest);
+  } catch (err) {
+    const failure = classifySshError(err);
+    updateJobProgress(job.id, 0, failure.message);
+    throw failure;
+  }
+  const { conn } = session;
+
+  try {
+    await new Promise<void>((resolve, reject) => {
+      // Fail the job rather than hang if the shared connection drops mid-copy
+      const onClose = () => {
+        const message = "SSH connection closed during transfer";
+        updateJobProgress(job.id, 0, message);
+        reject(new Error(message));
+      };
+      conn.once("close", onClose);
+      const settle = (err?: Error) => {
+        conn.removeListener("close", onClose);
+        if (err) reject(err);
+        else resolve();
+      };
 
-    conn.on("ready", () => {
       conn.sftp((err, sftp) => {
         if (err) {
           updateJobProgress(job.id, 0, err.message);
-          conn.end();
-          reject(err);
+          settle(err);
           return;
         }
 
-        // Create remote directories
-        const dirs = path.dirname(fullDest).split("/").filter(Boolean);
-        let currentDir = "/";
-        const mkdirRecursive = (index: number) => {
-          if (index >= dirs.length) {
-            doTransfer(sftp);
+        // eslint-disable-next-line @typescript-eslint/no-explicit-any
+        const doTransfer = (sftpStream: any) => {
+          // Resume from a partial upload unless the source changed or resume is off
+          if (restart || !isResumeEnabled()) {
+            startCopy(sftpStream, 0);
             return;
           }
-          currentDir += (currentDir === "/" ? "" : "/") + dirs[index];
-          sftp.mkdir(currentDir, (mkErr) => {
-            // Ignore EEXIST errors
-            mkdirRecursive(index + 1);
+          sftpStream.stat(tempDest, (statErr: Error | undefined, stats: { size: number }) => {
+            const consistent = !statErr && stats.size <= job.fileSize;
+            startCopy(sftpStream, consistent ? stats.size : 0);
           });
         };
 
         // eslint-disable-next-line @typescript-eslint/no-explicit-any
-        const doTransfer = (sftpStream: any) => {
+        const startCopy = (sftpStream: any, offset: number) => {
           const totalSize = job.fileSize;
-          let transferred = 0;
+          let transferred = offset;
 
-          const readStream = fs.createReadStream(job.sourcePath);
-          const writeStream = sftpStream.createWriteStream(fullDest);
+          const readStream = fs.createReadStream(job.sourcePath, {
+            start: offset,
+            highWaterMark: CHUNK_SIZE,
+          });
+          // "r+" writes at the offset without truncating what's already there
+          const writeStream = sftpStream.createWriteStream(tempDest, {
+            flags: offset > 0 ? "r+" : "w",
+            start: offset,
+            highWaterMark: CHUNK_SIZE,
+          });
 
           readStream.on("data", (chunk) => {
             transferred += chunk.length;
-            const progress = Math.min(transferred / totalSize, 1);
+            recordTransferBytes(job.id, transferred);
+            const progress = progressOf(transferred, totalSize);
             updateJobProgress(job.id, progress);
           });
 
           readStream.on("error", (readErr) => {
-            updateJobProgress(job.id, transferred / totalSize, readErr.message);
-            conn.end();
-            reject(readErr);
+            updateJobProgress(job.id, progressOf(transferred, totalSize), readErr.message);
+            sftpStream.end();
+            settle(readErr);
           });
 
           writeStream.on("error", (writeErr: Error) => {
-            updateJobProgress(
-              job.id,
-              transferred / totalSize,
-              writeErr.message
-            );
-            conn.end();
-            reject(writeErr);
+            const message = describeSftpError(writeErr, "write", tempDest);
+            updateJobProgress(job.id, progressOf(transferred, totalSize), message);
+            sftpStream.end();
+            settle(new Error(message));
           });
 
           writeStream.on("close", () => {
-            updateJobProgress(job.id, 1);
-            db.update(jobs)
-              .set({
-                destinationId: dest.id,
-                destinationPath: fullDest,
-                updatedAt: new Date().toISOString(),
-              })
-              .where(eq(jobs.id, job.id))
-              .run();
-            conn.end();
-            resolve();
+            // SFTP rename refuses to overwrite, so clear any old copy first
+            sftpStream.unlink(fullDest, () => {
+              sftpStream.rename(tempDest, fullDest, (renameErr: Error | undefined) => {
+                sftpStream.end();
+                if (renameErr) {
+                  const message = describeSftpError(renameErr, "rename into", fullDest);
+                  updateJobProgress(job.id, progressOf(transferred, totalSize), message);
+                  settle(new Error(message));
+                  return;
+                }
+                updateJobProgress(job.id, 1);
+                db.update(jobs)
+                  .set({
+                    destinationId: dest.id,
+                    destinationPath: fullDest,
+                    updatedAt: new Date().toISOString(),
+                  })
+                  .where(eq(jobs.id, job.id))
+                  .run();
+                settle();
+              });
+            });
           });
 
-          readStream.pipe(writeStream);
+          readStream.pipe(pauseGate()).pipe(writeStream);
         };
 
-        mkdirRecursive(0);
+        // Create remote directories
+        mkdirRemote(sftp, path.dirname(fullDest)).then(
+          () => doTransfer(sftp),
+          (mkErr: Error) => {
+            updateJobProgress(job.id, 0, mkErr.message);
+            sftp.end();
+            settle(mkErr);
+          }
+        );
       });
     });
-
-    conn.on("error", (connErr) => {
-      updateJobProgress(job.id, 0, connErr.message);
-      reject(connErr);
-    });
-
-    const connectConfig: Record<string, unknown> = {
-      host: dest.sshHost!,
-      port: dest.sshPort || 22,
-      username: dest.sshUser!,
-    };
-
-    if (dest.sshKeyPath) {
-      connectConfig.privateKey = fs.readFileSync(dest.sshKeyPath);
-      if (dest.sshKeyPassphrase) {
-        connectConfig.passphrase = dest.sshKeyPassphrase;
-      }
-    }
-
-    conn.connect(connectConfig);
-  });
+  } finally {
+    releaseSshSession(dest.id, session);
+  }
 }
 
 /**
@@ -282,6 +790,21 @@ async function processTransfer(jobId: number, destinationId: number) {
       throw new Error("Job or destination not found");
     }
 
+    // The source may also have been rewritten since the scan
+    const sourceStat = statSource(job.sourcePath);
+    const sizeDrift = sourceStat.size !== job.fileSize;
+    if (sizeDrift) {
+      log.warn(
+        `Size of ${job.sourcePath} changed since scan ` +
+          `(${job.fileSize} -> ${sourceStat.size} bytes); restarting instead of resuming`
+      );
+      db.update(jobs)
+        .set({ fileSize: sourceStat.size, updatedAt: new Date().toISOString() })
+        .where(eq(jobs.id, jobId))
+        .run();
+      job.fileSize = sourceStat.size;
+    }
+
     // Mark as transferring
     db.update(jobs)
       .set({
@@ -292,28 +815,157 @@ async function processTransfer(jobId: number, destinationId: number) {
       })
       .where(eq(jobs.id, jobId))
       .run();
+    recordJobEvent(jobId, "transferring", `Started transfer to ${dest.name}`);
 
     if (dest.type === "ssh") {
-      await transferSFTP(job, dest);
+      await transferSFTP(job, dest, sizeDrift);
     } else {
-      await transferLocal(job, dest);
+      await transferLocal(job, dest, sizeDrift);
     }
   } catch (err) {
     const message = err instanceof Error ? err.message : "Transfer failed";
+    log.warn(`Job failed: ${message}`);
     updateJobProgress(jobId, 0, message);
   }
 
+  if (batch) {
+    const status = db.select({ status: jobs.status }).from(jobs).where(eq(jobs.id, jobId)).get()?.status;
+    if (status === "completed") batch.completed++;
+    else batch.failed++;
+  }
+
+  transferRates.delete(jobId);
   activeTransfers--;
   processQueue();
+  finishBatch();
+}
+
+/**
+ * Check that a local destination's base path exists, is a directory, and is
+ * writable (by creating and removing a probe file). Catches unmounted drives
+ * before any transfer is attempted.
+ */
+export function checkLocalDestination(basePath: string): { ok: boolean; error?: string } {
+  let stat: fs.Stats;
+  try {
+    stat = fs.statSync(basePath);
+  } catch {
+    return { ok: false, error: `Path does not exist: ${basePath}` };
+  }
+  if (!stat.isDirectory()) {
+    return { ok: false, error: `Not a directory: ${basePath}` };
+  }
+
+  const probe = path.join(basePath, `.reelname-write-test-${process.pid}-${Date.now()}`);
+  try {
+    fs.writeFileSync(probe, "");
+    fs.unlinkSync(probe);
+  } catch (err) {
+    const code = (err as NodeJS.ErrnoException).code;
+    return {
+      ok: false,
+      error: `Path is not writable${code ? ` (${code})` : ""}: ${basePath}`,
+    };
+  }
+  return { ok: true };
+}
+
+export type ScriptFormat = "rsync" | "robocopy";
+
+/** Quote a string as a single POSIX shell word. */
+function shellQuote(value: string): string {
+  return `'${value.replace(/'/g, `'\\''`)}'`;
+}
+
+/** Quote a path for cmd.exe batch files (paths can't contain double quotes). */
+function batchQuote(value: string): string {
+  return `"${value.replace(/%/g, "%%")}"`;
+}
+
+/**
+ * Build a shell (rsync) or batch (robocopy) script that copies the given jobs
+ * to their computed destination paths, for users who would rather run the
+ * copy themselves. Directories are created before the first file in them.
+ */
+export function buildTransferScript(
+  jobIds: number[],
+  destinationId: number,
+  format: ScriptFormat
+): { script: string; filename: string; jobCount: number } {
+  const dest = db.select().from(destinations).where(eq(destinations.id, destinationId)).get();
+  if (!dest) throw new Error("Destination not found");
+  if (format === "robocopy" && dest.type !== "local") {
+    throw new Error("robocopy scripts need a local destination");
+  }
+
+  const batch = jobIds.length
+    ? db.select().from(jobs).where(inArray(jobs.id, jobIds)).orderBy(jobs.sourcePath).all()
+    : [];
+  const createdDirs = new Set<string>();
+  const lines: string[] = [];
+
+  if (format === "robocopy") {
+    lines.push("@echo off", `rem ReelName transfer to ${dest.name}`, "");
+    for (const job of batch) {
+      const fullDest = localDestPath(job, dest);
+      const destDir = path.win32.dirname(fullDest);
+      if (!createdDirs.has(destDir)) {
+        createdDirs.add(destDir);
+        lines.push(`if not exist ${batchQuote(destDir)} mkdir ${batchQuote(destDir)}`);
+      }
+      // robocopy keeps the source file name, so rename after copying
+      const srcName = path.win32.basename(job.sourcePath);
+      const destName = path.win32.basename(fullDest);
+      lines.push(
+        `robocopy ${batchQuote(path.win32.dirname(job.sourcePath))} ${batchQuote(destDir)} ${batchQuote(srcName)} /Z /NJH /NJS`
+      );
+      if (srcName !== destName) {
+        lines.push(`move /Y ${batchQuote(path.win32.join(destDir, srcName))} ${batchQuote(fullDest)}`);
+      }
+    }
+    return { script: lines.join("\r\n") + "\r\n", filename: "reelname-transfer.bat", jobCount: batch.length };
+  }
+
+  lines.push("#!/bin/sh", `# ReelName transfer to ${dest.name}`, "set -e", "");
+  const remote = dest.type === "ssh";
+  const sshCommand = remote
+    ? ["ssh", "-p", String(dest.sshPort || 22), ...(dest.sshKeyPath ? ["-i", dest.sshKeyPath] : [])]
+    : [];
+  const sshTarget = remote ? `${dest.sshUser}@${dest.sshHost}` : "";
+
+  for (const job of batch) {
+    const fullDest = remote ? remoteDestPath(job, dest) : localDestPath(job, dest);
+    const destDir = remote ? path.posix.dirname(fullDest) : path.dirname(fullDest);
+    if (!createdDirs.has(destDir)) {
+      createdDirs.add(destDir);
+      if (remote) {
+        // ssh joins its arguments into a string the remote shell parses again
+        lines.push(
+          [...sshCommand.map(shellQuote), shellQuote(sshTarget), "mkdir", "-p", shellQuote(shellQuote(destDir))].join(" ")
+        );
+      } else {
+        lines.push(`mkdir -p ${shellQuote(destDir)}`);
+      }
+    }
+    const target = remote ? `${sshTarget}:${fullDest}` : fullDest;
+    const rsyncArgs = remote
+      ? ["-a", "--partial", "--protect-args", "-e", shellQuote(sshCommand.map(shellQuote).join(" "))]
+      : ["-a", "--partial"];
+    lines.push(`rsync ${rsyncArgs.join(" ")} ${shellQuote(job.sourcePath)} ${shellQuote(target)}`);
+  }
+  return { script: lines.join("\n") + "\n", filename: "reelname-transfer.sh", jobCount: batch.length };
 }
 
 /**
  * Process next items from the queue
  */
 function processQueue() {
-  while (activeTransfers < MAX_CONCURRENT && transferQueue.length > 0) {
+  while (!paused && activeTransfers < MAX_CONCURRENT && transferQueue.length > 0) {
     const next = transferQueue.shift()!;
-    processTransfer(next.jobId, next.destinationId);
+    // Each job runs in its batch's span so its lines carry batchId and jobId
+    withSpan("batch", { batchId: next.batchId, jobId: next.jobId }, () =>
+      processTransfer(next.jobId, next.destinationId)
+    );
   }
 }
 
@@ -324,9 +976,60 @@ export function queueTransfers(
   jobIds: number[],
   destinationId: number
 ): { queued: number } {
+  if (!batch) {
+    batch = { id: nextBatchId++, completed: 0, failed: 0, destinationIds: new Set() };
+    resetPostTransferHookState();
+  }
+  batch.destinationIds.add(destinationId);
+  log.info(`Queued ${jobIds.length} jobs`, { batchId: batch.id, destinationId });
   for (const jobId of jobIds) {
-    transferQueue.push({ jobId, destinationId });
+    transferQueue.push({ jobId, destinationId, batchId: batch.id });
   }
   processQueue();
   return { queued: jobIds.length };
 }
+
+/**
+ * Remove temp files left in local destinations by jobs that were sent before
+ * but will no longer resume (e.g. reset to matched or skipped after failing).
+ * Only those jobs' own temp paths are checked, never a walk of the library,
+ * and a path that can't be built or removed is skipped on its own. SFTP
+ * destinations aren't cleaned here: that would mean connecting to each one on
+ * startup. Runs once when the transfer module is first loaded.
+ */
+async function cleanupStaleParts() {
+  const localDests = db
+    .select()
+    .from(destinations)
+    .all()
+    .filter((d) => d.type === "local");
+  if (localDests.length === 0) return;
+
+  const attempted = db.selectDistinct({ jobId: jobEvents.jobId }).from(jobEvents);
+  const stale = db
+    .select()
+    .from(jobs)
+    .where(
+      and(
+        inArray(jobs.id, attempted),
+        notInArray(jobs.status, [...RESUMABLE_STATUSES, "completed"])
+      )
+    )
+    .all();
+
+  for (const job of stale) {
+    for (const dest of localDests) {
+      try {
+        await fs.promises.unlink(partPath(localDestPath(job, dest)));
+      } catch {
+        // Never written here, already gone, or the destination isn't mounted
+      }
+    }
+  }
+}
+
+if (process.env.NEXT_PHASE !== "phase-production-build") {
+  cleanupStaleParts().catch((err) => {
+    log.error("Failed to clean up stale temp files", { err });
+  });
+}
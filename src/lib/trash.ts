@@ -0,0 +1,94 @@
+import { db } from "@/lib/db";
+import { groups, jobs, matchCandidates } from "@/lib/db/schema";
+import { and, eq, inArray, isNotNull, isNull } from "drizzle-orm";
+
+// Deletes are soft: rows get a deleted_at timestamp and drop out of the list
+// queries. Restoring clears it; emptying the trash removes the rows for real.
+
+/**
+ * Move groups and all of their jobs to the trash. Jobs get the group's
+ * timestamp; ones already trashed on their own keep theirs.
+ */
+export function trashGroups(groupIds: number[]) {
+  if (groupIds.length === 0) return;
+  const now = new Date().toISOString();
+  db.update(groups)
+    .set({ deletedAt: now, updatedAt: now })
+    .where(inArray(groups.id, groupIds))
+    .run();
+  db.update(jobs)
+    .set({ deletedAt: now, updatedAt: now })
+    .where(and(inArray(jobs.groupId, groupIds), isNull(jobs.deletedAt)))
+    .run();
+}
+
+/** Move individual jobs to the trash, leaving their group in place. */
+export function trashJobs(jobIds: number[]) {
+  if (jobIds.length === 0) return;
+  const now = new Date().toISOString();
+  db.update(jobs)
+    .set({ deletedAt: now, updatedAt: now })
+    .where(inArray(jobs.id, jobIds))
+    .run();
+}
+
+/**
+ * Restore groups with the jobs that were trashed along with them (same
+ * timestamp); jobs trashed individually beforehand stay in the trash. For a
+ * live group listed in the trash view, all of its trashed jobs come back.
+ */
+export function restoreGroups(groupIds: number[]) {
+  if (groupIds.length === 0) return;
+  const now = new Date().toISOString();
+  const trashed = db
+    .select({ id: groups.id, deletedAt: groups.deletedAt })
+    .from(groups)
+    .where(inArray(groups.id, groupIds))
+    .all();
+  db.update(groups)
+    .set({ deletedAt: null, updatedAt: now })
+    .where(inArray(groups.id, groupIds))
+    .run();
+  for (const group of trashed) {
+    db.update(jobs)
+      .set({ deletedAt: null, updatedAt: now })
+      .where(
+        and(
+          eq(jobs.groupId, group.id),
+          group.deletedAt ? eq(jobs.deletedAt, group.deletedAt) : isNotNull(jobs.deletedAt)
+        )
+      )
+      .run();
+  }
+}
+
+export function restoreJobs(jobIds: number[]) {
+  if (jobIds.length === 0) return;
+  db.update(jobs)
+    .set({ deletedAt: null, updatedAt: new Date().toISOString() })
+    .where(inArray(jobs.id, jobIds))
+    .run();
+}
+
+/**
+ * Permanently delete trashed rows. With groupIds, only those groups (if
+ * trashed) and their trashed jobs are removed; otherwise the whole trash.
+ */
+export function purgeTrash(groupIds?: number[]): { groups: number; jobs: number } {
+  const groupWhere = groupIds
+    ? and(isNotNull(groups.deletedAt), inArray(groups.id, groupIds))
+    : isNotNull(groups.deletedAt);
+  const jobWhere = groupIds
+    ? and(isNotNull(jobs.deletedAt), inArray(jobs.groupId, groupIds))
+    : isNotNull(jobs.deletedAt);
+
+  const trashedJobIds = db.select({ id: jobs.id }).from(jobs).where(jobWhere).all().map((j) => j.id);
+  if (trashedJobIds.length > 0) {
+    db.delete(matchCandidates).where(inArray(matchCandidates.jobId, trashedJobIds)).run();
+    db.delete(jobs).where(inArray(jobs.id, trashedJobIds)).run();
+  }
+
+  // Cascade delete handles any remaining jobs and candidates
+  const removedGroups = db.delete(groups).where(groupWhere).run().changes;
+  return { groups: removedGroups, jobs: trashedJobIds.length };
+}
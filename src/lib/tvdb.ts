@@ -0,0 +1,95 @@
+import { db } from "@/lib/db";
+import { settings } from "@/lib/db/schema";
+import { eq } from "drizzle-orm";
+
+const TVDB_BASE = "https://api4.thetvdb.com/v4";
+
+// Login tokens are valid for a month; refresh well before that
+const TOKEN_TTL = 24 * 60 * 60 * 1000;
+
+let cachedToken: { apiKey: string; token: string; fetchedAt: number } | null = null;
+
+function getApiKey(): string {
+  const setting = db
+    .select()
+    .from(settings)
+    .where(eq(settings.key, "tvdb_api_key"))
+    .get();
+  return setting?.value || "";
+}
+
+async function getToken(): Promise<string> {
+  const apiKey = getApiKey();
+  if (!apiKey) throw new Error("TVDB API key not configured");
+
+  if (
+    cachedToken &&
+    cachedToken.apiKey === apiKey &&
+    Date.now() - cachedToken.fetchedAt < TOKEN_TTL
+  ) {
+    return cachedToken.token;
+  }
+
+  const res = await fetch(`${TVDB_BASE}/login`, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ apikey: apiKey }),
+  });
+  if (!res.ok) throw new Error(`TVDB login failed: ${res.status}`);
+
+  const data = await res.json();
+  const token: string = data.data?.token;
+  if (!token) throw new Error("TVDB login returned no token");
+
+  cachedToken = { apiKey, token, fetchedAt: Date.now() };
+  return token;
+}
+
+async function tvdbFetch<T>(apiPath: string): Promise<T | null> {
+  const token = await getToken();
+  const res = await fetch(`${TVDB_BASE}${apiPath}`, {
+    headers: { Authorization: `Bearer ${token}` },
+  });
+  if (res.status === 401) {
+    // Token revoked or expired early — force a fresh login next time
+    cachedToken = null;
+  }
+  if (res.status === 404) return null;
+  if (!res.ok) throw new Error(`TVDB API error: ${res.status}`);
+  return res.json();
+}
+
+export interface TvdbEpisode {
+  id: number;
+  name: string | null;
+  number: number;
+  seasonNumber: number;
+  aired: string | null;
+  overview: string | null;
+}
+
+/**
+ * Fetch every episode of a series in the given season ordering
+ * ("default" = aired, "dvd", "absolute"). TVDB pages these 500 at a time.
+ */
+export async function getSeriesEpisodes(
+  tvdbId: number,
+  seasonType = "default",
+  season?: number
+): Promise<TvdbEpisode[]> {
+  const episodes: TvdbEpisode[] = [];
+  for (let page = 0; ; page++) {
+    const params = new URLSearchParams({ page: String(page) });
+    if (season != null) params.set("season", String(season));
+
+    const data = await tvdbFetch<{
+      data: { episodes: TvdbEpisode[] };
+      links?: { next: string | null };
+    }>(`/series/${tvdbId}/episodes/${seasonType}?${params}`);
+    if (!data) break;
+
+    episodes.push(...(data.data?.episodes || []));
+    if (!data.links?.next) break;
+  }
+  return episodes;
+}
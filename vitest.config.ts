@@ -0,0 +1,18 @@
+import fs from "fs";
+import os from "os";
+import path from "path";
+import { defineConfig } from "vitest/config";
+
+export default defineConfig({
+  resolve: {
+    alias: { "@": path.resolve(__dirname, "src") },
+  },
+  test: {
+    include: ["src/**/*.test.ts"],
+    environment: "node",
+    env: {
+      // Modules that open the database or the secret key get a throwaway data dir
+      REELNAME_DATA_DIR: fs.mkdtempSync(path.join(os.tmpdir(), "reelname-test-")),
+    },
+  },
+});